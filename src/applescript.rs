@@ -0,0 +1,82 @@
+/// Escape a string for embedding in an AppleScript double-quoted string.
+/// Newlines (LF, CRLF or a lone CR) are replaced with `" & return & "` so
+/// multi-line text is concatenated properly instead of breaking the string
+/// literal. Tabs become spaces (a literal tab would be typed into the pane
+/// and can trigger completion), and any other control characters are dropped.
+pub fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\" & return & \""),
+            '\r' => {
+                if chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                escaped.push_str("\" & return & \"");
+            }
+            '\t' => escaped.push_str("    "),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Whether `osascript` is on PATH. It ships with macOS but can be missing in
+/// sandboxes, and never exists elsewhere.
+pub fn is_osascript_available() -> bool {
+    crate::util::find_program("osascript").is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape;
+
+    #[test]
+    fn test_escape_quotes_and_backslashes() {
+        assert_eq!(escape(r#"say "hi""#), r#"say \"hi\""#);
+        assert_eq!(escape(r"C:\path\to"), r"C:\\path\\to");
+        assert_eq!(escape(r#"\""#), r#"\\\""#);
+    }
+
+    #[test]
+    fn test_escape_newlines() {
+        assert_eq!(escape("a\nb"), "a\" & return & \"b");
+        assert_eq!(escape("a\r\nb"), "a\" & return & \"b");
+        assert_eq!(escape("a\rb"), "a\" & return & \"b");
+        assert_eq!(
+            escape("a\r\n\r\nb"),
+            "a\" & return & \"\" & return & \"b"
+        );
+    }
+
+    #[test]
+    fn test_escape_tabs_and_control_chars() {
+        assert_eq!(escape("a\tb"), "a    b");
+        assert_eq!(escape("a\u{7}b\u{1b}c"), "abc");
+    }
+
+    #[test]
+    fn test_escape_paths_with_spaces_and_quotes() {
+        assert_eq!(
+            escape("/Users/me/My Projects/app-claude"),
+            "/Users/me/My Projects/app-claude"
+        );
+        assert_eq!(
+            escape(r#"/tmp/it's "quoted"/wt"#),
+            r#"/tmp/it's \"quoted\"/wt"#
+        );
+        assert_eq!(
+            escape(r#"claude --append "x\y""#),
+            r#"claude --append \"x\\y\""#
+        );
+    }
+
+    #[test]
+    fn test_escape_plain_text_unchanged() {
+        assert_eq!(escape("Review this — ok?"), "Review this — ok?");
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -18,12 +19,86 @@ pub struct ProjectConfig {
     /// Optional worktrees path for global configs - where worktrees should be created
     #[serde(default)]
     pub worktrees_path: Option<PathBuf>,
+    /// How the ultrathink hint is joined to a prompt sent via `mai send`
+    #[serde(default)]
+    pub ultrathink_separator: UltrathinkSeparator,
+    /// Optional directory for per-app transcripts of the AI panes (tmux modes only)
+    #[serde(default)]
+    pub log_dir: Option<String>,
+    /// Also put text sent via `mai send` on the tmux paste buffer stack and system clipboard
+    #[serde(default)]
+    pub copy_sent_text: bool,
+    /// Pane index within each column (0 is the AI pane) that `mai send` targets in
+    /// Command mode; defaults to the bottom pane
+    #[serde(default)]
+    pub command_pane: Option<usize>,
+    /// Narrowest iTerm2 column (in characters) an AI TUI still renders well in
+    #[serde(default)]
+    pub min_pane_width: Option<usize>,
+    /// Most app columns per iTerm2 window; more apps are spread over several windows
+    #[serde(default)]
+    pub max_columns_per_window: Option<usize>,
+    /// How Ctrl+O in `mai send` inserts a file into the prompt
+    #[serde(default)]
+    pub file_reference: FileReferenceStyle,
+    /// Percentage of an app's space its AI pane gets next to the shell pane (tmux only);
+    /// an even split when unset
+    #[serde(default)]
+    pub pane_split_ratio: Option<u8>,
+    /// Function key (`"F1"` to `"F12"`) that toggles the monitor view in `mai send`;
+    /// F2 when unset
+    #[serde(default)]
+    pub monitor_key: Option<String>,
+    /// Dotenv-style file whose variables are exported in every AI pane before its
+    /// command runs; relative to `project_path`
+    #[serde(default)]
+    pub env_file: Option<String>,
+    /// Whether `mai remove` also kills the prefix's tmux session; true when unset
+    #[serde(default)]
+    pub remove_kills_session: Option<bool>,
+    /// Milliseconds tmux waits for a new pane's shell before typing the app's command;
+    /// 500 when unset
+    #[serde(default)]
+    pub pane_init_delay_ms: Option<u64>,
+    /// Milliseconds the iTerm2 layout script waits for each new pane's shell (twice
+    /// that for the first pane of a tab or window); 1000 when unset
+    #[serde(default)]
+    pub iterm2_pane_delay_ms: Option<u64>,
+    /// Name or slug of the app whose tmux window or column is selected when a session is
+    /// created; the first app when unset or not found
+    #[serde(default)]
+    pub focus_app: Option<String>,
+    /// Settings for `mai review`
+    #[serde(default)]
+    pub review: ReviewConfig,
+}
+
+/// The `review` section of the config
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ReviewConfig {
+    /// Whether fetching branches for the picker passes `--prune`, which drops tracking
+    /// refs of branches deleted upstream; true when unset
+    #[serde(default)]
+    pub fetch_prune: Option<bool>,
+}
+
+impl ReviewConfig {
+    pub fn fetch_prune(&self) -> bool {
+        self.fetch_prune.unwrap_or(true)
+    }
 }
 
 fn default_terminals_per_column() -> usize {
     2
 }
 
+/// Text columns assumed for an iTerm2 window, which AppleScript can't easily report.
+/// Roughly a maximized window on a laptop screen at the default font size.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const ASSUMED_ITERM2_WINDOW_WIDTH: usize = 240;
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const DEFAULT_MIN_PANE_WIDTH: usize = 60;
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum TmuxLayout {
@@ -31,7 +106,7 @@ pub enum TmuxLayout {
     SingleWindow,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "kebab-case")]
 pub enum Mode {
     Iterm2,
@@ -50,10 +125,71 @@ impl Mode {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum UltrathinkSeparator {
+    /// Hint goes on its own paragraph after a blank line
+    #[default]
+    BlankLine,
+    /// Hint is appended on the same line after a space
+    Space,
+    /// Hint is appended directly with nothing in between
+    None,
+}
+
+impl UltrathinkSeparator {
+    /// Append the ultrathink hint to a prompt using this separator
+    pub fn join(self, prompt: &str, hint: &str) -> String {
+        let separator = match self {
+            UltrathinkSeparator::BlankLine => "\n\n",
+            UltrathinkSeparator::Space => " ",
+            UltrathinkSeparator::None => "",
+        };
+        format!("{}{}{}", prompt, separator, hint)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileReferenceStyle {
+    /// `@path`, for agents that read mentioned files themselves
+    #[default]
+    AtPath,
+    /// The file's contents in a fenced code block
+    Inline,
+}
+
+impl FileReferenceStyle {
+    pub fn toggled(self) -> Self {
+        match self {
+            FileReferenceStyle::AtPath => FileReferenceStyle::Inline,
+            FileReferenceStyle::Inline => FileReferenceStyle::AtPath,
+        }
+    }
+}
+
+/// What an app's column runs in its top pane
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AppKind {
+    /// An AI agent; the target of prompts
+    #[default]
+    Ai,
+    /// An editor on the worktree; an empty `command` opens `$EDITOR .`
+    Editor,
+    /// Just a shell in the worktree; `command` is not run
+    Shell,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AiApp {
     pub name: String,
+    /// Optional for `editor` and `shell` apps
+    #[serde(default)]
     pub command: String,
+    /// Per-mode replacements for `command`, e.g. `{"iterm2": "claude --chrome"}`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub commands: BTreeMap<Mode, String>,
     #[serde(default)]
     pub slug: Option<String>,
     #[serde(default)]
@@ -62,6 +198,19 @@ pub struct AiApp {
     pub default: bool,
     #[serde(default)]
     pub meta_review: bool,
+    /// The agent submits on the first Enter, so no extra submit keystroke is sent after a prompt
+    #[serde(default)]
+    pub auto_submit: bool,
+    #[serde(default)]
+    pub kind: AppKind,
+    /// Launch the AI command in this directory under the worktree root (e.g. `packages/web`);
+    /// `cwd` is accepted as another name for it
+    #[serde(default, alias = "cwd")]
+    pub subdir: Option<String>,
+    /// Side-by-side columns the app gets in a shared window (tmux single-window and
+    /// iTerm2); the app runs in the first and the rest are shells. 1 when unset
+    #[serde(default)]
+    pub columns: Option<usize>,
     #[serde(default)]
     pub description: Option<String>,
 }
@@ -71,22 +220,175 @@ impl AiApp {
         &self.name
     }
 
-    pub fn command(&self) -> &str {
-        &self.command
-    }
-
     pub fn ultrathink(&self) -> Option<&str> {
         self.ultrathink.as_deref()
     }
 
+    /// Directory the AI command starts in: the worktree root, or `subdir` below it
+    pub fn launch_dir(&self, worktree_path: &str) -> String {
+        match self.subdir.as_deref().map(|s| s.trim_matches('/')) {
+            Some(subdir) if !subdir.is_empty() => {
+                format!("{}/{}", worktree_path.trim_end_matches('/'), subdir)
+            }
+            _ => worktree_path.to_string(),
+        }
+    }
+
+    /// How many columns the app takes in a shared window
+    pub fn columns(&self) -> usize {
+        self.columns.unwrap_or(1)
+    }
+
+    /// Whether the app is an AI agent that prompts are sent to
+    pub fn is_ai(&self) -> bool {
+        self.kind == AppKind::Ai
+    }
+
+    /// The command to launch in `mode`: its `commands` entry, else `command`
+    pub fn command_for(&self, mode: &Mode) -> &str {
+        self.commands.get(mode).unwrap_or(&self.command)
+    }
+
+    /// Shell line typed into the app's top pane: `cd` to the launch dir, then start it
+    pub fn launch_line(&self, worktree_path: &str, mode: &Mode) -> String {
+        let launch_dir = self.launch_dir(worktree_path);
+        let dir = shell_quote(&launch_dir);
+        let command = self.command_for(mode);
+        match self.kind {
+            AppKind::Shell => format!("cd {}", dir),
+            AppKind::Editor if command.trim().is_empty() => {
+                format!("cd {} && ${{EDITOR:-vi}} .", dir)
+            }
+            AppKind::Ai | AppKind::Editor => format!("cd {} && {}", dir, command),
+        }
+    }
+
     /// Return a git-safe slug for use in branch names and worktree directories.
-    /// Uses the explicit `slug` field if set, otherwise auto-generates from the command.
+    /// Uses the explicit `slug` field if set, otherwise auto-generates from the command
+    /// (or the name, for apps without one).
     pub fn slug(&self) -> String {
         if let Some(ref s) = self.slug {
             return s.clone();
         }
+        if self.command.trim().is_empty() {
+            return slugify_command(&self.name);
+        }
         slugify_command(&self.command)
     }
+
+    /// Return a copy that launches with `words` appended to the command (one run only).
+    /// Each word (see `split_shell_words`) is shell-quoted; the slug is pinned so branch
+    /// names stay the same as without the extra args.
+    pub fn with_extra_args(&self, words: &[String]) -> AiApp {
+        let mut app = self.clone();
+        app.slug = Some(self.slug());
+        for command in std::iter::once(&mut app.command).chain(app.commands.values_mut()) {
+            for word in words {
+                command.push(' ');
+                command.push_str(&shell_quote(word));
+            }
+        }
+        app
+    }
+
+    /// Return a copy that exports `vars` before running its command, in every mode.
+    /// Only AI apps are changed; the slug is pinned so branch names stay the same.
+    pub fn with_env(&self, vars: &[(String, String)]) -> AiApp {
+        if !self.is_ai() || vars.is_empty() {
+            return self.clone();
+        }
+        let exports: Vec<String> = vars
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, shell_quote(value)))
+            .collect();
+        let mut app = self.clone();
+        app.slug = Some(self.slug());
+        for command in std::iter::once(&mut app.command).chain(app.commands.values_mut()) {
+            *command = format!("export {} && {}", exports.join(" "), command);
+        }
+        app
+    }
+
+    /// Return a copy that launches with `command` instead of the configured one (one
+    /// run only), in every mode. The slug is pinned so branch names don't change.
+    pub fn with_command(&self, command: &str) -> AiApp {
+        let mut app = self.clone();
+        app.slug = Some(self.slug());
+        app.command = command.to_string();
+        app.commands.clear();
+        app
+    }
+}
+
+/// Quote a single shell word with single quotes unless it only contains safe characters.
+pub(crate) fn shell_quote(token: &str) -> std::borrow::Cow<'_, str> {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "-_./=:@,+%".contains(c);
+    if !token.is_empty() && token.chars().all(is_safe) {
+        std::borrow::Cow::Borrowed(token)
+    } else {
+        std::borrow::Cow::Owned(format!("'{}'", token.replace('\'', "'\\''")))
+    }
+}
+
+/// Split `text` into words the way a POSIX shell does, without expanding anything.
+/// Whitespace separates words. Single quotes keep their contents literally. Double
+/// quotes keep whitespace and take `\"`, `\\`, `\$` and `` \` `` escapes. Outside quotes
+/// a backslash escapes the next character.
+pub(crate) fn split_shell_words(text: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(format!("unterminated single quote in '{}'", text)),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err(format!("unterminated double quote in '{}'", text)),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err(format!("unterminated double quote in '{}'", text)),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                word.push(chars.next().unwrap_or('\\'));
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
 }
 
 /// Turn a command string into a git-safe branch-name component.
@@ -169,77 +471,221 @@ pub(crate) fn slugify_command(command: &str) -> String {
     result.trim_end_matches('-').to_string()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::slugify_command;
-
-    #[test]
-    fn test_slugify_basic_commands() {
-        assert_eq!(slugify_command("claude"), "claude");
-        assert_eq!(slugify_command("gemini"), "gemini");
-        assert_eq!(slugify_command("codex"), "codex");
-    }
-
-    #[test]
-    fn test_slugify_yolo_variants() {
-        assert_eq!(slugify_command("gemini --yolo"), "gemini-yolo");
-        assert_eq!(slugify_command("codex --yolo"), "codex-yolo");
-        assert_eq!(
-            slugify_command("claude --dangerously-skip-permissions"),
-            "claude-yolo"
-        );
-        assert_eq!(
-            slugify_command("amp --dangerously-allow-all"),
-            "amp-yolo"
-        );
-        assert_eq!(
-            slugify_command("copilot --allow-all-tools"),
-            "copilot-yolo"
-        );
-        assert_eq!(
-            slugify_command("cursor-agent --force"),
-            "cursor-agent-force"
-        );
-    }
-
-    #[test]
-    fn test_slugify_permission_mode() {
-        assert_eq!(
-            slugify_command("claude --permission-mode plan --allow-dangerously-skip-permissions"),
-            "claude-plan-yolo"
-        );
-    }
+/// The process's working directory, where config discovery starts. Fails with a hint
+/// when it was deleted out from under us, e.g. a worktree removed while standing in it.
+pub fn current_dir() -> crate::error::Result<PathBuf> {
+    std::env::current_dir().map_err(current_dir_error)
+}
 
-    #[test]
-    fn test_slugify_model_variants() {
-        assert_eq!(
-            slugify_command("codex --yolo --model gpt-5.3-codex --config model_reasoning_effort='high'"),
-            "codex-yolo-gpt-5.3-codex"
-        );
-        assert_eq!(
-            slugify_command("codex --yolo --model gpt-5.1 --config model_reasoning_effort='high'"),
-            "codex-yolo-gpt-5.1"
-        );
-    }
+fn current_dir_error(e: std::io::Error) -> crate::error::MultiAiError {
+    let hint = if e.kind() == std::io::ErrorKind::NotFound {
+        ". Your current directory may have been deleted; cd to a valid directory and retry"
+    } else {
+        ""
+    };
+    crate::error::MultiAiError::Config(format!("Failed to get current directory: {}{}", e, hint))
 }
 
 /// Result of finding a config file
 /// Contains: (config_file_path, parsed_config, effective_project_path)
 pub type ConfigFindResult = (PathBuf, ProjectConfig, PathBuf);
 
+/// Environment variable holding an inline JSON/JSONC config for ephemeral runs.
+/// When set, it takes precedence over any config file in ~/.config/multi-ai-cli/.
+pub const CONFIG_ENV_VAR: &str = "MAI_CONFIG_JSON";
+
+/// gwt's project config, which can carry the mai config in its `multi_ai` section
+pub const GWT_CONFIG_FILE: &str = "git-worktree-config.jsonc";
+pub const GWT_CONFIG_SECTION: &str = "multi_ai";
+
 impl ProjectConfig {
     pub fn from_json(content: &str) -> anyhow::Result<Self> {
-        // Parse JSONC (JSON with Comments) which also handles regular JSON
-        let mut parsed = jsonc_parser::parse_to_serde_value(content, &Default::default())?
-            .ok_or_else(|| anyhow::anyhow!("Failed to parse JSON/JSONC content"))?;
+        Self::from_value(parse_jsonc(content)?)
+    }
+
+    /// The config in the `multi_ai` section of a gwt config, or None when it has none
+    pub fn from_gwt_json(content: &str) -> anyhow::Result<Option<Self>> {
+        match parse_jsonc(content)? {
+            serde_json::Value::Object(mut map) => match map.remove(GWT_CONFIG_SECTION) {
+                Some(section) => Self::from_value(section).map(Some),
+                None => Ok(None),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Read a config file, applying its `{name}.local.jsonc` sibling on top when present.
+    pub fn from_file(config_path: &Path) -> anyhow::Result<Self> {
+        let mut parsed = parse_jsonc(&fs::read_to_string(config_path)?)?;
+        let local_path = local_config_path(config_path);
+        if local_path.exists() {
+            let local = parse_jsonc(&fs::read_to_string(&local_path)?)
+                .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", local_path.display(), e))?;
+            merge_config_values(&mut parsed, local);
+        }
+        Self::from_value(parsed)
+    }
 
+    fn from_value(mut parsed: serde_json::Value) -> anyhow::Result<Self> {
         // For backward compatibility with configs that don't have the optional `mode` field.
         // Inject `null` so serde will populate `None` rather than erroring.
         if let serde_json::Value::Object(ref mut map) = parsed {
             map.entry("mode").or_insert(serde_json::Value::Null);
         }
 
-        Ok(serde_json::from_value(parsed)?)
+        let config: Self = serde_json::from_value(parsed)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Env file to load: `override_path` (from `--env-file`) resolved against
+    /// `current_dir` like any other command-line path, else `env_file` resolved
+    /// against `project_path`
+    fn env_file_path(
+        &self,
+        project_path: &Path,
+        current_dir: &Path,
+        override_path: Option<&Path>,
+    ) -> Option<PathBuf> {
+        match (override_path, &self.env_file) {
+            (Some(path), _) => Some(current_dir.join(path)),
+            (None, Some(file)) => Some(project_path.join(shellexpand::tilde(file).as_ref())),
+            (None, None) => None,
+        }
+    }
+
+    /// Variables from the env file (see `env_file_path`). A file passed with
+    /// `--env-file` has to be readable; a missing `env_file` only prints a warning.
+    pub fn env_vars(
+        &self,
+        project_path: &Path,
+        override_path: Option<&Path>,
+    ) -> crate::error::Result<Vec<(String, String)>> {
+        let Some(path) = self.env_file_path(project_path, &current_dir()?, override_path) else {
+            return Ok(Vec::new());
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) => Ok(parse_env_file(&content)),
+            Err(e) if override_path.is_some() => Err(crate::error::MultiAiError::Config(format!(
+                "Failed to read env file {}: {}",
+                path.display(),
+                e
+            ))),
+            Err(e) => {
+                eprintln!("Warning: env file {} not loaded: {}", path.display(), e);
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    /// Number of the function key that toggles the `mai send` monitor view
+    pub fn monitor_key(&self) -> u8 {
+        self.monitor_key
+            .as_deref()
+            .and_then(parse_function_key)
+            .unwrap_or(2)
+    }
+
+    /// Whether `mai remove` kills the tmux session along with the worktrees
+    pub fn remove_kills_session(&self) -> bool {
+        self.remove_kills_session.unwrap_or(true)
+    }
+
+    pub fn pane_init_delay(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.pane_init_delay_ms.unwrap_or(500))
+    }
+
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub fn iterm2_pane_delay(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.iterm2_pane_delay_ms.unwrap_or(1000))
+    }
+
+    /// Reject values the layout code can't handle
+    pub fn validate(&self) -> crate::error::Result<()> {
+        if self.terminals_per_column < 1 {
+            return Err(crate::error::MultiAiError::Config(format!(
+                "terminals_per_column must be at least 1 (got {})",
+                self.terminals_per_column
+            )));
+        }
+        if let Some(ratio) = self.pane_split_ratio
+            && !(10..=90).contains(&ratio)
+        {
+            return Err(crate::error::MultiAiError::Config(format!(
+                "pane_split_ratio must be between 10 and 90 (percent for the AI pane), got {}",
+                ratio
+            )));
+        }
+        if self.min_pane_width == Some(0) {
+            return Err(crate::error::MultiAiError::Config(
+                "min_pane_width must be at least 1".to_string(),
+            ));
+        }
+        if self.max_columns_per_window == Some(0) {
+            return Err(crate::error::MultiAiError::Config(
+                "max_columns_per_window must be at least 1".to_string(),
+            ));
+        }
+        if let Some(key) = &self.monitor_key
+            && parse_function_key(key).is_none()
+        {
+            return Err(crate::error::MultiAiError::Config(format!(
+                "monitor_key must be a function key from F1 to F12, got '{}'",
+                key
+            )));
+        }
+        // Branch names are `{prefix}-{slug}`, so a repeated name or slug collides there
+        for (i, app) in self.ai_apps.iter().enumerate() {
+            let earlier = &self.ai_apps[..i];
+            if earlier.iter().any(|a| a.name == app.name) {
+                return Err(crate::error::MultiAiError::Config(format!(
+                    "Duplicate app name: {} (use distinct names like {}-a and {}-b)",
+                    app.name, app.name, app.name
+                )));
+            }
+            if let Some(other) = earlier.iter().find(|a| a.slug() == app.slug()) {
+                return Err(crate::error::MultiAiError::Config(format!(
+                    "Apps '{}' and '{}' both use branch suffix '{}'; set a distinct slug on one of them",
+                    other.name,
+                    app.name,
+                    app.slug()
+                )));
+            }
+        }
+        if let Some(app) = self.ai_apps.iter().find(|app| app.columns == Some(0)) {
+            return Err(crate::error::MultiAiError::Config(format!(
+                "ai_apps entry '{}' needs at least 1 column (got columns: 0)",
+                app.name
+            )));
+        }
+        if let Some(app) = self
+            .ai_apps
+            .iter()
+            .find(|app| app.is_ai() && app.command.trim().is_empty())
+        {
+            return Err(crate::error::MultiAiError::Config(format!(
+                "ai_apps entry '{}' needs a command (or a kind of \"editor\" or \"shell\")",
+                app.name
+            )));
+        }
+        if let Some(index) = self.command_pane
+            && (index < 1 || index >= self.terminals_per_column)
+        {
+            return Err(crate::error::MultiAiError::Config(format!(
+                "command_pane must be between 1 and {} (terminals_per_column - 1), got {}",
+                self.terminals_per_column.saturating_sub(1),
+                index
+            )));
+        }
+        Ok(())
+    }
+
+    /// How many app columns fit side by side in an iTerm2 tab before they get narrower
+    /// than `min_pane_width`, assuming a typical window width
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub fn iterm2_max_columns(&self) -> usize {
+        let min_width = self.min_pane_width.unwrap_or(DEFAULT_MIN_PANE_WIDTH).max(1);
+        (ASSUMED_ITERM2_WINDOW_WIDTH / min_width).max(1)
     }
 
     /// Returns the config directory (~/.config/multi-ai-cli)
@@ -252,12 +698,24 @@ impl ProjectConfig {
     /// Find a config file in ~/.config/multi-ai-cli/.
     ///
     /// Search order:
+    /// 0. Inline config from the MAI_CONFIG_JSON environment variable (no file needed)
+    /// 0b. `multi_ai` section of the nearest git-worktree-config.jsonc above `start_dir`
     /// 1. Git remote URL -> generate filename -> look up ~/.config/multi-ai-cli/{filename}.jsonc
     /// 2. Fallback: scan all .jsonc files for matching project_path or worktrees_path
     /// 3. Legacy: check deprecated ~/.config/multi-ai-cli/projects/ subdirectory
     ///
     /// Returns: (config_file_path, parsed_config, effective_project_path)
     pub fn find_config(start_dir: &Path) -> anyhow::Result<Option<ConfigFindResult>> {
+        if let Ok(content) = std::env::var(CONFIG_ENV_VAR)
+            && !content.trim().is_empty()
+        {
+            return Self::from_env_content(&content, start_dir).map(Some);
+        }
+
+        if let Some(result) = Self::find_config_in_gwt(start_dir)? {
+            return Ok(Some(result));
+        }
+
         let config_dir = match Self::config_dir() {
             Ok(dir) => dir,
             Err(_) => return Ok(None),
@@ -297,6 +755,71 @@ impl ProjectConfig {
         Ok(None)
     }
 
+    /// Path of the config file for `start_dir`, for editing it. A config named after
+    /// the git remote is found even when it no longer parses; one that is only matched
+    /// by its `project_path` or `worktrees_path` has to parse to be found.
+    pub fn find_config_file(start_dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+        if let Some(gwt_path) = gwt_config_with_section(start_dir) {
+            return Ok(Some(gwt_path));
+        }
+        let config_dir = Self::config_dir()?;
+        if let Some(repo_url) = git::get_remote_origin_url(start_dir) {
+            let config_filename = format!("{}.jsonc", git::generate_config_filename(&repo_url));
+            for dir in [config_dir.clone(), config_dir.join("projects")] {
+                let config_path = dir.join(&config_filename);
+                if config_path.exists() {
+                    return Ok(Some(config_path));
+                }
+            }
+        }
+
+        Ok(Self::find_config(start_dir)?.map(|(config_path, _, _)| config_path))
+    }
+
+    /// Build a config from the inline MAI_CONFIG_JSON content.
+    /// `project_path` is optional here and defaults to the repo containing `start_dir`.
+    fn from_env_content(content: &str, start_dir: &Path) -> anyhow::Result<ConfigFindResult> {
+        let mut config = Self::from_json(content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", CONFIG_ENV_VAR, e))?;
+
+        let project_path = match config.project_path.clone() {
+            Some(path) => path,
+            None => git::get_repo_root(start_dir).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{} has no 'project_path' and the current directory is not inside a git repository.",
+                    CONFIG_ENV_VAR
+                )
+            })?,
+        };
+        config.project_path = Some(project_path.clone());
+
+        Ok((PathBuf::from(format!("${}", CONFIG_ENV_VAR)), config, project_path))
+    }
+
+    /// Read the config from the `multi_ai` section of the nearest gwt config above
+    /// `start_dir`. `project_path` defaults to the directory holding that file.
+    fn find_config_in_gwt(start_dir: &Path) -> anyhow::Result<Option<ConfigFindResult>> {
+        let Some(gwt_path) = nearest_gwt_config(start_dir) else {
+            return Ok(None);
+        };
+
+        let content = fs::read_to_string(&gwt_path)?;
+        let Some(mut config) = Self::from_gwt_json(&content).map_err(|e| {
+            anyhow::anyhow!("Failed to parse '{}' in {}: {}", GWT_CONFIG_SECTION, gwt_path.display(), e)
+        })?
+        else {
+            return Ok(None);
+        };
+
+        let project_path = match config.project_path.clone() {
+            Some(path) => path,
+            None => gwt_path.parent().map(Path::to_path_buf).unwrap_or_default(),
+        };
+        config.project_path = Some(project_path.clone());
+
+        Ok(Some((gwt_path, config, project_path)))
+    }
+
     /// Find config by generating filename from git remote URL.
     fn find_config_by_url(
         start_dir: &Path,
@@ -314,8 +837,7 @@ impl ProjectConfig {
             return Ok(None);
         }
 
-        let content = fs::read_to_string(&config_path)?;
-        let config = Self::from_json(&content)?;
+        let config = Self::from_file(&config_path)?;
 
         if config.project_path.is_none() {
             anyhow::bail!(
@@ -340,13 +862,10 @@ impl ProjectConfig {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().map(|e| e == "jsonc").unwrap_or(false) {
-                let content = match fs::read_to_string(&path) {
-                    Ok(c) => c,
-                    Err(_) => continue,
-                };
-
-                let config = match Self::from_json(&content) {
+            if path.extension().map(|e| e == "jsonc").unwrap_or(false)
+                && !is_local_config(&path)
+            {
+                let config = match Self::from_file(&path) {
                     Ok(c) => c,
                     Err(_) => continue,
                 };
@@ -382,3 +901,590 @@ impl ProjectConfig {
         Ok(None)
     }
 }
+
+/// `KEY=value` pairs from dotenv-style content. Blank lines, `#` comments and an
+/// `export ` prefix are allowed. Values may be single-quoted (taken literally) or
+/// double-quoted (`\n`, `\"` and `\\` escapes); unquoted values end at ` #`.
+/// Later assignments win; lines that aren't assignments are skipped.
+pub fn parse_env_file(content: &str) -> Vec<(String, String)> {
+    let mut vars: Vec<(String, String)> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let valid_key = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_key {
+            continue;
+        }
+        let value = value.trim();
+        let value = if let Some(quoted) = value.strip_prefix('\'') {
+            quoted.split('\'').next().unwrap_or_default().to_string()
+        } else if let Some(quoted) = value.strip_prefix('"') {
+            let mut out = String::new();
+            let mut chars = quoted.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => match chars.next() {
+                        Some('n') => out.push('\n'),
+                        Some(other) => out.push(other),
+                        None => break,
+                    },
+                    c => out.push(c),
+                }
+            }
+            out
+        } else {
+            value.split(" #").next().unwrap_or_default().trim_end().to_string()
+        };
+        vars.retain(|(existing, _)| existing != key);
+        vars.push((key.to_string(), value));
+    }
+    vars
+}
+
+/// `"F5"` (or `"f5"`) -> 5, for F1 to F12
+fn parse_function_key(key: &str) -> Option<u8> {
+    let number: u8 = key.strip_prefix(['F', 'f'])?.parse().ok()?;
+    (1..=12).contains(&number).then_some(number)
+}
+
+/// git-worktree-config.jsonc in `start_dir` or the closest ancestor that has one
+fn nearest_gwt_config(start_dir: &Path) -> Option<PathBuf> {
+    start_dir
+        .ancestors()
+        .map(|dir| dir.join(GWT_CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+/// The nearest gwt config above `start_dir` when it has a `multi_ai` section, valid
+/// or not, so `mai config edit` can open it to fix it
+fn gwt_config_with_section(start_dir: &Path) -> Option<PathBuf> {
+    let gwt_path = nearest_gwt_config(start_dir)?;
+    let parsed = parse_jsonc(&fs::read_to_string(&gwt_path).ok()?).ok()?;
+    parsed.get(GWT_CONFIG_SECTION).is_some().then_some(gwt_path)
+}
+
+/// Suffix of the per-machine override file that sits next to a project config.
+pub const LOCAL_CONFIG_SUFFIX: &str = ".local.jsonc";
+
+/// `foo.jsonc` -> `foo.local.jsonc`
+pub fn local_config_path(config_path: &Path) -> PathBuf {
+    let stem = config_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    config_path.with_file_name(format!("{}{}", stem, LOCAL_CONFIG_SUFFIX))
+}
+
+fn is_local_config(path: &Path) -> bool {
+    path.file_name()
+        .map(|n| n.to_string_lossy().ends_with(LOCAL_CONFIG_SUFFIX))
+        .unwrap_or(false)
+}
+
+fn parse_jsonc(content: &str) -> anyhow::Result<serde_json::Value> {
+    // Parse JSONC (JSON with Comments) which also handles regular JSON
+    jsonc_parser::parse_to_serde_value(content, &Default::default())?
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse JSON/JSONC content"))
+}
+
+/// Merge a local override into the base config. Objects merge key by key and
+/// anything else in `local` replaces the base value, except `ai_apps`, whose
+/// entries merge with the base app of the same `name` and are appended otherwise.
+fn merge_config_values(base: &mut serde_json::Value, local: serde_json::Value) {
+    use serde_json::Value;
+
+    let (Value::Object(base_map), Value::Object(local_map)) = (&mut *base, &local) else {
+        *base = local;
+        return;
+    };
+    for (key, local_value) in local_map {
+        match (key.as_str(), base_map.get_mut(key), local_value) {
+            ("ai_apps", Some(Value::Array(base_apps)), Value::Array(local_apps)) => {
+                for local_app in local_apps {
+                    let name = local_app.get("name").and_then(Value::as_str);
+                    let existing = name.and_then(|name| {
+                        base_apps
+                            .iter_mut()
+                            .find(|app| app.get("name").and_then(Value::as_str) == Some(name))
+                    });
+                    match existing {
+                        Some(app) => merge_json(app, local_app.clone()),
+                        None => base_apps.push(local_app.clone()),
+                    }
+                }
+            }
+            (_, Some(base_value), _) => merge_json(base_value, local_value.clone()),
+            (_, None, _) => {
+                base_map.insert(key.clone(), local_value.clone());
+            }
+        }
+    }
+}
+
+fn merge_json(base: &mut serde_json::Value, local: serde_json::Value) {
+    match (base, local) {
+        (serde_json::Value::Object(base_map), serde_json::Value::Object(local_map)) => {
+            for (key, value) in local_map {
+                match base_map.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        base_map.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, local) => *base = local,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        current_dir_error, gwt_config_with_section, local_config_path, merge_config_values,
+        parse_env_file, shell_quote, slugify_command, split_shell_words, AiApp, AppKind, Mode, ProjectConfig,
+        UltrathinkSeparator, GWT_CONFIG_FILE,
+    };
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    fn app(name: &str) -> AiApp {
+        serde_json::from_value(serde_json::json!({"name": name, "command": name})).unwrap()
+    }
+
+    #[test]
+    fn test_slugify_basic_commands() {
+        assert_eq!(slugify_command("claude"), "claude");
+        assert_eq!(slugify_command("gemini"), "gemini");
+        assert_eq!(slugify_command("codex"), "codex");
+    }
+
+    #[test]
+    fn test_slugify_yolo_variants() {
+        assert_eq!(slugify_command("gemini --yolo"), "gemini-yolo");
+        assert_eq!(slugify_command("codex --yolo"), "codex-yolo");
+        assert_eq!(
+            slugify_command("claude --dangerously-skip-permissions"),
+            "claude-yolo"
+        );
+        assert_eq!(
+            slugify_command("amp --dangerously-allow-all"),
+            "amp-yolo"
+        );
+        assert_eq!(
+            slugify_command("copilot --allow-all-tools"),
+            "copilot-yolo"
+        );
+        assert_eq!(
+            slugify_command("cursor-agent --force"),
+            "cursor-agent-force"
+        );
+    }
+
+    #[test]
+    fn test_slugify_permission_mode() {
+        assert_eq!(
+            slugify_command("claude --permission-mode plan --allow-dangerously-skip-permissions"),
+            "claude-plan-yolo"
+        );
+    }
+
+    #[test]
+    fn test_slugify_model_variants() {
+        assert_eq!(
+            slugify_command("codex --yolo --model gpt-5.3-codex --config model_reasoning_effort='high'"),
+            "codex-yolo-gpt-5.3-codex"
+        );
+        assert_eq!(
+            slugify_command("codex --yolo --model gpt-5.1 --config model_reasoning_effort='high'"),
+            "codex-yolo-gpt-5.1"
+        );
+    }
+
+    #[test]
+    fn test_from_env_content_uses_inline_project_path() {
+        let (config_path, config, project_path) = ProjectConfig::from_env_content(
+            r#"{ "project_path": "/tmp/my-project", "mode": "tmux-single-window" } // inline"#,
+            Path::new("/"),
+        )
+        .unwrap();
+        assert_eq!(config_path, PathBuf::from("$MAI_CONFIG_JSON"));
+        assert_eq!(project_path, PathBuf::from("/tmp/my-project"));
+        assert_eq!(config.mode, Some(Mode::TmuxSingleWindow));
+        assert_eq!(config.terminals_per_column, 2);
+        assert_eq!(config.ultrathink_separator, UltrathinkSeparator::BlankLine);
+    }
+
+    #[test]
+    fn test_config_from_gwt_section() {
+        let root = std::env::temp_dir().join(format!("mai-gwt-config-{}", std::process::id()));
+        let worktree = root.join("feat-claude");
+        fs::create_dir_all(&worktree).unwrap();
+        fs::write(
+            root.join(GWT_CONFIG_FILE),
+            r#"{
+                "repositoryUrl": "git@github.com:me/repo.git",
+                // mai's settings live alongside gwt's
+                "multi_ai": { "mode": "tmux-single-window", "ai_apps": [{"name": "claude", "command": "claude"}] }
+            }"#,
+        )
+        .unwrap();
+
+        let (config_path, config, project_path) =
+            ProjectConfig::find_config_in_gwt(&worktree).unwrap().unwrap();
+        assert_eq!(config_path, root.join(GWT_CONFIG_FILE));
+        assert_eq!(project_path, root);
+        assert_eq!(config.ai_apps[0].name, "claude");
+
+        // Without the section the standalone config is used instead
+        fs::write(root.join(GWT_CONFIG_FILE), r#"{"repositoryUrl": "x"}"#).unwrap();
+        assert!(ProjectConfig::find_config_in_gwt(&worktree).unwrap().is_none());
+        assert!(gwt_config_with_section(&worktree).is_none());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_ultrathink_separator_join() {
+        assert_eq!(
+            UltrathinkSeparator::BlankLine.join("fix it", "ultrathink"),
+            "fix it\n\nultrathink"
+        );
+        assert_eq!(
+            UltrathinkSeparator::Space.join("fix it", "ultrathink"),
+            "fix it ultrathink"
+        );
+        assert_eq!(
+            UltrathinkSeparator::None.join("fix it", "ultrathink"),
+            "fix itultrathink"
+        );
+    }
+
+    #[test]
+    fn test_launch_dir_appends_subdir() {
+        let mut app = app("claude");
+        assert_eq!(app.launch_dir("/work/feat-claude"), "/work/feat-claude");
+
+        app.subdir = Some("/packages/web/".to_string());
+        assert_eq!(
+            app.launch_dir("/work/feat-claude/"),
+            "/work/feat-claude/packages/web"
+        );
+
+        let app: AiApp =
+            serde_json::from_str(r#"{"name": "claude", "command": "claude", "cwd": "packages/web"}"#).unwrap();
+        assert_eq!(app.subdir.as_deref(), Some("packages/web"));
+    }
+
+    #[test]
+    fn test_app_kinds_launch_lines() {
+        let apps: Vec<AiApp> = serde_json::from_str(
+            r#"[
+                {"name": "claude", "command": "claude"},
+                {"name": "edit", "kind": "editor"},
+                {"name": "zed", "kind": "editor", "command": "zed ."},
+                {"name": "term", "kind": "shell"}
+            ]"#,
+        )
+        .unwrap();
+        let lines: Vec<String> = apps
+            .iter()
+            .map(|a| a.launch_line("/w", &Mode::TmuxSingleWindow))
+            .collect();
+        assert_eq!(
+            lines,
+            ["cd /w && claude", "cd /w && ${EDITOR:-vi} .", "cd /w && zed .", "cd /w"]
+        );
+        assert_eq!(apps[1].kind, AppKind::Editor);
+        assert_eq!(apps[3].slug(), "term");
+        assert!(apps[0].is_ai() && !apps[3].is_ai());
+
+        let mut config: ProjectConfig =
+            serde_json::from_str(r#"{"ai_apps": [{"name": "claude"}]}"#).unwrap();
+        assert!(config.validate().is_err());
+        config.ai_apps[0].kind = AppKind::Shell;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_pane_split_ratio() {
+        let mut config: ProjectConfig =
+            serde_json::from_str(r#"{"ai_apps": [{"name": "claude", "command": "claude"}]}"#).unwrap();
+        for ratio in [10, 70, 90] {
+            config.pane_split_ratio = Some(ratio);
+            assert!(config.validate().is_ok(), "{} should be accepted", ratio);
+        }
+        for ratio in [0, 9, 91, 95] {
+            config.pane_split_ratio = Some(ratio);
+            assert!(config.validate().is_err(), "{} should be rejected", ratio);
+        }
+    }
+
+    #[test]
+    fn test_monitor_key() {
+        let mut config: ProjectConfig =
+            serde_json::from_str(r#"{"ai_apps": [{"name": "claude", "command": "claude"}]}"#).unwrap();
+        assert_eq!(config.monitor_key(), 2);
+        config.monitor_key = Some("F13".to_string());
+        assert!(config.validate().is_err());
+        config.monitor_key = Some("f5".to_string());
+        assert!(config.validate().is_ok());
+        assert_eq!(config.monitor_key(), 5);
+    }
+
+    #[test]
+    fn test_commands_override_per_mode() {
+        let app: AiApp = serde_json::from_str(
+            r#"{"name": "claude", "command": "claude", "commands": {"iterm2": "claude --chrome"}}"#,
+        )
+        .unwrap();
+        assert_eq!(app.command_for(&Mode::Iterm2), "claude --chrome");
+        assert_eq!(app.command_for(&Mode::TmuxSingleWindow), "claude");
+        assert_eq!(app.launch_line("/w", &Mode::Iterm2), "cd /w && claude --chrome");
+
+        let launched = app.with_extra_args(&split_shell_words("--model opus").unwrap());
+        assert_eq!(launched.command_for(&Mode::Iterm2), "claude --chrome --model opus");
+        assert_eq!(launched.slug(), "claude");
+
+        let replaced = app.with_command("claude --resume");
+        assert_eq!(replaced.command_for(&Mode::Iterm2), "claude --resume");
+        assert_eq!(replaced.slug(), "claude");
+    }
+
+    #[test]
+    fn test_validate_rejects_duplicate_apps() {
+        let config: ProjectConfig = serde_json::from_str(
+            r#"{"ai_apps": [
+                {"name": "claude", "command": "claude"},
+                {"name": "claude", "command": "claude --model opus"}
+            ]}"#,
+        )
+        .unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("Duplicate app name: claude"), "{}", err);
+
+        let config: ProjectConfig = serde_json::from_str(
+            r#"{"ai_apps": [
+                {"name": "claude-a", "command": "claude"},
+                {"name": "claude-b", "command": "claude"}
+            ]}"#,
+        )
+        .unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("branch suffix 'claude'"), "{}", err);
+    }
+
+    #[test]
+    fn test_with_extra_args_quotes_and_pins_slug() {
+        let app = AiApp {
+            command: "claude --dangerously-skip-permissions".to_string(),
+            ..app("claude")
+        };
+
+        let launched = app.with_extra_args(&split_shell_words("--model opus").unwrap());
+        assert_eq!(
+            launched.command,
+            "claude --dangerously-skip-permissions --model opus"
+        );
+
+        // A quoted argument stays one word, spaces and all
+        let words = split_shell_words(r#"--append-system-prompt "be terse" --x 'it''s' a\ b"#).unwrap();
+        assert_eq!(words, ["--append-system-prompt", "be terse", "--x", "its", "a b"]);
+        assert_eq!(
+            app.with_extra_args(&words).command,
+            "claude --dangerously-skip-permissions --append-system-prompt 'be terse' --x its 'a b'"
+        );
+        assert_eq!(split_shell_words(r#"say "it's \"ok\"""#).unwrap(), ["say", r#"it's "ok""#]);
+        assert!(split_shell_words("--model 'opus").is_err());
+        assert_eq!(launched.slug(), app.slug());
+        assert_eq!(app.command, "claude --dangerously-skip-permissions");
+
+        assert_eq!(shell_quote("a;rm"), "'a;rm'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+
+    #[test]
+    fn test_terminals_per_column_must_be_positive() {
+        let err = ProjectConfig::from_json(r#"{"terminals_per_column": 0}"#).unwrap_err();
+        assert!(err.to_string().contains("terminals_per_column must be at least 1"));
+
+        // Negative values can't be coerced into usize and are rejected by serde
+        assert!(ProjectConfig::from_json(r#"{"terminals_per_column": -1}"#).is_err());
+
+        let config = ProjectConfig::from_json(r#"{"terminals_per_column": 1}"#).unwrap();
+        assert_eq!(config.terminals_per_column, 1);
+    }
+
+    #[test]
+    fn test_command_pane_must_be_a_shell_pane() {
+        let config =
+            ProjectConfig::from_json(r#"{"terminals_per_column": 3, "command_pane": 1}"#).unwrap();
+        assert_eq!(config.command_pane, Some(1));
+
+        // 0 is the AI pane, and 3 doesn't exist with three panes per column
+        assert!(ProjectConfig::from_json(r#"{"terminals_per_column": 3, "command_pane": 0}"#).is_err());
+        assert!(ProjectConfig::from_json(r#"{"terminals_per_column": 3, "command_pane": 3}"#).is_err());
+    }
+
+    #[test]
+    fn test_current_dir_error_hints_at_deleted_directory() {
+        let message = current_dir_error(std::io::Error::from(std::io::ErrorKind::NotFound)).to_string();
+        assert!(message.contains("may have been deleted"), "{}", message);
+
+        let message = current_dir_error(std::io::Error::from(std::io::ErrorKind::PermissionDenied)).to_string();
+        assert!(!message.contains("may have been deleted"), "{}", message);
+    }
+
+    #[test]
+    fn test_iterm2_max_columns() {
+        let config = ProjectConfig::from_json("{}").unwrap();
+        assert_eq!(config.iterm2_max_columns(), 4);
+
+        let config = ProjectConfig::from_json(r#"{"min_pane_width": 100}"#).unwrap();
+        assert_eq!(config.iterm2_max_columns(), 2);
+
+        // Never fewer than one column, however wide the minimum
+        let config = ProjectConfig::from_json(r#"{"min_pane_width": 500}"#).unwrap();
+        assert_eq!(config.iterm2_max_columns(), 1);
+
+        assert!(ProjectConfig::from_json(r#"{"min_pane_width": 0}"#).is_err());
+        assert!(ProjectConfig::from_json(r#"{"max_columns_per_window": 0}"#).is_err());
+        assert!(ProjectConfig::from_json(r#"{"ai_apps": [{"name": "a", "command": "a", "columns": 0}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_pane_delays() {
+        let config = ProjectConfig::from_json("{}").unwrap();
+        assert_eq!(config.pane_init_delay().as_millis(), 500);
+        assert_eq!(config.iterm2_pane_delay().as_millis(), 1000);
+
+        let config =
+            ProjectConfig::from_json(r#"{"pane_init_delay_ms": 1500, "iterm2_pane_delay_ms": 250}"#)
+                .unwrap();
+        assert_eq!(config.pane_init_delay().as_millis(), 1500);
+        assert_eq!(config.iterm2_pane_delay().as_millis(), 250);
+    }
+
+    #[test]
+    fn test_review_fetch_prune() {
+        let config = ProjectConfig::from_json("{}").unwrap();
+        assert!(config.review.fetch_prune());
+
+        let config = ProjectConfig::from_json(r#"{"review": {"fetch_prune": false}}"#).unwrap();
+        assert!(!config.review.fetch_prune());
+    }
+
+    #[test]
+    fn test_local_config_overrides() {
+        assert_eq!(
+            local_config_path(Path::new("/cfg/github_com_me_repo.jsonc")),
+            PathBuf::from("/cfg/github_com_me_repo.local.jsonc")
+        );
+
+        let mut base = serde_json::json!({
+            "project_path": "/shared/repo",
+            "terminals_per_column": 2,
+            "ai_apps": [
+                {"name": "claude", "command": "claude"},
+                {"name": "codex", "command": "codex"}
+            ]
+        });
+        let local = serde_json::json!({
+            "project_path": "/home/me/repo",
+            "remove_kills_session": false,
+            "ai_apps": [
+                {"name": "codex", "command": "codex --model o3"},
+                {"name": "gemini", "command": "gemini"}
+            ]
+        });
+        merge_config_values(&mut base, local);
+
+        let config: ProjectConfig = ProjectConfig::from_json(&base.to_string()).unwrap();
+        assert_eq!(config.project_path, Some(PathBuf::from("/home/me/repo")));
+        assert_eq!(config.terminals_per_column, 2);
+        assert!(!config.remove_kills_session());
+        let apps: Vec<(&str, &str)> = config
+            .ai_apps
+            .iter()
+            .map(|a| (a.name.as_str(), a.command.as_str()))
+            .collect();
+        assert_eq!(
+            apps,
+            vec![("claude", "claude"), ("codex", "codex --model o3"), ("gemini", "gemini")]
+        );
+    }
+
+    #[test]
+    fn test_env_file_exports() {
+        let vars = parse_env_file(
+            "# keys\nexport API_KEY=abc123\nNAME=\"two words\\n\" \nRAW='$HOME #x'\nPLAIN=v # note\n1BAD=x\nnot a line\nAPI_KEY=override\n",
+        );
+        let pairs: Vec<(&str, &str)> = vars.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        assert_eq!(
+            pairs,
+            vec![("NAME", "two words\n"), ("RAW", "$HOME #x"), ("PLAIN", "v"), ("API_KEY", "override")]
+        );
+
+        let apps: Vec<AiApp> = serde_json::from_str(
+            r#"[{"name": "claude", "command": "claude"}, {"name": "term", "kind": "shell"}]"#,
+        )
+        .unwrap();
+        let vars = vec![("A".to_string(), "1".to_string()), ("B".to_string(), "x y".to_string())];
+        let claude = apps[0].with_env(&vars);
+        assert_eq!(
+            claude.launch_line("/w", &Mode::TmuxSingleWindow),
+            "cd /w && export A=1 B='x y' && claude"
+        );
+        assert_eq!(claude.slug(), "claude");
+        assert_eq!(apps[1].with_env(&vars).launch_line("/w", &Mode::TmuxSingleWindow), "cd /w");
+    }
+
+    #[test]
+    fn test_env_file_path_resolution() {
+        let project = Path::new("/repo");
+        let cwd = Path::new("/repo/packages/web");
+        let mut config: ProjectConfig = serde_json::from_str(r#"{"ai_apps": []}"#).unwrap();
+        assert_eq!(config.env_file_path(project, cwd, None), None);
+
+        // `--env-file` is relative to where mai runs, like any other argument
+        assert_eq!(
+            config.env_file_path(project, cwd, Some(Path::new("./local.env"))),
+            Some(PathBuf::from("/repo/packages/web/./local.env"))
+        );
+
+        // The config's `env_file` is relative to the project
+        config.env_file = Some(".env.agents".to_string());
+        assert_eq!(
+            config.env_file_path(project, cwd, None),
+            Some(PathBuf::from("/repo/.env.agents"))
+        );
+        assert_eq!(
+            config.env_file_path(project, cwd, Some(Path::new("/tmp/other.env"))),
+            Some(PathBuf::from("/tmp/other.env"))
+        );
+    }
+
+    #[test]
+    fn test_launch_line_quotes_the_worktree_path() {
+        let app: AiApp =
+            serde_json::from_str(r#"{"name": "claude", "command": "claude", "subdir": "web app"}"#)
+                .unwrap();
+        assert_eq!(
+            app.launch_line("/w/it's here", &Mode::Iterm2),
+            r"cd '/w/it'\''s here/web app' && claude"
+        );
+        // The AppleScript string keeps the shell quoting intact
+        assert_eq!(
+            crate::applescript::escape(&app.launch_line("/w/a \"b\"", &Mode::Iterm2)),
+            r#"cd '/w/a \"b\"/web app' && claude"#
+        );
+    }
+}
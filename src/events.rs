@@ -0,0 +1,106 @@
+use crate::config::Mode;
+use serde::Serialize;
+use std::fmt::Display;
+use std::io::Write;
+
+/// A progress event for `add`/`remove --events`, written to stdout as one JSON
+/// object per line. The `event` field names the variant in snake_case.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    WorktreeCreated {
+        app: String,
+        branch: String,
+        path: String,
+    },
+    WorktreeRemoved {
+        branch: String,
+    },
+    SessionCreated {
+        session: String,
+        mode: Mode,
+    },
+    SessionRemoved {
+        session: String,
+    },
+    Cancelled {
+        branch_prefix: String,
+    },
+    Error {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        app: Option<String>,
+        message: String,
+    },
+}
+
+/// Routes progress output either to humans (plain lines) or to machines (JSON
+/// events), so stdout never mixes the two.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Reporter {
+    events: bool,
+}
+
+impl Reporter {
+    pub fn new(events: bool) -> Self {
+        Self { events }
+    }
+
+    pub fn events(&self) -> bool {
+        self.events
+    }
+
+    /// Print a human-readable progress line; suppressed in events mode
+    pub fn say(&self, message: impl Display) {
+        if !self.events {
+            println!("{}", message);
+        }
+    }
+
+    /// Write `event` as a JSON line; a no-op unless events mode is on
+    pub fn emit(&self, event: Event) {
+        if !self.events {
+            return;
+        }
+        if let Ok(line) = serde_json::to_string(&event) {
+            let mut stdout = std::io::stdout().lock();
+            let _ = writeln!(stdout, "{}", line);
+            let _ = stdout.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_serialization() {
+        let event = Event::WorktreeCreated {
+            app: "claude".to_string(),
+            branch: "feat-claude".to_string(),
+            path: "/work/feat-claude".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"worktree_created","app":"claude","branch":"feat-claude","path":"/work/feat-claude"}"#
+        );
+
+        let event = Event::SessionCreated {
+            session: "proj-feat".to_string(),
+            mode: Mode::TmuxSingleWindow,
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"session_created","session":"proj-feat","mode":"tmux-single-window"}"#
+        );
+
+        let event = Event::Error {
+            app: None,
+            message: "boom".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"error","message":"boom"}"#
+        );
+    }
+}
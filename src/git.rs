@@ -1,5 +1,6 @@
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
 
 /// Get the remote origin URL for a git repository
 pub fn get_remote_origin_url(path: &Path) -> Option<String> {
@@ -76,6 +77,16 @@ pub fn generate_config_filename(repo_url: &str) -> String {
     result
 }
 
+/// Whether `name` is a valid branch name (`git check-ref-format --branch`).
+/// Slashes are allowed (`feature/login`), but not e.g. `a//b`, `a..b` or spaces.
+pub fn is_valid_branch_name(name: &str) -> bool {
+    Command::new("git")
+        .args(["check-ref-format", "--branch", name])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
 /// Get the top-level directory of the git repository.
 /// Works from within worktrees as well.
 pub fn get_repo_root(path: &Path) -> Option<PathBuf> {
@@ -97,6 +108,33 @@ pub fn get_repo_root(path: &Path) -> Option<PathBuf> {
     }
 }
 
+/// The ref branches are usually based on: the remote's default branch
+/// (e.g. "origin/main"), else a local `main` or `master`.
+pub fn default_base_ref(path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["symbolic-ref", "--short", "refs/remotes/origin/HEAD"])
+        .current_dir(path)
+        .output()
+        .ok()?;
+    if output.status.success() {
+        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !name.is_empty() {
+            return Some(name);
+        }
+    }
+
+    ["main", "master"]
+        .into_iter()
+        .find(|name| {
+            Command::new("git")
+                .args(["rev-parse", "--verify", "--quiet", &format!("refs/heads/{}", name)])
+                .current_dir(path)
+                .output()
+                .is_ok_and(|out| out.status.success())
+        })
+        .map(str::to_string)
+}
+
 /// A git branch with its name and last commit date.
 #[derive(Clone)]
 pub struct BranchInfo {
@@ -134,15 +172,111 @@ pub fn list_local_branches(path: &Path) -> Vec<BranchInfo> {
     }
 }
 
+/// Fetch latest refs from origin, with `prune` also dropping deleted remote branches
+/// (best-effort, doesn't fail if offline)
+pub fn fetch(path: &Path, prune: bool) {
+    let mut cmd = Command::new("git");
+    cmd.arg("fetch");
+    if prune {
+        cmd.arg("--prune");
+    }
+    let _ = cmd.current_dir(path).output();
+}
+
+/// Why `finished_branches` considers a branch done with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinishedReason {
+    /// Reachable from the base ref (`git branch --merged`)
+    Merged,
+    /// Its upstream branch was deleted on the remote
+    UpstreamGone,
+}
+
+/// Local branches that are done with: merged into `base`, or tracking an upstream
+/// branch that has been deleted (`[gone]` after `git fetch --prune`). Branches nothing
+/// was ever committed to are left out, since they are trivially "merged".
+///
+/// A branch counts as committed to when its reflog shows it moved after it was
+/// created, or when its tip is off `base`'s first-parent line (it was merged in
+/// with a merge commit). A branch with no reflog at all is judged by its tip
+/// alone, with a warning, so a fast-forwarded one is kept.
+pub fn finished_branches(path: &Path, base: &str) -> BTreeMap<String, FinishedReason> {
+    let lines = |args: &[&str]| -> Vec<String> {
+        match Command::new("git").args(args).current_dir(path).output() {
+            Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout)
+                .lines()
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        }
+    };
+    let base_name = base.rsplit('/').next().unwrap_or(base);
+
+    let base_line: HashSet<String> = lines(&["rev-list", "--first-parent", base]).into_iter().collect();
+
+    let mut finished = BTreeMap::new();
+    for line in lines(&["branch", "--merged", base, "--format=%(refname:short)\t%(objectname)"]) {
+        let Some((name, tip)) = line.split_once('\t') else {
+            continue;
+        };
+        if name == base_name {
+            continue;
+        }
+        let reflog = lines(&["reflog", "show", "--format=%H", &format!("refs/heads/{}", name)]);
+        let committed = match reflog.len() {
+            0 => {
+                eprintln!(
+                    "warning: branch '{}' has no reflog; it counts as merged only if {} merged it with a merge commit",
+                    name, base
+                );
+                !base_line.contains(tip)
+            }
+            // Only the "Created from" entry: fetched branches start out like this too
+            1 => !base_line.contains(tip),
+            _ => true,
+        };
+        if committed {
+            finished.insert(name.to_string(), FinishedReason::Merged);
+        }
+    }
+    for line in lines(&["for-each-ref", "--format=%(refname:short)\t%(upstream:track)", "refs/heads"]) {
+        if let Some((name, "[gone]")) = line.split_once('\t') {
+            finished.entry(name.to_string()).or_insert(FinishedReason::UpstreamGone);
+        }
+    }
+    finished
+}
+
+/// Branch checked out at `path` (`HEAD` when detached), or None if it isn't a checkout
+pub fn current_branch(path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(path)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!branch.is_empty()).then_some(branch)
+}
+
+/// Whether the checkout at `path` has staged, unstaged or untracked changes
+pub fn has_uncommitted_changes(path: &Path) -> bool {
+    Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(path)
+        .output()
+        .is_ok_and(|out| !out.stdout.is_empty())
+}
+
 /// List all branches (local + remote) sorted by most recent commit date.
 /// Remote branches that have a local counterpart are excluded (local wins).
-/// Fetches from origin first to ensure the list is up-to-date.
-pub fn list_all_branches(path: &Path) -> Vec<BranchInfo> {
-    // Fetch latest refs from origin (best-effort, don't fail if offline)
-    let _ = Command::new("git")
-        .args(["fetch", "--prune"])
-        .current_dir(path)
-        .output();
+/// Fetches from origin first to ensure the list is up-to-date, pruning deleted remote
+/// branches when `prune` is set.
+pub fn list_all_branches(path: &Path, prune: bool) -> Vec<BranchInfo> {
+    fetch(path, prune);
 
     let local = list_local_branches(path);
     let local_names: std::collections::HashSet<&str> =
@@ -195,30 +329,30 @@ pub fn list_all_branches(path: &Path) -> Vec<BranchInfo> {
         .current_dir(path)
         .output();
 
-    if let Ok(out) = combined_output {
-        if out.status.success() {
-            let mut seen = std::collections::HashSet::new();
-            let mut sorted = Vec::new();
-            for line in String::from_utf8_lossy(&out.stdout).lines() {
-                if let Some((full_name, date)) = line.split_once('\t') {
-                    let short = full_name.strip_prefix("origin/").unwrap_or(full_name);
-                    if short == "HEAD" {
-                        continue;
-                    }
-                    if seen.contains(short) {
-                        continue;
-                    }
-                    seen.insert(short.to_string());
-                    let is_remote = full_name.starts_with("origin/") && !local_names.contains(short);
-                    sorted.push(BranchInfo {
-                        name: short.to_string(),
-                        date: date.to_string(),
-                        remote_only: is_remote,
-                    });
+    if let Ok(out) = combined_output
+        && out.status.success()
+    {
+        let mut seen = std::collections::HashSet::new();
+        let mut sorted = Vec::new();
+        for line in String::from_utf8_lossy(&out.stdout).lines() {
+            if let Some((full_name, date)) = line.split_once('\t') {
+                let short = full_name.strip_prefix("origin/").unwrap_or(full_name);
+                if short == "HEAD" {
+                    continue;
                 }
+                if seen.contains(short) {
+                    continue;
+                }
+                seen.insert(short.to_string());
+                let is_remote = full_name.starts_with("origin/") && !local_names.contains(short);
+                sorted.push(BranchInfo {
+                    name: short.to_string(),
+                    date: date.to_string(),
+                    remote_only: is_remote,
+                });
             }
-            return sorted;
         }
+        return sorted;
     }
 
     // Fallback: concatenate local + remote without re-sorting
@@ -231,6 +365,62 @@ pub fn list_all_branches(path: &Path) -> Vec<BranchInfo> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_valid_branch_name() {
+        assert!(is_valid_branch_name("feature/login-claude"));
+        assert!(is_valid_branch_name("fix-1234-codex"));
+        for invalid in ["feature//login", "feature/", "a..b", "has space", "-claude"] {
+            assert!(!is_valid_branch_name(invalid), "{} should be invalid", invalid);
+        }
+    }
+
+    #[test]
+    fn test_finished_branches() {
+        let dir = std::env::temp_dir().join(format!("mai-finished-branches-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(&dir)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "base"]);
+        git(&["branch", "fresh"]);
+        git(&["checkout", "-q", "-b", "done"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "done"]);
+        git(&["checkout", "-q", "-b", "wip", "main"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "wip"]);
+        git(&["checkout", "-q", "main"]);
+        git(&["merge", "-q", "--ff-only", "done"]);
+
+        let finished = finished_branches(&dir, "main");
+        assert_eq!(
+            finished.into_iter().collect::<Vec<_>>(),
+            vec![("done".to_string(), FinishedReason::Merged)]
+        );
+
+        // Without reflogs, only the tip is left to go by: a branch merged with a
+        // merge commit still counts, while fresh and fast-forwarded ones are kept
+        git(&["checkout", "-q", "-b", "merged", "main~1"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "merged"]);
+        git(&["checkout", "-q", "main"]);
+        git(&["merge", "-q", "--no-ff", "-m", "merge", "merged"]);
+        git(&["reflog", "expire", "--expire=all", "--all"]);
+
+        let finished = finished_branches(&dir, "main");
+        assert_eq!(
+            finished.into_iter().collect::<Vec<_>>(),
+            vec![("merged".to_string(), FinishedReason::Merged)]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_generate_config_filename_ssh() {
         assert_eq!(
@@ -1,6 +1,7 @@
-use crate::config::{AiApp, Mode, ProjectConfig};
+use crate::config::{AiApp, AppKind, Mode, ProjectConfig, UltrathinkSeparator};
 use crate::error::{MultiAiError, Result};
-use std::path::PathBuf;
+use crate::util;
+use std::path::{Path, PathBuf};
 use ratatui::crossterm::{
     event::{self, Event, KeyCode, KeyModifiers},
     execute,
@@ -27,6 +28,15 @@ enum WizardStep {
         selected: usize,
     },
     Review,
+    /// Add an app that isn't in apps.jsonc; returns to Review when done
+    CustomCommand {
+        name: String,
+        command: String,
+        editing_command: bool,
+        error: Option<String>,
+    },
+    /// Shown after Review when the config file already exists
+    ConfirmOverwrite,
 }
 
 struct WizardState {
@@ -36,6 +46,10 @@ struct WizardState {
     app_state: AppState,
     project_path: PathBuf,
     worktrees_path: Option<PathBuf>,
+    // Project-level apps from `--from-file`; empty means use the global apps.jsonc
+    ai_apps: Vec<AiApp>,
+    // Where the config is saved, resolved up front so an existing file can be confirmed in the TUI
+    config_path: PathBuf,
 }
 
 #[derive(PartialEq)]
@@ -46,10 +60,11 @@ enum AppState {
 }
 
 impl WizardState {
-    fn new(project_path: PathBuf) -> Result<Self> {
+    fn new(project_path: PathBuf, ai_apps: Vec<AiApp>) -> Result<Self> {
         // Auto-detect worktrees_path from gwt config
         let worktrees_path =
             crate::worktree::WorktreeManager::read_worktrees_path_public(&project_path);
+        let config_path = config_target_path(&project_path)?;
 
         Ok(Self {
             current_step: WizardStep::SelectMode {
@@ -60,6 +75,8 @@ impl WizardState {
             app_state: AppState::Running,
             project_path,
             worktrees_path,
+            ai_apps,
+            config_path,
         })
     }
 
@@ -81,24 +98,82 @@ impl WizardState {
     fn step_number(&self) -> (usize, usize) {
         match &self.current_step {
             WizardStep::SelectMode { .. } => (1, 2),
-            WizardStep::Review => (2, 2),
+            WizardStep::Review
+            | WizardStep::CustomCommand { .. }
+            | WizardStep::ConfirmOverwrite => (2, 2),
+        }
+    }
+
+    /// Append a custom app to the project's apps. The first one seeds the list with
+    /// the global default apps so they keep launching alongside it.
+    fn add_custom_app(&mut self, name: &str, command: &str) -> std::result::Result<(), String> {
+        let (name, command) = (name.trim(), command.trim());
+        if name.is_empty() || command.is_empty() {
+            return Err("Name and command are both required".to_string());
+        }
+        if self.ai_apps.is_empty() {
+            let globals = load_apps().map_err(|e| e.to_string())?;
+            self.ai_apps = globals.into_iter().filter(|app| app.default).collect();
+        }
+        let app = AiApp {
+            name: name.to_string(),
+            command: command.to_string(),
+            slug: None,
+            ultrathink: None,
+            default: false,
+            meta_review: false,
+            auto_submit: false,
+            kind: AppKind::Ai,
+            commands: Default::default(),
+            subdir: None,
+            columns: None,
+            description: None,
+        };
+        if self.ai_apps.iter().any(|a| a.name == app.name || a.slug() == app.slug()) {
+            return Err(format!("An app named '{}' or using branch suffix '{}' already exists", app.name, app.slug()));
         }
+        self.ai_apps.push(app);
+        Ok(())
     }
 
     fn get_config(&self) -> ProjectConfig {
         ProjectConfig {
-            ai_apps: Vec::new(),
+            ai_apps: self.ai_apps.clone(),
             terminals_per_column: 2,
             mode: Some(self.terminal_mode.clone()),
             project_path: Some(self.project_path.clone()),
             worktrees_path: self.worktrees_path.clone(),
+            ultrathink_separator: UltrathinkSeparator::default(),
+            log_dir: None,
+            copy_sent_text: false,
+            command_pane: None,
+            min_pane_width: None,
+            max_columns_per_window: None,
+            file_reference: Default::default(),
+            pane_split_ratio: None,
+            monitor_key: None,
+            env_file: None,
+            remove_kills_session: None,
+            pane_init_delay_ms: None,
+            iterm2_pane_delay_ms: None,
+            focus_app: None,
+            review: Default::default(),
         }
     }
 }
 
-pub fn run_init() -> Result<()> {
-    let current_dir = std::env::current_dir()
-        .map_err(|e| MultiAiError::Config(format!("Failed to get current directory: {}", e)))?;
+pub fn run_init(from_file: Option<PathBuf>) -> Result<()> {
+    let ai_apps = match from_file {
+        Some(path) => {
+            let content = fs::read_to_string(&path).map_err(|e| {
+                MultiAiError::Config(format!("Failed to read {}: {}", path.display(), e))
+            })?;
+            parse_apps_file(&content)?
+        }
+        None => Vec::new(),
+    };
+
+    let current_dir = crate::config::current_dir()?;
 
     let project_path = crate::git::get_repo_root(&current_dir).ok_or_else(|| {
         MultiAiError::Config(
@@ -107,7 +182,7 @@ pub fn run_init() -> Result<()> {
     })?;
 
     let mut terminal = setup_terminal()?;
-    let mut wizard = WizardState::new(project_path)?;
+    let mut wizard = WizardState::new(project_path, ai_apps)?;
 
     let result = run_wizard(&mut terminal, &mut wizard);
 
@@ -155,6 +230,10 @@ fn handle_input(wizard: &mut WizardState) -> Result<()> {
     if event::poll(Duration::from_millis(16))?
         && let Event::Key(key) = event::read()?
     {
+        if matches!(wizard.current_step, WizardStep::CustomCommand { .. }) {
+            handle_custom_command_input(wizard, key.code, key.modifiers);
+            return Ok(());
+        }
         match key.code {
             KeyCode::Esc | KeyCode::Left => {
                 wizard.back();
@@ -168,12 +247,86 @@ fn handle_input(wizard: &mut WizardState) -> Result<()> {
             KeyCode::Char('q') => {
                 wizard.app_state = AppState::Cancelled;
             }
+            KeyCode::Char('y') if matches!(wizard.current_step, WizardStep::ConfirmOverwrite) => {
+                wizard.app_state = AppState::Completed;
+            }
+            KeyCode::Char('n') if matches!(wizard.current_step, WizardStep::ConfirmOverwrite) => {
+                wizard.back();
+            }
+            KeyCode::Char('a') if matches!(wizard.current_step, WizardStep::Review) => {
+                wizard.next(WizardStep::CustomCommand {
+                    name: String::new(),
+                    command: String::new(),
+                    editing_command: false,
+                    error: None,
+                });
+            }
             _ => {}
         }
     }
     Ok(())
 }
 
+/// Text entry for the custom app step: typed keys edit the fields instead of navigating.
+fn handle_custom_command_input(wizard: &mut WizardState, code: KeyCode, modifiers: KeyModifiers) {
+    let WizardStep::CustomCommand {
+        name,
+        command,
+        editing_command,
+        error,
+    } = &mut wizard.current_step
+    else {
+        return;
+    };
+
+    match code {
+        KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+            wizard.app_state = AppState::Cancelled;
+        }
+        KeyCode::Esc => {
+            wizard.back();
+        }
+        KeyCode::Enter if *editing_command => {
+            let (name, command) = (name.clone(), command.clone());
+            match wizard.add_custom_app(&name, &command) {
+                Ok(()) => {
+                    wizard.back();
+                }
+                Err(message) => {
+                    if let WizardStep::CustomCommand { error, .. } = &mut wizard.current_step {
+                        *error = Some(message);
+                    }
+                }
+            }
+        }
+        KeyCode::Enter => {
+            *editing_command = true;
+            *error = None;
+        }
+        KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+            *editing_command = !*editing_command;
+            *error = None;
+        }
+        KeyCode::Backspace => {
+            if *editing_command {
+                command.pop();
+            } else {
+                name.pop();
+            }
+            *error = None;
+        }
+        KeyCode::Char(c) => {
+            if *editing_command {
+                command.push(c);
+            } else {
+                name.push(c);
+            }
+            *error = None;
+        }
+        _ => {}
+    }
+}
+
 fn handle_up(wizard: &mut WizardState) {
     if let WizardStep::SelectMode { selected } = &mut wizard.current_step {
         let max = get_mode_options().len() - 1;
@@ -195,9 +348,13 @@ fn validate_and_next(wizard: &mut WizardState) {
             wizard.terminal_mode = modes[*selected].clone();
             wizard.next(WizardStep::Review);
         }
-        WizardStep::Review => {
+        WizardStep::Review if wizard.config_path.exists() => {
+            wizard.next(WizardStep::ConfirmOverwrite);
+        }
+        WizardStep::Review | WizardStep::ConfirmOverwrite => {
             wizard.app_state = AppState::Completed;
         }
+        WizardStep::CustomCommand { .. } => {}
     }
 }
 
@@ -241,9 +398,7 @@ fn render_header(f: &mut Frame, area: Rect, wizard: &WizardState) {
     let title = format!(" Multi-AI CLI Configuration (Step {}/{}) ", current, total);
     let header = Paragraph::new(title)
         .style(
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
+            util::fg(Color::Cyan).add_modifier(Modifier::BOLD),
         )
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
@@ -258,9 +413,45 @@ fn render_content(f: &mut Frame, area: Rect, wizard: &WizardState) {
         WizardStep::Review => {
             render_review(f, area, wizard);
         }
+        WizardStep::CustomCommand {
+            name,
+            command,
+            editing_command,
+            error,
+        } => {
+            render_custom_command(f, area, name, command, *editing_command, error.as_deref());
+        }
+        WizardStep::ConfirmOverwrite => {
+            render_confirm_overwrite(f, area, &wizard.config_path);
+        }
     }
 }
 
+fn render_confirm_overwrite(f: &mut Frame, area: Rect, config_path: &Path) {
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "A configuration for this project already exists:",
+            util::fg(Color::Yellow),
+        )),
+        Line::from(format!("  {}", config_path.display())),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Overwrite it?",
+            util::fg(Color::Green).add_modifier(Modifier::BOLD),
+        )),
+    ];
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Overwrite Configuration ")
+            .title_bottom(" y/Enter: overwrite, n/ESC: back "),
+    );
+
+    f.render_widget(paragraph, area);
+}
+
 fn render_mode_select(f: &mut Frame, area: Rect, selected: usize) {
     let modes = get_mode_options();
     let mode_labels = modes
@@ -279,10 +470,7 @@ fn render_mode_select(f: &mut Frame, area: Rect, selected: usize) {
             let checkbox = if i == selected { "[✓]" } else { "[ ]" };
             let content = format!(" {} {}", checkbox, label);
             let style = if i == selected {
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Gray)
-                    .add_modifier(Modifier::BOLD)
+                util::selected().add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             };
@@ -300,19 +488,64 @@ fn render_mode_select(f: &mut Frame, area: Rect, selected: usize) {
     f.render_widget(list, area);
 }
 
+fn render_custom_command(
+    f: &mut Frame,
+    area: Rect,
+    name: &str,
+    command: &str,
+    editing_command: bool,
+    error: Option<&str>,
+) {
+    let field = |label: &str, value: &str, active: bool| {
+        let style = if active {
+            util::fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            util::fg(Color::Yellow)
+        };
+        let cursor = if active { "█" } else { "" };
+        vec![
+            Line::from(Span::styled(label.to_string(), style)),
+            Line::from(format!("  {}{}", value, cursor)),
+            Line::from(""),
+        ]
+    };
+
+    let mut lines = vec![Line::from("")];
+    lines.extend(field("Name:", name, !editing_command));
+    lines.extend(field("Command:", command, editing_command));
+    lines.push(Line::from(Span::styled(
+        "The app is added to this project's config along with your default apps from apps.jsonc",
+        util::fg(Color::DarkGray),
+    )));
+    if let Some(error) = error {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            error.to_string(),
+            util::fg(Color::Red),
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Add Custom App ")
+            .title_bottom(" Tab: switch field, Enter: next/add, ESC: cancel "),
+    );
+
+    f.render_widget(paragraph, area);
+}
+
 fn render_review(f: &mut Frame, area: Rect, wizard: &WizardState) {
     let mut lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             "Configuration Summary:",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
+            util::fg(Color::Cyan).add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from(Span::styled(
             "Project Path:",
-            Style::default().fg(Color::Yellow),
+            util::fg(Color::Yellow),
         )),
         Line::from(format!("  {}", wizard.project_path.display())),
     ];
@@ -321,7 +554,7 @@ fn render_review(f: &mut Frame, area: Rect, wizard: &WizardState) {
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "Worktrees Path:",
-            Style::default().fg(Color::Yellow),
+            util::fg(Color::Yellow),
         )));
         lines.push(Line::from(format!("  {}", wt_path.display())));
     }
@@ -329,7 +562,7 @@ fn render_review(f: &mut Frame, area: Rect, wizard: &WizardState) {
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "Terminal Mode:",
-        Style::default().fg(Color::Yellow),
+        util::fg(Color::Yellow),
     )));
     let mode_str = match wizard.terminal_mode {
         Mode::Iterm2 => "iTerm2",
@@ -339,18 +572,30 @@ fn render_review(f: &mut Frame, area: Rect, wizard: &WizardState) {
     lines.push(Line::from(format!("  {}", mode_str)));
 
     lines.push(Line::from(""));
-    lines.push(Line::from(Span::styled(
-        "AI tools are configured globally in apps.jsonc (run 'mai apps' to edit)",
-        Style::default().fg(Color::DarkGray),
-    )));
+    if wizard.ai_apps.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "AI tools are configured globally in apps.jsonc (run 'mai apps' to edit)",
+            util::fg(Color::DarkGray),
+        )));
+    } else {
+        lines.push(Line::from(Span::styled(
+            "AI Tools (this project only):",
+            util::fg(Color::Yellow),
+        )));
+        // Nothing here takes focus, so long commands wrap under themselves instead of
+        // being cut off
+        let width = area.width.saturating_sub(2) as usize;
+        for app in &wizard.ai_apps {
+            let prefix = format!("  {}: ", app.name);
+            lines.extend(util::wrap_after(&prefix, &app.command, width).into_iter().map(Line::from));
+        }
+    }
 
     // Add save confirmation prompt
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "Save configuration?",
-        Style::default()
-            .fg(Color::Green)
-            .add_modifier(Modifier::BOLD),
+        util::fg(Color::Green).add_modifier(Modifier::BOLD),
     )));
 
     let paragraph = Paragraph::new(lines)
@@ -358,7 +603,7 @@ fn render_review(f: &mut Frame, area: Rect, wizard: &WizardState) {
             Block::default()
                 .borders(Borders::ALL)
                 .title(" Review & Save Configuration ")
-                .title_bottom(" Enter: save, ESC: back "),
+                .title_bottom(" Enter: save, a: add custom app, ESC: back "),
         )
         .alignment(Alignment::Left);
 
@@ -370,33 +615,125 @@ fn render_footer(f: &mut Frame, area: Rect, wizard: &WizardState) {
         WizardStep::SelectMode { .. } => {
             "↑/↓: select | Enter/→: next | ESC/←: cancel | Ctrl+C/q: quit"
         }
-        WizardStep::Review => "Enter/→: save | ESC/←: back | Ctrl+C/q: quit",
+        WizardStep::Review => "Enter/→: save | a: add custom app | ESC/←: back | Ctrl+C/q: quit",
+        WizardStep::CustomCommand { .. } => "Tab: switch field | Enter: next/add | ESC: cancel | Ctrl+C: quit",
+        WizardStep::ConfirmOverwrite => "y/Enter: overwrite | n/ESC/←: back | Ctrl+C/q: quit",
     };
 
     let footer = Paragraph::new(hints)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(util::fg(Color::DarkGray))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
 
     f.render_widget(footer, area);
 }
 
+/// Build apps from one command per line (blank lines and `#` comments are skipped).
+/// The name is the first token's file name, e.g. `/opt/bin/claude --model opus` -> `claude`,
+/// or the slug when an earlier line already took that name.
+fn parse_apps_file(content: &str) -> Result<Vec<AiApp>> {
+    let mut apps: Vec<AiApp> = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let first = line.split_whitespace().next().unwrap_or(line);
+        let name = first.rsplit('/').next().unwrap_or(first).to_string();
+        let mut app = AiApp {
+            name,
+            command: line.to_string(),
+            slug: None,
+            ultrathink: None,
+            default: false,
+            meta_review: false,
+            auto_submit: false,
+            kind: AppKind::Ai,
+            commands: Default::default(),
+            subdir: None,
+            columns: None,
+            description: None,
+        };
+        // Branch names are built from slugs, so two commands with the same slug would collide
+        if let Some(existing) = apps.iter().find(|a| a.slug() == app.slug()) {
+            return Err(MultiAiError::Config(format!(
+                "'{}' and '{}' both map to branch suffix '{}'; make the commands distinct",
+                existing.command,
+                app.command,
+                app.slug()
+            )));
+        }
+        // Names must be unique as well, e.g. for `claude` and `claude --model opus`
+        if apps.iter().any(|a| a.name == app.name) {
+            app.name = app.slug();
+        }
+        if let Some(existing) = apps.iter().find(|a| a.name == app.name) {
+            return Err(MultiAiError::Config(format!(
+                "'{}' and '{}' both map to app name '{}'; make the commands distinct",
+                existing.command, app.command, app.name
+            )));
+        }
+        apps.push(app);
+    }
+
+    if apps.is_empty() {
+        return Err(MultiAiError::Config("No commands found in file".to_string()));
+    }
+    Ok(apps)
+}
+
 pub fn default_apps_content() -> String {
     EMBEDDED_APPS_JSONC.to_string()
 }
 
-/// Load AI apps from `~/.config/multi-ai-cli/apps.jsonc`.
-/// If the file doesn't exist, falls back to the embedded default.
-pub fn load_apps() -> Result<Vec<AiApp>> {
+/// Environment variable naming an apps.jsonc file; skips discovery when set.
+pub const APPS_PATH_ENV_VAR: &str = "MAI_APPS_PATH";
+
+/// apps.jsonc locations in search order: `./`, `./main/`, `./.mai/` under `base`,
+/// then the global config directory.
+fn apps_search_paths(base: Option<&Path>, config_dir: &Path) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(base) = base {
+        paths.push(base.join("apps.jsonc"));
+        paths.push(base.join("main").join("apps.jsonc"));
+        paths.push(base.join(".mai").join("apps.jsonc"));
+    }
+    paths.push(config_dir.join("apps.jsonc"));
+    paths
+}
+
+/// Find the apps.jsonc to use: `MAI_APPS_PATH` if set, otherwise the first existing
+/// file in [`apps_search_paths`] from the current directory. `None` means the
+/// embedded default applies.
+pub fn find_apps_file() -> Result<Option<PathBuf>> {
+    if let Ok(path) = std::env::var(APPS_PATH_ENV_VAR) {
+        let path = PathBuf::from(shellexpand::tilde(&path).as_ref());
+        if !path.is_file() {
+            return Err(MultiAiError::Config(format!(
+                "{} points to {}, which is not a file",
+                APPS_PATH_ENV_VAR,
+                path.display()
+            )));
+        }
+        return Ok(Some(path));
+    }
+
     let config_dir = ProjectConfig::config_dir()
         .map_err(|e| MultiAiError::Config(format!("Could not determine config directory: {}", e)))?;
-    let apps_path = config_dir.join("apps.jsonc");
+    // A deleted cwd only rules out the local locations
+    let cwd = std::env::current_dir().ok();
+    Ok(apps_search_paths(cwd.as_deref(), &config_dir)
+        .into_iter()
+        .find(|p| p.is_file()))
+}
 
-    let content = if apps_path.exists() {
-        fs::read_to_string(&apps_path)
-            .map_err(|e| MultiAiError::Config(format!("Failed to read apps.jsonc: {}", e)))?
-    } else {
-        EMBEDDED_APPS_JSONC.to_string()
+/// Load AI apps from the apps.jsonc found by [`find_apps_file`].
+/// If there is none, falls back to the embedded default.
+pub fn load_apps() -> Result<Vec<AiApp>> {
+    let content = match find_apps_file()? {
+        Some(apps_path) => fs::read_to_string(&apps_path).map_err(|e| {
+            MultiAiError::Config(format!("Failed to read {}: {}", apps_path.display(), e))
+        })?,
+        None => EMBEDDED_APPS_JSONC.to_string(),
     };
 
     let parsed = jsonc_parser::parse_to_serde_value(&content, &Default::default())
@@ -410,15 +747,128 @@ pub fn load_apps() -> Result<Vec<AiApp>> {
     Ok(apps)
 }
 
-fn save_config(wizard: &WizardState) -> Result<()> {
-    let config = wizard.get_config();
+/// `mai init --list`: print the known apps grouped by name, without entering the wizard
+pub fn print_app_list() -> Result<()> {
+    let source = match find_apps_file()? {
+        Some(path) => path.display().to_string(),
+        None => "built-in defaults".to_string(),
+    };
+    println!("Apps from {}:\n", source);
+    print!("{}", format_app_list(&load_apps()?));
+    println!("\n* = pre-selected in pickers, meta = meta reviewer in mai review");
+    Ok(())
+}
+
+/// Each app name followed by its variants: slug, command and description
+fn format_app_list(apps: &[AiApp]) -> String {
+    let mut names: Vec<&str> = Vec::new();
+    for app in apps {
+        if !names.contains(&app.name.as_str()) {
+            names.push(&app.name);
+        }
+    }
+    let slug_width = apps.iter().map(|app| app.slug().len()).max().unwrap_or(0);
+    let command_width = apps.iter().map(|app| app.command.len()).max().unwrap_or(0);
 
+    let marks = |app: &AiApp| {
+        let mut marks = Vec::new();
+        if app.default {
+            marks.push("*");
+        }
+        if app.meta_review {
+            marks.push("meta");
+        }
+        match app.kind {
+            AppKind::Ai => {}
+            AppKind::Editor => marks.push("editor"),
+            AppKind::Shell => marks.push("shell"),
+        }
+        marks.join(",")
+    };
+    let marks_width = apps.iter().map(|app| marks(app).len()).max().unwrap_or(0);
+
+    let mut out = String::new();
+    for name in names {
+        out.push_str(name);
+        out.push('\n');
+        for app in apps.iter().filter(|app| app.name == name) {
+            let line = format!(
+                "  {:<slug_width$}  {:<command_width$}  {:<marks_width$}  {}",
+                app.slug(),
+                app.command,
+                marks(app),
+                app.description.as_deref().unwrap_or(""),
+            );
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Optional file in the global config directory listing app names, one per line,
+/// to pin to the top of the app pickers.
+const SERVICE_ORDER_FILE: &str = "service_order";
+
+/// Load apps like [`load_apps`], ordered for the `add` and review pickers by the
+/// `service_order` file when it exists.
+pub fn load_picker_apps() -> Result<Vec<AiApp>> {
+    let mut apps = load_apps()?;
+    if let Ok(config_dir) = ProjectConfig::config_dir()
+        && let Ok(content) = fs::read_to_string(config_dir.join(SERVICE_ORDER_FILE))
+    {
+        let order: Vec<&str> = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+        apply_service_order(&mut apps, &order);
+    }
+    Ok(apps)
+}
+
+/// Move the apps named in `order` to the front, in that order. The rest keep
+/// their apps.jsonc order, and unknown names are ignored.
+fn apply_service_order(apps: &mut [AiApp], order: &[&str]) {
+    apps.sort_by_key(|app| {
+        order
+            .iter()
+            .position(|name| *name == app.name)
+            .unwrap_or(order.len())
+    });
+}
+
+/// One-line JSON object for an app, listing only the fields that differ from their defaults.
+fn app_entry_json(app: &AiApp) -> String {
+    let quote = |value: &str| serde_json::Value::String(value.to_string()).to_string();
+    let mut fields = vec![
+        format!("\"name\":{}", quote(&app.name)),
+        format!("\"command\":{}", quote(&app.command)),
+    ];
+    if let Some(ref slug) = app.slug {
+        fields.push(format!("\"slug\":{}", quote(slug)));
+    }
+    if let Some(ref ultrathink) = app.ultrathink {
+        fields.push(format!("\"ultrathink\":{}", quote(ultrathink)));
+    }
+    if app.meta_review {
+        fields.push("\"meta_review\":true".to_string());
+    }
+    if app.auto_submit {
+        fields.push("\"auto_submit\":true".to_string());
+    }
+    if let Some(ref subdir) = app.subdir {
+        fields.push(format!("\"subdir\":{}", quote(subdir)));
+    }
+    format!("{{{}}}", fields.join(","))
+}
+
+/// `~/.config/multi-ai-cli/<name from the origin URL>.jsonc` for the project
+fn config_target_path(project_path: &Path) -> Result<PathBuf> {
     let config_dir = ProjectConfig::config_dir()
         .map_err(|e| MultiAiError::Config(format!("Could not determine config directory: {}", e)))?;
 
-    fs::create_dir_all(&config_dir)?;
-
-    let repo_url = crate::git::get_remote_origin_url(&wizard.project_path).ok_or_else(|| {
+    let repo_url = crate::git::get_remote_origin_url(project_path).ok_or_else(|| {
         MultiAiError::Config(
             "Could not determine git remote URL. Make sure you have a remote named 'origin'."
                 .to_string(),
@@ -429,23 +879,16 @@ fn save_config(wizard: &WizardState) -> Result<()> {
         "{}.jsonc",
         crate::git::generate_config_filename(&repo_url)
     );
-    let config_path = config_dir.join(&config_filename);
-
-    // Check if file exists
-    if fs::metadata(&config_path).is_ok() {
-        print!(
-            "\n{} already exists. Overwrite? [y/n]: ",
-            config_path.display()
-        );
-        io::Write::flush(&mut io::stdout())?;
+    Ok(config_dir.join(config_filename))
+}
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+/// Write the config; overwriting an existing file was already confirmed in the wizard
+fn save_config(wizard: &WizardState) -> Result<()> {
+    let config = wizard.get_config();
+    let config_path = &wizard.config_path;
 
-        if !matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
-            println!("Configuration not saved.");
-            return Ok(());
-        }
+    if let Some(config_dir) = config_path.parent() {
+        fs::create_dir_all(config_dir)?;
     }
 
     let worktrees_line = if let Some(ref wt_path) = wizard.worktrees_path {
@@ -457,17 +900,33 @@ fn save_config(wizard: &WizardState) -> Result<()> {
         String::new()
     };
 
+    let (apps_comment, apps_lines) = if config.ai_apps.is_empty() {
+        ("AI tools are configured globally — run 'mai apps' to edit", String::new())
+    } else {
+        let entries: Vec<String> = config
+            .ai_apps
+            .iter()
+            .map(|app| format!("    {}", app_entry_json(app)))
+            .collect();
+        (
+            "AI tools below override the global apps.jsonc for this project",
+            format!("\n  \"ai_apps\": [\n{}\n  ],", entries.join(",\n")),
+        )
+    };
+
     let json_content = format!(
         r#"{{
   // Multi-AI CLI configuration
   // Generated by: mai init
-  // AI tools are configured globally — run 'mai apps' to edit
-  "project_path": "{}",{}
+  // {}
+  "project_path": "{}",{}{}
   "terminals_per_column": {},  // Number of terminal panes per column (first is AI command, rest are shells)
   "mode": "{}"                 // iterm2 | tmux-single-window | tmux-multi-window
 }}"#,
+        apps_comment,
         wizard.project_path.display(),
         worktrees_line,
+        apps_lines,
         config.terminals_per_column,
         match wizard.terminal_mode {
             Mode::Iterm2 => "iterm2",
@@ -476,8 +935,8 @@ fn save_config(wizard: &WizardState) -> Result<()> {
         },
     );
 
-    fs::write(&config_path, json_content)?;
-    println!("\n✓ Configuration saved to {}", config_path.display());
+    fs::write(config_path, json_content)?;
+    println!("\n{}Configuration saved to {}", util::icon("✓ "), config_path.display());
     println!("  Project path: {}", wizard.project_path.display());
     println!("\nYou can now run:");
     println!("  mai add <branch-prefix>              # Uses mode from config");
@@ -486,3 +945,100 @@ fn save_config(wizard: &WizardState) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        app_entry_json, apply_service_order, apps_search_paths, format_app_list, parse_apps_file,
+    };
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_format_app_list_groups_variants_by_name() {
+        let apps: Vec<crate::config::AiApp> = serde_json::from_str(
+            r#"[
+                {"name": "claude", "command": "claude", "description": "Standard"},
+                {"name": "codex", "command": "codex"},
+                {"name": "claude", "slug": "claude-yolo", "command": "claude --yolo", "default": true}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            format_app_list(&apps),
+            "claude\n  claude       claude            Standard\n  claude-yolo  claude --yolo  *\ncodex\n  codex        codex\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_apps_file() {
+        let apps = parse_apps_file(
+            "# my agents\nclaude --model opus\n\n/opt/bin/codex\n  gemini -y  \n",
+        )
+        .unwrap();
+        let names: Vec<&str> = apps.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["claude", "codex", "gemini"]);
+        assert_eq!(apps[0].command, "claude --model opus");
+        assert_eq!(apps[2].command, "gemini -y");
+    }
+
+    #[test]
+    fn test_parse_apps_file_rejects_empty_and_duplicates() {
+        assert!(parse_apps_file("# nothing\n\n").is_err());
+        assert!(parse_apps_file("codex\ncodex\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_apps_file_names_a_repeated_tool_by_its_slug() {
+        let apps = parse_apps_file("claude\nclaude --model opus\n").unwrap();
+        let names: Vec<&str> = apps.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["claude", "claude-opus"]);
+
+        // The second claude's slug is already the first line's name
+        let err = parse_apps_file("claude-opus --yolo\nclaude\nclaude --model opus\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("app name 'claude-opus'"), "{}", err);
+    }
+
+    #[test]
+    fn test_app_entry_json_omits_defaults() {
+        let mut apps = parse_apps_file("ollama run llama3\n").unwrap();
+        assert_eq!(
+            app_entry_json(&apps[0]),
+            r#"{"name":"ollama","command":"ollama run llama3"}"#
+        );
+
+        apps[0].ultrathink = Some("think hard".to_string());
+        apps[0].auto_submit = true;
+        assert_eq!(
+            app_entry_json(&apps[0]),
+            r#"{"name":"ollama","command":"ollama run llama3","ultrathink":"think hard","auto_submit":true}"#
+        );
+    }
+
+    #[test]
+    fn test_apply_service_order() {
+        let mut apps = parse_apps_file("claude\ncodex\ngemini\namp\n").unwrap();
+        apply_service_order(&mut apps, &["gemini", "unknown", "claude"]);
+        let names: Vec<&str> = apps.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["gemini", "claude", "codex", "amp"]);
+    }
+
+    #[test]
+    fn test_apps_search_paths_order() {
+        let config_dir = Path::new("/home/u/.config/multi-ai-cli");
+        assert_eq!(
+            apps_search_paths(Some(Path::new("/proj")), config_dir),
+            vec![
+                PathBuf::from("/proj/apps.jsonc"),
+                PathBuf::from("/proj/main/apps.jsonc"),
+                PathBuf::from("/proj/.mai/apps.jsonc"),
+                config_dir.join("apps.jsonc"),
+            ]
+        );
+        assert_eq!(
+            apps_search_paths(None, config_dir),
+            vec![config_dir.join("apps.jsonc")]
+        );
+    }
+}
@@ -1,14 +1,39 @@
 #![cfg_attr(not(target_os = "macos"), allow(dead_code))]
 
-use crate::config::AiApp;
+use crate::applescript;
+use crate::config::{shell_quote, AiApp, Mode};
 use crate::error::{MultiAiError, Result};
+use crate::tmux::PaneRole;
+use crate::util;
 use std::process::Command;
+use std::time::Duration;
+
+/// iTerm2 session variable mai tags each pane it creates with (see `pane_tag`), so
+/// `mai send` can find the tab and its columns again
+const PANE_TAG_VARIABLE: &str = "user.mai_pane";
+
+/// A pane of a tab mai created, as found through its tag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ITerm2Pane {
+    /// iTerm2's unique id for the session (pane)
+    pub id: String,
+    /// `{project}-{prefix}`, like the tmux session name
+    pub session: String,
+    pub column: usize,
+    pub row: usize,
+    pub role: Option<PaneRole>,
+}
 
 pub struct ITerm2Manager {
-    #[allow(dead_code)]
     project: String,
     branch_prefix: String,
     terminals_per_column: usize,
+    initial_prompt: Option<String>,
+    max_columns: Option<usize>,
+    max_columns_per_window: Option<usize>,
+    /// How long the layout script waits for each new pane's shell
+    pane_delay: Duration,
+    label: Option<String>,
 }
 
 impl ITerm2Manager {
@@ -17,10 +42,48 @@ impl ITerm2Manager {
             project: project.to_string(),
             branch_prefix: branch_prefix.to_string(),
             terminals_per_column,
+            initial_prompt: None,
+            max_columns: None,
+            max_columns_per_window: None,
+            pane_delay: Duration::from_secs(1),
+            label: None,
         }
     }
 
-    /// Create a single tab with all AI apps in columns
+    /// Type `prompt` into every AI pane once the apps have had time to start
+    pub fn with_initial_prompt(mut self, prompt: Option<&str>) -> Self {
+        self.initial_prompt = prompt.map(str::to_string);
+        self
+    }
+
+    /// Warn before creating more columns than fit at a usable width
+    pub fn with_max_columns(mut self, max_columns: usize) -> Self {
+        self.max_columns = Some(max_columns);
+        self
+    }
+
+    /// Spread the apps over new windows of at most `max` columns each once there are
+    /// more than `max` of them
+    pub fn with_max_columns_per_window(mut self, max: Option<usize>) -> Self {
+        self.max_columns_per_window = max;
+        self
+    }
+
+    /// Wait `delay` for each new pane's shell before writing to it, and twice that for
+    /// the first pane of a new tab or window (`iterm2_pane_delay_ms`)
+    pub fn with_pane_delay(mut self, delay: Duration) -> Self {
+        self.pane_delay = delay;
+        self
+    }
+
+    /// Name the tab `label` instead of the branch prefix
+    pub fn with_label(mut self, label: Option<&str>) -> Self {
+        self.label = label.map(str::to_string);
+        self
+    }
+
+    /// Create a single tab with all AI apps in columns, or one window per
+    /// `max_columns_per_window` apps when there are more.
     /// Each app gets a vertical column with configurable number of panes (first for AI command, rest for shells)
     pub fn create_tabs_per_app(
         &self,
@@ -31,32 +94,108 @@ impl ITerm2Manager {
             return Ok(());
         }
 
+        if !applescript::is_osascript_available() {
+            return Err(MultiAiError::ITerm2(
+                "osascript not found; use tmux mode (--mode tmux-single-window)".to_string(),
+            ));
+        }
+
+        let per_window = self
+            .max_columns_per_window
+            .filter(|&max| max > 0 && worktree_paths.len() > max)
+            .unwrap_or(worktree_paths.len());
+
+        let chunks: Vec<&[(AiApp, String)]> = worktree_paths.chunks(per_window).collect();
+
+        // iTerm2 splits columns evenly with no minimum width, and cramped TUIs render garbage.
+        // Wide apps take several columns, so count columns rather than apps.
+        let widest = chunks
+            .iter()
+            .map(|chunk| chunk.iter().map(|(app, _)| app.columns()).sum::<usize>())
+            .max()
+            .unwrap_or(0);
+        if let Some(max_columns) = self.max_columns
+            && widest > max_columns
+        {
+            eprintln!(
+                "{}{} columns likely leaves each iTerm2 column too narrow (about {} fit at min_pane_width).",
+                util::icon("⚠ "),
+                widest,
+                max_columns
+            );
+            eprintln!("  Use a wider window, fewer apps (--select on continue), max_columns_per_window, or tmux multi-window mode.");
+        }
+
+        let title = self.label.as_deref().unwrap_or(&self.branch_prefix);
+        for (index, chunk) in chunks.iter().enumerate() {
+            let window = (chunks.len() > 1).then_some((index + 1, chunks.len()));
+            let applescript = self.layout_script(chunk, index * per_window, window, title);
+            run_layout_script(&applescript, chunk.len())?;
+        }
+
+        Ok(())
+    }
+
+    /// AppleScript laying out `worktree_paths` as columns, numbered from `first_column`
+    /// in the pane tags. `window` is `(number, count)` when the apps are spread over
+    /// new windows; otherwise they get a new tab in the current window. A wide app's
+    /// extra columns are tagged as rows below its own, so `mai send` sees one column
+    /// per app.
+    fn layout_script(
+        &self,
+        worktree_paths: &[(AiApp, String)],
+        first_column: usize,
+        window: Option<(usize, usize)>,
+        title: &str,
+    ) -> String {
+        let pane_delay = delay_seconds(self.pane_delay);
+        let first_pane_delay = delay_seconds(self.pane_delay * 2);
+
         // Build AppleScript for creating column-based layout
-        let mut applescript = String::from(
-            r#"
+        let mut applescript = String::from(match window {
+            Some(_) => {
+                r#"
+tell application "iTerm"
+    -- Create a new window
+    create window with default profile
+    tell current window
+        
+        tell current session"#
+            }
+            None => {
+                r#"
 tell application "iTerm"
     tell current window
         -- Create a new tab
         create tab with default profile
         
-        tell current session"#,
-        );
+        tell current session"#
+            }
+        });
 
-        let num_apps = worktree_paths.len();
+        // One entry per column: the app's index, the app, its worktree and which of the
+        // app's columns it is (0 runs the app, the rest are extra shell columns)
+        let columns: Vec<(usize, &AiApp, &String, usize)> = worktree_paths
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (app, path))| (0..app.columns()).map(move |extra| (i, app, path, extra)))
+            .collect();
+        let num_columns = columns.len();
 
         // Handle single app case
-        if num_apps == 1 {
+        if num_columns == 1 {
             let (app, path) = &worktree_paths[0];
+            let launch_line = applescript::escape(&app.launch_line(path, &Mode::Iterm2));
+            let path = applescript::escape(&shell_quote(path));
             applescript.push_str(&format!(
                 r#"
             -- Single app: {} (1x{} layout)
             -- Wait for shell to initialize
-            delay 2
-            write text "cd {} && {}""#,
+            delay {first_pane_delay}
+            write text "{}""#,
                 app.as_str(),
                 self.terminals_per_column,
-                path,
-                app.command()
+                launch_line
             ));
 
             // Create additional panes for shells
@@ -74,7 +213,7 @@ tell application "iTerm"
                             r#"
             set pane{} to (split horizontally with default profile)
             tell pane{}
-                delay 1
+                delay {pane_delay}
                 write text "cd {}""#,
                             i, i, path
                         ));
@@ -86,7 +225,7 @@ tell application "iTerm"
                 
                 set pane{} to (split horizontally with default profile)
                 tell pane{}
-                    delay 1
+                    delay {pane_delay}
                     write text "cd {}""#,
                             i, i, path
                         ));
@@ -108,13 +247,13 @@ tell application "iTerm"
             // Multiple apps: create dynamic column layout
             applescript.push_str(&format!(
                 r#"
-            -- {} apps: {}x{} layout
+            -- {} columns: {}x{} layout
             -- Create {} columns"#,
-                num_apps, num_apps, self.terminals_per_column, num_apps
+                num_columns, num_columns, self.terminals_per_column, num_columns
             ));
 
             // Create vertical splits for columns (skip first column as it's the current session)
-            for i in 2..=num_apps {
+            for i in 2..=num_columns {
                 if i == 2 {
                     applescript.push_str(
                         "\n            set col2 to (split vertically with default profile)",
@@ -144,7 +283,7 @@ tell application "iTerm"
                 }
 
                 // For other columns
-                for i in 2..=num_apps {
+                for i in 2..=num_columns {
                     for pane_idx in 2..=self.terminals_per_column {
                         if pane_idx == 2 {
                             applescript.push_str(&format!("\n            tell col{}\n                set col{}Pane{} to (split horizontally with default profile)\n            end tell", i, i, pane_idx));
@@ -157,22 +296,30 @@ tell application "iTerm"
 
             // Populate panes
             applescript.push_str("\n            \n            -- Populate panes");
-            for (i, (app, path)) in worktree_paths.iter().enumerate() {
+            for (i, &(app_index, app, path, extra)) in columns.iter().enumerate() {
                 let col_num = i + 1;
+                let launch_line = applescript::escape(&app.launch_line(path, &Mode::Iterm2));
+                let path = applescript::escape(&shell_quote(path));
+                // Extra columns of a wide app only get a shell
+                let (top_pane, top_line) = if extra > 0 {
+                    ("shell (extra column)", format!("cd {}", path))
+                } else {
+                    ("app command", launch_line)
+                };
 
                 if i == 0 {
                     // First column uses current session
                     applescript.push_str(&format!(
                         r#"
             -- App {}: {} (column {})
-            -- Top pane: AI command
-            delay 2
-            write text "cd {} && {}""#,
-                        i + 1,
+            -- Top pane: {}
+            delay {first_pane_delay}
+            write text "{}""#,
+                        app_index + 1,
                         app.as_str(),
                         col_num,
-                        path,
-                        app.command()
+                        top_pane,
+                        top_line
                     ));
 
                     // Additional panes for shells
@@ -182,7 +329,7 @@ tell application "iTerm"
             
             -- Pane {}: shell
             tell col1Pane{}
-                delay 1
+                delay {pane_delay}
                 write text "cd {}"
             end tell"#,
                             pane_idx, pane_idx, path
@@ -194,17 +341,17 @@ tell application "iTerm"
                         r#"
             
             -- App {}: {} (column {})
-            -- Top pane: AI command
+            -- Top pane: {}
             tell col{}
-                delay 1
-                write text "cd {} && {}"
+                delay {pane_delay}
+                write text "{}"
             end tell"#,
-                        i + 1,
+                        app_index + 1,
                         app.as_str(),
                         col_num,
+                        top_pane,
                         col_num,
-                        path,
-                        app.command()
+                        top_line
                     ));
 
                     // Additional panes for shells
@@ -214,7 +361,7 @@ tell application "iTerm"
             
             -- Pane {}: shell
             tell col{}Pane{}
-                delay 1
+                delay {pane_delay}
                 write text "cd {}"
             end tell"#,
                             pane_idx, col_num, pane_idx, path
@@ -224,13 +371,68 @@ tell application "iTerm"
             }
         }
 
-        // Set the tab name
+        // Tag every pane so `mai send` can find the tab and its columns later
+        applescript.push_str("\n            \n            -- Tag panes for mai send");
+        let session = format!("{}-{}", self.project, self.branch_prefix);
+        for (column, &(app_index, app, _, extra)) in columns.iter().enumerate() {
+            for row in 0..self.terminals_per_column {
+                let role = match (extra, row) {
+                    (0, 0) => PaneRole::for_app(app),
+                    (0, _) => PaneRole::Shell,
+                    _ => PaneRole::Extra,
+                };
+                let tag_row = extra * self.terminals_per_column + row;
+                let set_tag = format!(
+                    "set variable named \"{}\" to \"{}\"",
+                    PANE_TAG_VARIABLE,
+                    applescript::escape(&pane_tag(&session, first_column + app_index, tag_row, role))
+                );
+                match pane_reference(num_columns, column, row) {
+                    Some(reference) => applescript.push_str(&format!("\n            tell {} to {}", reference, set_tag)),
+                    None => applescript.push_str(&format!("\n            {}", set_tag)),
+                }
+            }
+        }
+
+        // Send the initial prompt to each AI pane after a delay
+        if let Some(prompt) = &self.initial_prompt {
+            let escaped_prompt = applescript::escape(prompt);
+            applescript.push_str("\n            \n            -- Initial prompt\n            delay 5");
+            for (i, &(_, app, _, extra)) in columns.iter().enumerate() {
+                if !app.is_ai() || extra > 0 {
+                    continue;
+                }
+                // Column 1 is the current session; the others are colN
+                let indent = if i == 0 { "            " } else { "                " };
+                let mut write = format!("\n{}write text \"{}\"", indent, escaped_prompt);
+                // `write text` ends with a newline; the extra blank line submits the
+                // prompt, except for agents that submit on the first Enter
+                if !app.auto_submit {
+                    write.push_str(&format!("\n{0}delay 0.5\n{0}write text \"\"", indent));
+                }
+                if i == 0 {
+                    applescript.push_str(&write);
+                } else {
+                    applescript.push_str(&format!(
+                        "\n            tell col{}\n                delay 1{}\n            end tell",
+                        i + 1,
+                        write
+                    ));
+                }
+            }
+        }
+
+        // Set the tab name, numbered when the apps span several windows
+        let title = match window {
+            Some((number, count)) => format!("{} ({}/{})", title, number, count),
+            None => title.to_string(),
+        };
         applescript.push_str(&format!(
             r#"
             
             -- Set tab title
             set name to "{}""#,
-            self.branch_prefix
+            applescript::escape(&title)
         ));
 
         applescript.push_str(
@@ -240,32 +442,283 @@ tell application "iTerm"
 end tell"#,
         );
 
-        // Debug: Log the AppleScript being executed
+        applescript
+    }
+}
+
+fn run_layout_script(applescript: &str, app_count: usize) -> Result<()> {
+    // Debug: Log the AppleScript being executed
+    eprintln!("DEBUG: Executing AppleScript for {} apps", app_count);
+
+    // Execute the AppleScript
+    let output = Command::new("osascript")
+        .arg("-e")
+        .arg(applescript)
+        .output()
+        .map_err(|e| MultiAiError::ITerm2(format!("Failed to execute AppleScript: {}", e)))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        eprintln!("DEBUG: AppleScript stderr: {}", error);
         eprintln!(
-            "DEBUG: Executing AppleScript for {} apps",
-            worktree_paths.len()
+            "DEBUG: AppleScript stdout: {}",
+            String::from_utf8_lossy(&output.stdout)
         );
+        return Err(MultiAiError::ITerm2(format!(
+            "AppleScript failed: {}",
+            error
+        )));
+    }
 
-        // Execute the AppleScript
-        let output = Command::new("osascript")
-            .arg("-e")
-            .arg(&applescript)
-            .output()
-            .map_err(|e| MultiAiError::ITerm2(format!("Failed to execute AppleScript: {}", e)))?;
+    Ok(())
+}
 
-        if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            eprintln!("DEBUG: AppleScript stderr: {}", error);
-            eprintln!(
-                "DEBUG: AppleScript stdout: {}",
-                String::from_utf8_lossy(&output.stdout)
-            );
-            return Err(MultiAiError::ITerm2(format!(
-                "AppleScript failed: {}",
-                error
-            )));
-        }
+/// Seconds for an AppleScript `delay`, without a trailing `.0` for whole seconds
+fn delay_seconds(delay: Duration) -> String {
+    format!("{}", delay.as_secs_f64())
+}
 
-        Ok(())
+/// AppleScript variable `create_tabs_per_app` keeps the pane at `column`/`row` in;
+/// None for the tab's first pane, which the script is already talking to
+fn pane_reference(num_columns: usize, column: usize, row: usize) -> Option<String> {
+    match (column, row) {
+        (0, 0) => None,
+        (0, row) if num_columns == 1 => Some(format!("pane{}", row + 1)),
+        (0, row) => Some(format!("col1Pane{}", row + 1)),
+        (column, 0) => Some(format!("col{}", column + 1)),
+        (column, row) => Some(format!("col{}Pane{}", column + 1, row + 1)),
+    }
+}
+
+/// Value of `PANE_TAG_VARIABLE`: `{session}:{column}:{row}:{role}`
+fn pane_tag(session: &str, column: usize, row: usize, role: PaneRole) -> String {
+    format!("{}:{}:{}:{}", session, column, row, role.as_str())
+}
+
+/// Parse a `{session id} {tag}` line listed by `tagged_panes`
+fn parse_tagged_pane(line: &str) -> Option<ITerm2Pane> {
+    let (id, tag) = line.split_once(' ')?;
+    let mut fields = tag.rsplitn(4, ':');
+    let role = PaneRole::parse(fields.next()?);
+    let row = fields.next()?.parse().ok()?;
+    let column = fields.next()?.parse().ok()?;
+    let session = fields.next().filter(|session| !session.is_empty())?;
+    Some(ITerm2Pane {
+        id: id.to_string(),
+        session: session.to_string(),
+        column,
+        row,
+        role,
+    })
+}
+
+/// Panes of all open tabs mai created, in every iTerm2 window. Tabs closed by hand
+/// simply aren't listed.
+pub fn tagged_panes() -> Result<Vec<ITerm2Pane>> {
+    if !applescript::is_osascript_available() {
+        return Err(MultiAiError::ITerm2(
+            "osascript not found; sending to iTerm2 panes needs macOS".to_string(),
+        ));
+    }
+    let script = format!(
+        r#"
+tell application "iTerm"
+    set output to ""
+    repeat with w in windows
+        repeat with t in tabs of w
+            repeat with s in sessions of t
+                tell s to set paneTag to (variable named "{}")
+                if paneTag is not missing value and paneTag is not "" then
+                    set output to output & (id of s) & " " & paneTag & linefeed
+                end if
+            end repeat
+        end repeat
+    end repeat
+    return output
+end tell"#,
+        PANE_TAG_VARIABLE
+    );
+    Ok(run_osascript(&script)?.lines().filter_map(parse_tagged_pane).collect())
+}
+
+/// AppleScript closing every tab whose first session is named `name` (iTerm2 may
+/// append the running job, as in `name (zsh)`) and returning how many it closed.
+/// The tabs are collected first so closing one doesn't shift the loop.
+fn close_tabs_script(name: &str) -> String {
+    format!(
+        r#"
+tell application "iTerm"
+    set matching to {{}}
+    repeat with w in windows
+        repeat with t in tabs of w
+            set tabName to name of current session of t
+            if tabName is "{name}" or tabName starts with "{name} (" then
+                set end of matching to contents of t
+            end if
+        end repeat
+    end repeat
+    repeat with t in matching
+        close t
+    end repeat
+    return count of matching
+end tell"#,
+        name = applescript::escape(name)
+    )
+}
+
+/// Close the tabs named `name`, such as a review's tab; returns how many were closed
+pub fn close_tabs_named(name: &str) -> Result<usize> {
+    if !applescript::is_osascript_available() {
+        return Err(MultiAiError::ITerm2("osascript not found".to_string()));
+    }
+    let closed = run_osascript(&close_tabs_script(name))?;
+    closed.trim().parse().map_err(|_| {
+        MultiAiError::ITerm2(format!("Unexpected AppleScript result: {}", closed))
+    })
+}
+
+/// AppleScript running `commands` inside `tell` for the session with `session_id`,
+/// failing when no window has it anymore (e.g. its tab was closed)
+fn tell_session_script(session_id: &str, commands: &str) -> String {
+    let session_id = applescript::escape(session_id);
+    format!(
+        r#"
+tell application "iTerm"
+    repeat with w in windows
+        repeat with t in tabs of w
+            repeat with s in sessions of t
+                set theSession to contents of s
+                if id of theSession is "{id}" then
+                    tell theSession
+{commands}
+                    end tell
+                end if
+            end repeat
+        end repeat
+    end repeat
+    error "iTerm2 session {id} no longer exists; was its tab closed?"
+end tell"#,
+        id = session_id,
+        commands = commands
+    )
+}
+
+/// Type `text` into a session, then press Enter
+pub fn write_text(session_id: &str, text: &str) -> Result<()> {
+    // Submitting separately works like the tmux paste, where some apps would take
+    // a trailing newline as part of the text
+    let commands = format!(
+        "                        write text \"{}\" newline no\n                        delay 0.1\n                        write text \"\"\n                        return",
+        applescript::escape(text)
+    );
+    run_osascript(&tell_session_script(session_id, &commands)).map(|_| ())
+}
+
+/// Text currently visible in a session
+pub fn session_contents(session_id: &str) -> Result<String> {
+    run_osascript(&tell_session_script(session_id, "                        return contents of theSession"))
+}
+
+fn run_osascript(script: &str) -> Result<String> {
+    let output = Command::new("osascript")
+        .arg("-e")
+        .arg(script)
+        .output()
+        .map_err(|e| MultiAiError::ITerm2(format!("Failed to execute AppleScript: {}", e)))?;
+    if !output.status.success() {
+        return Err(MultiAiError::ITerm2(format!(
+            "AppleScript failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // osascript ends the result with a newline of its own
+    Ok(stdout.strip_suffix('\n').unwrap_or(&stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{close_tabs_script, pane_reference, pane_tag, parse_tagged_pane, ITerm2Manager, ITerm2Pane};
+    use crate::config::AiApp;
+    use crate::tmux::PaneRole;
+    use std::time::Duration;
+
+    fn app(name: &str) -> AiApp {
+        serde_json::from_value(serde_json::json!({"name": name, "command": name})).unwrap()
+    }
+
+    #[test]
+    fn test_pane_tags_round_trip() {
+        let tag = pane_tag("proj-feat", 1, 2, PaneRole::Shell);
+        assert_eq!(
+            parse_tagged_pane(&format!("ABC-123 {}", tag)),
+            Some(ITerm2Pane {
+                id: "ABC-123".to_string(),
+                session: "proj-feat".to_string(),
+                column: 1,
+                row: 2,
+                role: Some(PaneRole::Shell),
+            })
+        );
+        assert_eq!(parse_tagged_pane("ABC-123 proj-feat:x:0:ai"), None);
+        assert_eq!(parse_tagged_pane("ABC-123"), None);
+
+        assert_eq!(pane_reference(3, 0, 0), None);
+        assert_eq!(pane_reference(1, 0, 1).as_deref(), Some("pane2"));
+        assert_eq!(pane_reference(3, 0, 1).as_deref(), Some("col1Pane2"));
+        assert_eq!(pane_reference(3, 2, 0).as_deref(), Some("col3"));
+        assert_eq!(pane_reference(3, 2, 1).as_deref(), Some("col3Pane2"));
+    }
+
+    #[test]
+    fn test_layout_script_for_second_window() {
+        let manager = ITerm2Manager::new("proj", "feat", 2);
+        let chunk = vec![(app("gemini"), "/wt/feat-gemini".to_string()), (app("qwen"), "/wt/feat-qwen".to_string())];
+
+        let script = manager.layout_script(&chunk, 2, Some((2, 3)), "feat");
+        assert!(script.contains("create window with default profile"));
+        assert!(!script.contains("create tab"));
+        assert!(script.contains("proj-feat:2:0:ai"));
+        assert!(script.contains("proj-feat:3:1:shell"));
+        assert!(script.contains("set name to \"feat (2/3)\""));
+
+        let script = manager.layout_script(&chunk, 0, None, "feat");
+        assert!(script.contains("create tab with default profile"));
+        assert!(script.contains("set name to \"feat\""));
+        assert!(script.contains("delay 2\n") && script.contains("delay 1\n"));
+
+        let script = manager
+            .with_pane_delay(Duration::from_millis(1500))
+            .layout_script(&chunk, 0, None, "feat");
+        assert!(script.contains("delay 3\n") && script.contains("delay 1.5\n"));
+    }
+
+    #[test]
+    fn test_close_tabs_script_matches_the_name() {
+        let script = close_tabs_script("feat \"x\"-review-01");
+        assert!(script.contains(r#"if tabName is "feat \"x\"-review-01" or tabName starts with "feat \"x\"-review-01 (" then"#));
+        assert!(script.contains("return count of matching"));
+    }
+
+    #[test]
+    fn test_layout_script_for_wide_app() {
+        let apps: Vec<AiApp> = serde_json::from_str(
+            r#"[{"name": "claude", "command": "claude", "columns": 2}, {"name": "codex", "command": "codex"}]"#,
+        )
+        .unwrap();
+        let chunk = vec![
+            (apps[0].clone(), "/wt/feat-claude".to_string()),
+            (apps[1].clone(), "/wt/feat-codex".to_string()),
+        ];
+        let script = ITerm2Manager::new("proj", "feat", 2).layout_script(&chunk, 0, None, "feat");
+
+        // Three columns; the extra one is tagged below claude's own panes
+        assert!(script.contains("set col3 to"));
+        assert!(script.contains("proj-feat:0:0:ai") && script.contains("proj-feat:0:1:shell"));
+        assert!(script.contains("proj-feat:0:2:extra") && script.contains("proj-feat:0:3:extra"));
+        assert!(script.contains("proj-feat:1:0:ai"));
+        // claude runs only in its first column; the second gets a shell
+        assert_eq!(script.matches("&& claude").count(), 1);
+        assert!(script.contains("tell col2\n                delay 1\n                write text \"cd /wt/feat-claude\""));
     }
 }
@@ -1,22 +1,27 @@
+mod applescript;
 mod config;
 mod error;
+mod events;
 mod git;
 mod init;
-#[cfg(target_os = "macos")]
 mod iterm2;
 mod picker;
 mod review;
+mod selftest;
 mod send;
+mod tail;
 mod tmux;
+mod util;
 mod worktree;
 
 use clap::{Parser, ValueEnum};
 use config::{Mode, ProjectConfig, TmuxLayout};
 use error::{MultiAiError, Result};
+use events::{Event, Reporter};
 use indicatif::{ProgressBar, ProgressStyle};
 #[cfg(target_os = "macos")]
 use iterm2::ITerm2Manager;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
@@ -33,26 +38,121 @@ struct Args {
     #[arg(short = 'v', long = "version", action = clap::ArgAction::Version)]
     _version: (),
 
+    #[arg(
+        short = 'i',
+        long,
+        help = "Open the command menu (default when run without a command in a terminal)"
+    )]
+    interactive: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Disable colors and emoji in output (also enabled by setting NO_COLOR)"
+    )]
+    no_color: bool,
+
     #[command(subcommand)]
     command: Option<Command>,
 
-    #[arg(help = "Path to project directory")]
+    #[arg(
+        requires = "branch_prefix",
+        help = "Shorthand for `mai add`: `mai <PROJECT_PATH> <BRANCH_PREFIX>` runs `add <BRANCH_PREFIX>` from PROJECT_PATH"
+    )]
     project_path: Option<String>,
 
-    #[arg(help = "Branch prefix (e.g., 'vercel-theme')")]
+    #[arg(help = "Branch prefix for the `mai <PROJECT_PATH> <BRANCH_PREFIX>` shorthand (e.g., 'vercel-theme')")]
     branch_prefix: Option<String>,
 }
 
 #[derive(Parser, Debug)]
 enum Command {
     #[command(about = "Initialize multi-ai-config.jsonc file interactively")]
-    Init,
+    Init {
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Use the commands in PATH (one per line) as this project's AI apps"
+        )]
+        from_file: Option<PathBuf>,
+
+        #[arg(
+            long,
+            conflicts_with = "from_file",
+            help = "Print the AI apps and command variants from apps.jsonc and exit"
+        )]
+        list: bool,
+    },
 
     #[command(about = "Add worktrees and session for multiple AI tools")]
     Add {
         #[arg(help = "Branch prefix for the worktrees (interactive picker if omitted)")]
         branch_prefix: Option<String>,
 
+        #[arg(
+            long = "arg",
+            value_name = "APP=ARGS",
+            value_parser = parse_extra_arg,
+            help = "Append extra arguments to an app's command for this run (e.g. --arg claude=\"--model opus\"); repeatable"
+        )]
+        extra_args: Vec<(String, Vec<String>)>,
+
+        #[arg(
+            long = "app-command",
+            value_name = "APP=COMMAND",
+            value_parser = parse_app_command,
+            help = "Replace an app's command for this run (e.g. --app-command claude=\"claude --resume\"); repeatable"
+        )]
+        app_commands: Vec<(String, String)>,
+
+        #[arg(
+            long,
+            value_name = "NAMES",
+            value_delimiter = ',',
+            help = "Column order by app name or slug, e.g. claude,codex (unlisted apps follow in config order)"
+        )]
+        app_order: Vec<String>,
+
+        #[arg(
+            long,
+            value_name = "TEXT",
+            help = "Send TEXT to every AI pane once the apps have started"
+        )]
+        initial_prompt: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "TEXT",
+            help = "Title for the iTerm2 tab or tmux terminal title (default: the branch prefix)"
+        )]
+        label: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Export the variables in this dotenv file in every AI pane (overrides env_file in the config)"
+        )]
+        env_file: Option<PathBuf>,
+
+        #[arg(
+            long,
+            help = "If some worktrees fail, still open the session with the ones that were created"
+        )]
+        keep_going: bool,
+
+        #[arg(
+            long,
+            help = "Also create a grouped tmux session <session>-view to watch the same windows from another terminal"
+        )]
+        group: bool,
+
+        #[arg(
+            long,
+            requires = "branch_prefix",
+            help = "Print progress as JSON lines on stdout instead of human-readable output (does not attach)"
+        )]
+        events: bool,
+
         #[arg(
             long,
             help = "Use tmux multi-window layout (legacy flag)",
@@ -89,6 +189,34 @@ enum Command {
             help = "Skip confirmation prompt and remove immediately"
         )]
         force: bool,
+
+        #[arg(
+            long,
+            help = "Keep the tmux session running (overrides remove_kills_session in the config)"
+        )]
+        no_kill_session: bool,
+
+        #[arg(
+            long,
+            requires_all = ["branch_prefix", "force"],
+            help = "Print progress as JSON lines on stdout instead of human-readable output"
+        )]
+        events: bool,
+    },
+
+    #[command(about = "Move a branch prefix's worktrees to a different base directory")]
+    Move {
+        #[arg(help = "Branch prefix whose worktrees to move")]
+        branch_prefix: String,
+
+        #[arg(help = "New base directory; each worktree keeps its directory name under it")]
+        new_path: PathBuf,
+    },
+
+    #[command(about = "Remove environments whose branches were merged or deleted upstream")]
+    Gc {
+        #[arg(long, help = "Only list the stale environments; remove nothing")]
+        dry_run: bool,
     },
 
     #[command(about = "Continue working on existing worktrees (creates new session/tab)")]
@@ -96,6 +224,27 @@ enum Command {
         #[arg(help = "Branch prefix for the existing worktrees")]
         branch_prefix: String,
 
+        #[arg(
+            long,
+            help = "Pick which of the existing worktrees' apps to relaunch"
+        )]
+        select: bool,
+
+        #[arg(
+            long,
+            value_name = "NAMES",
+            value_delimiter = ',',
+            help = "Column order by app name or slug, e.g. claude,codex (unlisted apps follow in config order)"
+        )]
+        app_order: Vec<String>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Export the variables in this dotenv file in every AI pane (overrides env_file in the config)"
+        )]
+        env_file: Option<PathBuf>,
+
         #[arg(
             long,
             help = "Use tmux multi-window layout (legacy flag)",
@@ -116,6 +265,27 @@ enum Command {
         #[arg(help = "Branch prefix for the existing worktrees")]
         branch_prefix: String,
 
+        #[arg(
+            long,
+            help = "Pick which of the existing worktrees' apps to relaunch"
+        )]
+        select: bool,
+
+        #[arg(
+            long,
+            value_name = "NAMES",
+            value_delimiter = ',',
+            help = "Column order by app name or slug, e.g. claude,codex (unlisted apps follow in config order)"
+        )]
+        app_order: Vec<String>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Export the variables in this dotenv file in every AI pane (overrides env_file in the config)"
+        )]
+        env_file: Option<PathBuf>,
+
         #[arg(
             long,
             help = "Use tmux multi-window layout (legacy flag)",
@@ -132,23 +302,242 @@ enum Command {
     },
 
     #[command(about = "Send text to a running session via TUI")]
-    Send,
+    #[command(group(clap::ArgGroup::new("input").args(["text", "file"])))]
+    Send {
+        #[arg(
+            long,
+            help = "Print the session's panes and how they map to app columns, then exit"
+        )]
+        list_panes: bool,
+
+        #[arg(
+            long,
+            value_name = "PREFIX",
+            help = "With --app or --list-panes, use the session for this branch prefix (required when several are running)"
+        )]
+        session: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "PATTERN",
+            requires = "input",
+            conflicts_with = "list_panes",
+            help = "Send TEXT without the TUI to apps matching PATTERN (name or slug, '*' wildcards; repeatable)"
+        )]
+        app: Vec<String>,
+
+        #[arg(long, requires = "app", help = "With --app, send to the command pane instead of the prompt pane")]
+        command: bool,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            requires = "app",
+            help = "With --app, send the contents of PATH instead of TEXT"
+        )]
+        file: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "TEXT",
+            requires = "app",
+            help = "With --app, wait until TEXT appears in every pane that was sent to, then exit"
+        )]
+        wait_for: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "DURATION",
+            requires = "wait_for",
+            value_parser = util::parse_duration,
+            help = "Fail if --wait-for hasn't matched after DURATION (e.g. 30m, 2h; default: 10m)"
+        )]
+        timeout: Option<Duration>,
+
+        #[arg(
+            long,
+            requires = "app",
+            help = "With --app, list the session, app and pane each target resolves to and the text, then ask before sending"
+        )]
+        dry_run: bool,
+
+        #[arg(requires = "app", help = "Text to send with --app")]
+        text: Option<String>,
+    },
+
+    #[command(about = "Attach to a running tmux session without touching its worktrees")]
+    Attach {
+        #[arg(help = "Branch prefix of the running session")]
+        branch_prefix: String,
+    },
+
+    #[command(about = "Add or remove shell panes of a running tmux session to match terminals_per_column")]
+    Relayout {
+        #[arg(help = "Branch prefix of the running session")]
+        branch_prefix: String,
+
+        #[arg(
+            long,
+            value_parser = parse_pane_count,
+            help = "Panes per app, AI pane included (default: terminals_per_column)"
+        )]
+        panes: Option<usize>,
+
+        #[arg(short = 'f', long = "force", help = "Don't ask before killing shell panes")]
+        force: bool,
+    },
+
+    #[command(about = "Stream new output from all AI panes of a session")]
+    Tail {
+        #[arg(help = "Branch prefix of the running session")]
+        branch_prefix: String,
+    },
+
+    #[command(about = "Toggle tmux synchronize-panes so typing goes to every pane of a session")]
+    Sync {
+        #[arg(help = "Branch prefix of the running session")]
+        branch_prefix: String,
+
+        #[arg(value_enum, help = "Turn synchronization on or off (toggles when omitted)")]
+        state: Option<SyncState>,
+    },
 
     #[command(about = "Launch interactive multi-AI code review")]
+    #[command(group(clap::ArgGroup::new("review_source").args(["branch", "working_tree"])))]
     Review {
         /// Branch to review (skips branch selection if exact match found)
-        #[arg(index = 1)]
+        #[arg(index = 1, conflicts_with = "cleanup")]
         branch: Option<String>,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["branch", "cleanup", "base_only"],
+            help = "Review the current worktree's uncommitted changes instead of a branch"
+        )]
+        working_tree: bool,
+
+        #[arg(
+            long,
+            value_name = "PREFIX",
+            help = "Remove review worktrees for a review prefix (e.g. feature-review-02) or every review of a source branch"
+        )]
+        cleanup: Option<String>,
+
+        #[arg(
+            short = 'f',
+            long = "force",
+            requires = "cleanup",
+            help = "Skip confirmation prompt when cleaning up"
+        )]
+        force: bool,
+
+        #[arg(
+            long,
+            conflicts_with = "cleanup",
+            help = "Skip the meta-review (no meta-* worktrees or columns)"
+        )]
+        no_meta: bool,
+
+        #[arg(
+            long,
+            conflicts_with = "cleanup",
+            help = "Check out the base branch and give reviewers the branch's changes as CHANGES.diff"
+        )]
+        base_only: bool,
+
+        #[arg(
+            long,
+            value_name = "REF",
+            requires = "base_only",
+            help = "Base for --base-only (default: origin's default branch, else main or master)"
+        )]
+        base: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with_all = ["cleanup", "replay"],
+            help = "Write the chosen reviewers, prompt and base to a JSON manifest for --replay"
+        )]
+        save: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with = "cleanup",
+            help = "Re-run a review saved with --save; only the branch is chosen"
+        )]
+        replay: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "N",
+            requires = "review_source",
+            conflicts_with_all = ["cleanup", "replay"],
+            help = "Skip the wizard: review BRANCH (or --working-tree) with the first N apps marked default in apps.jsonc and the default prompt"
+        )]
+        reviewers: Option<usize>,
+
+        #[arg(
+            long,
+            value_name = "APP",
+            requires = "reviewers",
+            conflicts_with = "no_meta",
+            help = "With --reviewers, run APP as the meta-reviewer"
+        )]
+        meta: Option<String>,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["cleanup", "save"],
+            help = "Print the reviewers, worktrees and prompts the review would use, then exit without creating anything"
+        )]
+        explain: bool,
     },
 
     #[command(about = "Open the project config file in the default application")]
-    Config,
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
 
     #[command(about = "List worktree environments and their worktrees")]
-    List,
+    List {
+        #[arg(
+            long,
+            value_name = "DURATION",
+            value_parser = util::parse_duration,
+            help = "Only show environments modified within DURATION (e.g. 30m, 2h, 7d, 1w)"
+        )]
+        since: Option<Duration>,
 
-    #[command(about = "Open the global AI tools configuration file")]
+        #[arg(long, help = "Print the environments as a JSON array")]
+        json: bool,
+    },
+
+    #[command(about = "Open the AI tools configuration file (apps.jsonc)")]
     Apps,
+
+    #[command(
+        visible_alias = "doctor",
+        about = "Check that gwt, tmux, the configs and the app commands are in place"
+    )]
+    Status,
+
+    #[command(about = "Check the tmux send pipeline in a scratch session, without touching the repo")]
+    Selftest,
+}
+
+#[derive(Parser, Debug)]
+enum ConfigAction {
+    #[command(about = "Edit the project config in $EDITOR and validate it on save")]
+    Edit,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+enum SyncState {
+    On,
+    Off,
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
@@ -172,18 +561,49 @@ impl From<ModeOverride> for Mode {
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    if args.no_color || util::no_color_env() {
+        util::disable_color();
+    }
+    if let Err(e) = util::install_interrupt_handler() {
+        eprintln!("warning: could not install Ctrl+C handler: {}", e);
+    }
 
     match args.command {
-        Some(Command::Init) => init::run_init(),
+        Some(Command::Init { list: true, .. }) => init::print_app_list(),
+        Some(Command::Init { from_file, .. }) => init::run_init(from_file),
         Some(Command::Add {
             branch_prefix,
+            extra_args,
+            app_commands,
+            app_order,
+            initial_prompt,
+            label,
+            env_file,
+            keep_going,
+            group,
+            events,
             tmux,
             mode,
         }) => {
+            let options = AddOptions {
+                extra_args: &extra_args,
+                app_commands: &app_commands,
+                app_order: &app_order,
+                initial_prompt: initial_prompt.as_deref(),
+                label: label.as_deref(),
+                env_file: env_file.as_deref(),
+                keep_going,
+                group,
+                reporter: Reporter::new(events),
+            };
             if let Some(prefix) = branch_prefix {
-                create_command(prefix, tmux, mode, None)
+                let attach = !events;
+                report_failure(
+                    options.reporter,
+                    create_command(prefix, tmux, mode, None, &options, attach),
+                )
             } else {
-                interactive_add_command(tmux, mode)
+                interactive_add_command(tmux, mode, &options)
             }
         }
         Some(Command::Remove {
@@ -191,28 +611,127 @@ fn main() -> Result<()> {
             tmux,
             mode,
             force,
+            no_kill_session,
+            events,
         }) => {
             if let Some(prefix) = branch_prefix {
-                remove_command(prefix, tmux, mode, force)
+                let reporter = Reporter::new(events);
+                report_failure(
+                    reporter,
+                    remove_command(prefix, tmux, mode, force, no_kill_session, reporter),
+                )
             } else {
-                interactive_remove_command(tmux, mode, force)
+                interactive_remove_command(tmux, mode, force, no_kill_session)
             }
         }
+        Some(Command::Move {
+            branch_prefix,
+            new_path,
+        }) => move_command(branch_prefix, new_path),
+        Some(Command::Gc { dry_run }) => gc_command(dry_run),
         Some(Command::Continue {
             branch_prefix,
+            select,
+            app_order,
+            env_file,
             tmux,
             mode,
-        }) => continue_command(branch_prefix, tmux, mode),
+        }) => continue_command(branch_prefix, tmux, mode, &app_order, select, env_file.as_deref()),
         Some(Command::Resume {
             branch_prefix,
+            select,
+            app_order,
+            env_file,
             tmux,
             mode,
-        }) => continue_command(branch_prefix, tmux, mode),
-        Some(Command::Send) => send_command(),
-        Some(Command::Review { branch }) => review_command(branch),
-        Some(Command::List) => list_command(),
-        Some(Command::Config) => config_command(),
+        }) => continue_command(branch_prefix, tmux, mode, &app_order, select, env_file.as_deref()),
+        Some(Command::Send {
+            list_panes,
+            session,
+            app,
+            command,
+            file,
+            wait_for,
+            timeout,
+            dry_run,
+            text,
+        }) => {
+            let text = match file {
+                Some(path) => Some(std::fs::read_to_string(&path).map_err(|e| {
+                    MultiAiError::Config(format!("Failed to read {}: {}", path.display(), e))
+                })?),
+                None => text,
+            };
+            let wait_for = wait_for.as_deref().map(|sentinel| send::WaitFor {
+                sentinel,
+                timeout: timeout.unwrap_or(send::DEFAULT_WAIT_TIMEOUT),
+            });
+            send_command(send::SendOptions {
+                list_panes,
+                session,
+                apps: app,
+                to_command_pane: command,
+                text,
+                wait_for,
+                dry_run,
+            })
+        }
+        Some(Command::Attach { branch_prefix }) => attach_command(branch_prefix),
+        Some(Command::Relayout {
+            branch_prefix,
+            panes,
+            force,
+        }) => relayout_command(branch_prefix, panes, force),
+        Some(Command::Tail { branch_prefix }) => tail_command(branch_prefix),
+        Some(Command::Sync {
+            branch_prefix,
+            state,
+        }) => sync_command(branch_prefix, state),
+        Some(Command::Review {
+            branch,
+            working_tree,
+            cleanup,
+            force,
+            no_meta,
+            base_only,
+            base,
+            save,
+            replay,
+            reviewers,
+            meta,
+            explain,
+        }) => review_command(
+            cleanup,
+            force,
+            review::ReviewOptions {
+                branch,
+                no_meta,
+                base_only,
+                base,
+                working_tree,
+                save,
+                replay,
+                quick: reviewers.map(|reviewers| review::QuickReview { reviewers, meta }),
+                explain,
+            },
+        ),
+        Some(Command::List { since, json }) => list_command(since, json),
+        Some(Command::Config { action: None }) => config_command(),
+        Some(Command::Config {
+            action: Some(ConfigAction::Edit),
+        }) => config_edit_command(),
         Some(Command::Apps) => apps_command(),
+        Some(Command::Status) => status_command(),
+        Some(Command::Selftest) => selftest::run_selftest(),
+        None if args.project_path.is_some() => {
+            let (Some(path), Some(prefix)) = (args.project_path, args.branch_prefix) else {
+                unreachable!("clap requires branch_prefix with project_path");
+            };
+            shorthand_add_command(&path, prefix)
+        }
+        None if args.interactive || (io::stdin().is_terminal() && io::stdout().is_terminal()) => {
+            menu_command()
+        }
         None => {
             eprintln!("Error: Command required. Use 'mai add <branch-prefix>' or 'mai remove <branch-prefix>'");
             eprintln!("Run 'mai --help' for more information.");
@@ -221,49 +740,235 @@ fn main() -> Result<()> {
     }
 }
 
-#[inline]
-fn system_default_mode() -> Mode {
-    #[cfg(target_os = "macos")]
-    {
-        Mode::Iterm2
-    }
-    #[cfg(not(target_os = "macos"))]
-    {
-        Mode::TmuxSingleWindow
-    }
+/// `mai <path> <prefix>`: `mai add <prefix>` run from `path`
+fn shorthand_add_command(path: &str, prefix: String) -> Result<()> {
+    let dir = PathBuf::from(shellexpand::tilde(path).as_ref());
+    std::env::set_current_dir(&dir).map_err(|e| {
+        MultiAiError::Config(format!("Cannot use {} as the project directory: {}", dir.display(), e))
+    })?;
+    create_command(prefix, false, None, None, &AddOptions::default(), true)
 }
 
-/// Find git-worktree-config.jsonc by checking:
-/// 1. Current directory
-/// 2. ./main/ subdirectory
-/// 3. Global gwt config by repo URL: ~/.config/git-worktree-cli/projects/{repo-name}.jsonc
-/// 4. Global gwt config by path match (worktreesPath or projectPath in config)
-fn find_gwt_config_file(base_path: &Path) -> Option<PathBuf> {
-    // First check current directory
-    let current_path = base_path.join("git-worktree-config.jsonc");
-    if current_path.exists() {
-        return Some(current_path);
-    }
-
-    // Then check ./main/ subdirectory
-    let main_path = base_path.join("main").join("git-worktree-config.jsonc");
-    if main_path.exists() {
-        return Some(main_path);
-    }
+#[derive(Clone, Copy)]
+enum MenuChoice {
+    Add,
+    Continue,
+    Remove,
+    Send,
+    Review,
+    Init,
+}
 
-    // Check global gwt configs - gwt uses ~/.config/ not the platform config dir
-    let home_dir = dirs::home_dir()?;
-    let gwt_projects_dir = home_dir.join(".config").join("git-worktree-cli").join("projects");
-    if !gwt_projects_dir.exists() {
-        return None;
-    }
+const MENU_CHOICES: &[(MenuChoice, &str, &str)] = &[
+    (MenuChoice::Add, "add", "Create worktrees and a session for AI tools"),
+    (MenuChoice::Continue, "continue", "Open a session for existing worktrees"),
+    (MenuChoice::Remove, "remove", "Remove worktrees and their session"),
+    (MenuChoice::Send, "send", "Send a prompt or command to running AI panes"),
+    (MenuChoice::Review, "review", "Review a branch with multiple AI tools"),
+    (MenuChoice::Init, "init", "Create a config for this project"),
+];
+
+/// Top-level menu for bare `mai`; each entry runs the command's interactive form.
+fn menu_command() -> Result<()> {
+    let items: Vec<(String, String)> = MENU_CHOICES
+        .iter()
+        .map(|(_, name, about)| (name.to_string(), about.to_string()))
+        .collect();
+    let Some(index) = picker::run_single_picker("mai", &items)? else {
+        return Ok(());
+    };
 
-    // Try to find by repo URL first
-    if let Some(repo_url) = git::get_remote_origin_url(base_path) {
-        let config_filename = format!("{}.jsonc", git::generate_config_filename(&repo_url));
-        let global_path = gwt_projects_dir.join(&config_filename);
-        if global_path.exists() {
-            return Some(global_path);
+    match MENU_CHOICES[index].0 {
+        MenuChoice::Add => interactive_add_command(false, None, &AddOptions::default()),
+        MenuChoice::Continue => {
+            let Some(prefix) = pick_existing_prefix()? else {
+                println!("Cancelled.");
+                return Ok(());
+            };
+            continue_command(prefix, false, None, &[], false, None)
+        }
+        MenuChoice::Remove => interactive_remove_command(false, None, false, false),
+        MenuChoice::Send => send_command(send::SendOptions::default()),
+        MenuChoice::Review => review_command(None, false, review::ReviewOptions::default()),
+        MenuChoice::Init => init::run_init(None),
+    }
+}
+
+/// Let the user choose one of the project's existing worktree prefixes.
+fn pick_existing_prefix() -> Result<Option<String>> {
+    let current_dir = config::current_dir()?;
+
+    let (_config_path, project_config, project_path) = ProjectConfig::find_config(&current_dir)
+        .map_err(|e| MultiAiError::Config(format!("Failed to find config: {}", e)))?
+        .ok_or_else(|| MultiAiError::Config(
+            "Config not found in ~/.config/multi-ai-cli/. Run 'mai init' from your project directory to create one.".to_string()
+        ))?;
+    let worktree_manager = make_worktree_manager(&project_config, project_path);
+
+    let groups = discover_all_prefixes(&worktree_manager, &project_config);
+    if groups.is_empty() {
+        println!("No worktree prefixes found.");
+        return Ok(None);
+    }
+
+    let items: Vec<(String, String)> = groups
+        .iter()
+        .map(|(prefix, worktrees)| (prefix.clone(), worktrees.join(", ")))
+        .collect();
+    Ok(picker::run_single_picker("Continue Environment", &items)?
+        .map(|index| groups[index].0.clone()))
+}
+
+/// Parse an `--arg` value of the form `<app>=<extra args>`, splitting the args into
+/// shell words so a quoted argument with spaces stays one word.
+fn parse_extra_arg(value: &str) -> std::result::Result<(String, Vec<String>), String> {
+    match value.split_once('=') {
+        Some((app, extra)) if !app.trim().is_empty() => {
+            Ok((app.trim().to_string(), config::split_shell_words(extra)?))
+        }
+        _ => Err(format!("expected <app>=<args>, got '{}'", value)),
+    }
+}
+
+/// Parse `--panes`: a whole number of at least 1
+fn parse_pane_count(value: &str) -> std::result::Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(count) if count >= 1 => Ok(count),
+        _ => Err(format!("invalid pane count '{}': expected a whole number of at least 1", value)),
+    }
+}
+
+/// Parse `APP=COMMAND` for `--app-command`
+fn parse_app_command(value: &str) -> std::result::Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((app, command)) if !app.trim().is_empty() && !command.trim().is_empty() => {
+            Ok((app.trim().to_string(), command.trim().to_string()))
+        }
+        _ => Err(format!("expected <app>=<command>, got '{}'", value)),
+    }
+}
+
+/// Reorder items so apps named in `--app-order` (by name or slug) come first, in that
+/// order; the rest keep their original order. Every name must match at least one app.
+fn apply_app_order<T>(
+    mut items: Vec<T>,
+    app_order: &[String],
+    app_of: impl Fn(&T) -> &config::AiApp,
+) -> Result<Vec<T>> {
+    let matches = |app: &config::AiApp, name: &str| app.name == name || app.slug() == name;
+
+    if let Some(unknown) = app_order
+        .iter()
+        .find(|name| !items.iter().any(|item| matches(app_of(item), name)))
+    {
+        return Err(MultiAiError::Config(format!(
+            "--app-order: no app named '{}' (available: {})",
+            unknown,
+            items
+                .iter()
+                .map(|item| app_of(item).name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+
+    // Stable sort keeps config order for apps with the same rank (including unlisted ones)
+    items.sort_by_cached_key(|item| {
+        app_order
+            .iter()
+            .position(|name| matches(app_of(item), name))
+            .unwrap_or(app_order.len())
+    });
+    Ok(items)
+}
+
+/// Index of the app named `target` (by name or slug), for `flag`'s error message.
+fn find_run_app(ai_apps: &[config::AiApp], target: &str, flag: &str) -> Result<usize> {
+    ai_apps
+        .iter()
+        .position(|app| app.name == target || app.slug() == target)
+        .ok_or_else(|| {
+            MultiAiError::Config(format!(
+                "{}: no app named '{}' in this run (available: {})",
+                flag,
+                target,
+                ai_apps
+                    .iter()
+                    .map(|app| app.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })
+}
+
+/// Replace the commands of the apps named by `--app-command`.
+fn apply_app_commands(
+    mut ai_apps: Vec<config::AiApp>,
+    app_commands: &[(String, String)],
+) -> Result<Vec<config::AiApp>> {
+    for (target, command) in app_commands {
+        let index = find_run_app(&ai_apps, target, "--app-command")?;
+        ai_apps[index] = ai_apps[index].with_command(command);
+    }
+    Ok(ai_apps)
+}
+
+/// Append `--arg` extras to the matching apps (by name or slug). Runs after
+/// `--app-command`, so both can be combined for the same app.
+fn apply_extra_args(
+    mut ai_apps: Vec<config::AiApp>,
+    extra_args: &[(String, Vec<String>)],
+) -> Result<Vec<config::AiApp>> {
+    for (target, extra) in extra_args {
+        let index = find_run_app(&ai_apps, target, "--arg")?;
+        ai_apps[index] = ai_apps[index].with_extra_args(extra);
+    }
+    Ok(ai_apps)
+}
+
+#[inline]
+fn system_default_mode() -> Mode {
+    #[cfg(target_os = "macos")]
+    {
+        Mode::Iterm2
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Mode::TmuxSingleWindow
+    }
+}
+
+/// Find git-worktree-config.jsonc by checking:
+/// 1. Current directory
+/// 2. ./main/ subdirectory
+/// 3. Global gwt config by repo URL: ~/.config/git-worktree-cli/projects/{repo-name}.jsonc
+/// 4. Global gwt config by path match (worktreesPath or projectPath in config)
+fn find_gwt_config_file(base_path: &Path) -> Option<PathBuf> {
+    // First check current directory
+    let current_path = base_path.join("git-worktree-config.jsonc");
+    if current_path.exists() {
+        return Some(current_path);
+    }
+
+    // Then check ./main/ subdirectory
+    let main_path = base_path.join("main").join("git-worktree-config.jsonc");
+    if main_path.exists() {
+        return Some(main_path);
+    }
+
+    // Check global gwt configs - gwt uses ~/.config/ not the platform config dir
+    let home_dir = dirs::home_dir()?;
+    let gwt_projects_dir = home_dir.join(".config").join("git-worktree-cli").join("projects");
+    if !gwt_projects_dir.exists() {
+        return None;
+    }
+
+    // Try to find by repo URL first
+    if let Some(repo_url) = git::get_remote_origin_url(base_path) {
+        let config_filename = format!("{}.jsonc", git::generate_config_filename(&repo_url));
+        let global_path = gwt_projects_dir.join(&config_filename);
+        if global_path.exists() {
+            return Some(global_path);
         }
     }
 
@@ -287,42 +992,39 @@ fn find_gwt_config_file(base_path: &Path) -> Option<PathBuf> {
     for entry in std::fs::read_dir(&gwt_projects_dir).ok()?.flatten() {
         let path = entry.path();
 
-        if path.extension().map(|e| e == "jsonc").unwrap_or(false) {
-            if let Ok(content) = std::fs::read_to_string(&path) {
-                if let Ok(Some(serde_json::Value::Object(map))) =
-                    jsonc_parser::parse_to_serde_value(&content, &Default::default())
+        if path.extension().map(|e| e == "jsonc").unwrap_or(false)
+            && let Ok(content) = std::fs::read_to_string(&path)
+            && let Ok(Some(serde_json::Value::Object(map))) =
+                jsonc_parser::parse_to_serde_value(&content, &Default::default())
+        {
+            // Check both worktreesPath and projectPath
+            // gwt uses camelCase: worktreesPath, projectPath
+            let matches = {
+                let mut found = false;
+
+                // Check worktreesPath
+                if let Some(serde_json::Value::String(worktrees_path)) =
+                    map.get("worktreesPath")
                 {
-                    // Check both worktreesPath and projectPath
-                    // gwt uses camelCase: worktreesPath, projectPath
-                    let matches = {
-                        let mut found = false;
-
-                        // Check worktreesPath
-                        if let Some(serde_json::Value::String(worktrees_path)) =
-                            map.get("worktreesPath")
-                        {
-                            let wt_path = PathBuf::from(worktrees_path);
-                            found = check_path_match(&base_path_canonical, base_path, &wt_path);
-                        }
+                    let wt_path = PathBuf::from(worktrees_path);
+                    found = check_path_match(&base_path_canonical, base_path, &wt_path);
+                }
 
-                        // Check projectPath
-                        if !found {
-                            if let Some(serde_json::Value::String(project_path)) =
-                                map.get("projectPath")
-                            {
-                                let proj_path = PathBuf::from(project_path);
-                                found =
-                                    check_path_match(&base_path_canonical, base_path, &proj_path);
-                            }
-                        }
+                // Check projectPath
+                if !found
+                    && let Some(serde_json::Value::String(project_path)) =
+                        map.get("projectPath")
+                {
+                    let proj_path = PathBuf::from(project_path);
+                    found =
+                        check_path_match(&base_path_canonical, base_path, &proj_path);
+                }
 
-                        found
-                    };
+                found
+            };
 
-                    if matches {
-                        return Some(path);
-                    }
-                }
+            if matches {
+                return Some(path);
             }
         }
     }
@@ -359,6 +1061,7 @@ fn discover_worktree_branches(worktree_manager: &WorktreeManager, branch_prefix:
 fn interactive_add_command(
     cli_tmux: bool,
     mode_override: Option<ModeOverride>,
+    options: &AddOptions,
 ) -> Result<()> {
     let result = picker::run_app_picker(None)?;
     let Some(result) = result else {
@@ -371,16 +1074,23 @@ fn interactive_add_command(
         return Ok(());
     }
 
-    create_command(result.env_name, cli_tmux, mode_override, Some(result.selected_apps))
+    create_command(
+        result.env_name,
+        cli_tmux,
+        mode_override,
+        Some(result.selected_apps),
+        options,
+        true,
+    )
 }
 
 fn interactive_remove_command(
     cli_tmux: bool,
     mode_override: Option<ModeOverride>,
     force: bool,
+    no_kill_session: bool,
 ) -> Result<()> {
-    let current_dir = std::env::current_dir()
-        .map_err(|e| MultiAiError::Config(format!("Failed to get current directory: {}", e)))?;
+    let current_dir = config::current_dir()?;
 
     let (_config_path, project_config, project_path) = ProjectConfig::find_config(&current_dir)
         .map_err(|e| MultiAiError::Config(format!("Failed to find config: {}", e)))?
@@ -437,41 +1147,150 @@ fn interactive_remove_command(
         mode = project_config.mode.clone();
     }
 
+    let kill_sessions = project_config.remove_kills_session() && !no_kill_session;
+
     // Show combined confirmation
     if !force {
-        println!("⚠️  You are about to remove:");
+        println!("{}You are about to remove:", util::icon("⚠️  "));
         for (prefix, branches) in &all_branches {
             println!("  [{}]", prefix);
             for branch in branches {
                 println!("    • {}", branch);
             }
             match mode {
-                Some(Mode::TmuxMultiWindow) | Some(Mode::TmuxSingleWindow) => {
+                Some(Mode::TmuxMultiWindow) | Some(Mode::TmuxSingleWindow) if kill_sessions => {
                     println!("    ⊘ tmux session: {}-{}", project_name, prefix);
                 }
                 _ => {}
             }
         }
         println!();
-        if !ask_confirmation("Are you sure you want to remove these worktrees and sessions?")? {
+        let question = if kill_sessions {
+            "Are you sure you want to remove these worktrees and sessions?"
+        } else {
+            "Are you sure you want to remove these worktrees (tmux sessions are kept)?"
+        };
+        if !ask_confirmation(question)? {
             println!("Removal cancelled.");
             return Ok(());
         }
     }
 
+    remove_environments(&project_name, worktree_manager, &all_branches, kill_sessions);
+
+    Ok(())
+}
+
+fn gc_command(dry_run: bool) -> Result<()> {
+    let current_dir = config::current_dir()?;
+
+    let (config_path, project_config, project_path) = ProjectConfig::find_config(&current_dir)
+        .map_err(|e| MultiAiError::Config(format!("Failed to find config: {}", e)))?
+        .ok_or_else(|| MultiAiError::Config(
+            "Config not found in ~/.config/multi-ai-cli/. Run 'mai init' from your project directory to create one.".to_string()
+        ))?;
+
+    println!("Using config: {}", config_path.display());
+
+    let project_name = project_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| MultiAiError::Config("Invalid project path".to_string()))?
+        .to_string();
+    let worktree_manager = make_worktree_manager(&project_config, project_path.clone());
+
+    let base = git::default_base_ref(&project_path).ok_or_else(|| {
+        MultiAiError::Worktree(
+            "Could not determine the default branch (no origin/HEAD, main or master)".to_string(),
+        )
+    })?;
+
+    // Branches deleted on the remote only show up as gone after a prune
+    let sp = spinner("Fetching from origin...");
+    git::fetch(&project_path, true);
+    sp.finish_and_clear();
+
+    let finished = git::finished_branches(&project_path, &base);
+    let wt_dir = worktree_manager.worktrees_path().to_path_buf();
+
+    // An environment is stale once every one of its branches is finished
+    let mut stale: Vec<(String, Vec<String>)> = Vec::new();
+    for (prefix, worktrees) in discover_all_prefixes(&worktree_manager, &project_config) {
+        if !worktrees.iter().all(|wt| finished.contains_key(wt)) {
+            continue;
+        }
+        if TmuxManager::new(&project_name, &prefix).session_exists().unwrap_or(false) {
+            println!("Skipping {}: its tmux session is still running", prefix);
+            continue;
+        }
+        if let Some(dirty) = worktrees.iter().find(|wt| git::has_uncommitted_changes(&wt_dir.join(wt))) {
+            println!("Skipping {}: {} has uncommitted changes", prefix, dirty);
+            continue;
+        }
+        stale.push((prefix, worktrees));
+    }
+
+    if stale.is_empty() {
+        println!("No stale environments (no branches merged into {} or deleted upstream).", base);
+        return Ok(());
+    }
+
+    println!("Stale environments (branches merged into {} or deleted upstream):", base);
+    for (prefix, worktrees) in &stale {
+        println!("  [{}]", prefix);
+        for wt in worktrees {
+            let reason = match finished[wt] {
+                git::FinishedReason::Merged => "merged",
+                git::FinishedReason::UpstreamGone => "deleted upstream",
+            };
+            println!("    • {} ({})", wt, reason);
+        }
+    }
+    println!();
+
+    if dry_run {
+        println!("Dry run: nothing was removed.");
+        return Ok(());
+    }
+
+    if !WorktreeManager::has_gwt_cli() {
+        return Err(MultiAiError::Worktree(
+            "gwt CLI is not installed. Please install from https://github.com/mikko-kohtala/git-worktree-cli".to_string()
+        ));
+    }
+    if !ask_confirmation("Remove these environments (worktrees and tmux sessions)?")? {
+        println!("Removal cancelled.");
+        return Ok(());
+    }
+
+    remove_environments(&project_name, worktree_manager, &stale, true);
+    Ok(())
+}
+
+/// Kills the tmux session of each `(prefix, worktrees)` environment (unless
+/// `kill_sessions` is false), then removes all the worktrees in parallel, reporting
+/// each result
+fn remove_environments(
+    project_name: &str,
+    worktree_manager: WorktreeManager,
+    environments: &[(String, Vec<String>)],
+    kill_sessions: bool,
+) {
     // Kill tmux sessions first (fast, sequential)
-    for prefix in &selected {
-        let tmux_manager = TmuxManager::new(&project_name, prefix);
-        match tmux_manager.kill_session() {
-            Ok(_) => println!("  ✓ Tmux session '{}-{}' removed or not present", project_name, prefix),
-            Err(e) => eprintln!("  ⚠ Tmux session '{}-{}' cleanup: {}", project_name, prefix, e),
+    if kill_sessions {
+        for (prefix, _) in environments {
+            let tmux_manager = TmuxManager::new(project_name, prefix);
+            match tmux_manager.kill_session() {
+                Ok(_) => println!("  {}Tmux session '{}-{}' removed or not present", util::icon("✓ "), project_name, prefix),
+                Err(e) => eprintln!("  {}Tmux session '{}-{}' cleanup: {}", util::icon("⚠ "), project_name, prefix, e),
+            }
         }
     }
 
     // Collect unique branch names across all prefix groups to avoid duplicate removals
     let mut seen = std::collections::HashSet::new();
     let mut unique_branches: Vec<String> = Vec::new();
-    for (_prefix, branches) in &all_branches {
+    for (_prefix, branches) in environments {
         for branch in branches {
             if seen.insert(branch.clone()) {
                 unique_branches.push(branch.clone());
@@ -497,21 +1316,19 @@ fn interactive_remove_command(
     let mut failed = false;
     for (branch_name, result) in rx {
         match result {
-            Ok(_) => println!("  ✓ Removed worktree: {}", branch_name),
+            Ok(_) => println!("  {}Removed worktree: {}", util::icon("✓ "), branch_name),
             Err(e) => {
-                eprintln!("  ✗ Failed to remove worktree {}: {}", branch_name, e);
+                eprintln!("  {}Failed to remove worktree {}: {}", util::icon("✗ "), branch_name, e);
                 failed = true;
             }
         }
     }
 
     if failed {
-        println!("\n⚠ Cleanup completed with errors.");
+        println!("\n{}Cleanup completed with errors.", util::icon("⚠ "));
     } else {
-        println!("\n✓ Cleanup completed!");
+        println!("\n{}Cleanup completed!", util::icon("✓ "));
     }
-
-    Ok(())
 }
 
 /// Recursively collect worktree directory names relative to `base`.
@@ -613,14 +1430,47 @@ fn discover_all_prefixes(
     prefix_map.into_iter().collect()
 }
 
+/// How long AI apps get to start up before `--initial-prompt` is pasted in
+const INITIAL_PROMPT_DELAY: Duration = Duration::from_secs(5);
+
+/// Per-run options for `mai add` that don't come from the project config
+#[derive(Default)]
+struct AddOptions<'a> {
+    extra_args: &'a [(String, Vec<String>)],
+    app_commands: &'a [(String, String)],
+    app_order: &'a [String],
+    initial_prompt: Option<&'a str>,
+    label: Option<&'a str>,
+    /// Dotenv file from `--env-file`, in place of the config's `env_file`
+    env_file: Option<&'a Path>,
+    /// Open the session with whichever worktrees were created when some fail
+    keep_going: bool,
+    /// Also create a tmux session grouped with the new one
+    group: bool,
+    reporter: Reporter,
+}
+
+/// In events mode, reports a failed command as an `error` event before returning it
+fn report_failure(reporter: Reporter, result: Result<()>) -> Result<()> {
+    if let Err(e) = &result {
+        reporter.emit(Event::Error {
+            app: None,
+            message: e.to_string(),
+        });
+    }
+    result
+}
+
 fn create_command(
     mut branch_prefix: String,
     cli_tmux: bool,
     mode_override: Option<ModeOverride>,
     override_apps: Option<Vec<config::AiApp>>,
+    options: &AddOptions,
+    attach: bool,
 ) -> Result<()> {
-    let current_dir = std::env::current_dir()
-        .map_err(|e| MultiAiError::Config(format!("Failed to get current directory: {}", e)))?;
+    let reporter = options.reporter;
+    let current_dir = config::current_dir()?;
 
     // Find config using the new search order
     let (config_path, project_config, project_path) = ProjectConfig::find_config(&current_dir)
@@ -629,7 +1479,7 @@ fn create_command(
             "Config not found in ~/.config/multi-ai-cli/. Run 'mai init' from your project directory to create one.".to_string()
         ))?;
 
-    println!("Using config: {}", config_path.display());
+    reporter.say(format!("Using config: {}", config_path.display()));
     let sp = spinner("Validating environment...");
 
     // Check for git-worktree-config.jsonc (at the project path)
@@ -646,7 +1496,7 @@ fn create_command(
 
     let worktree_manager = make_worktree_manager(&project_config, project_path.clone());
 
-    if !worktree_manager.has_gwt_cli() {
+    if !WorktreeManager::has_gwt_cli() {
         return Err(MultiAiError::Worktree(
             "gwt CLI is not installed. Please install from https://github.com/mikko-kohtala/git-worktree-cli".to_string()
         ));
@@ -658,12 +1508,21 @@ fn create_command(
         ));
     }
 
-    sp.finish_with_message("Environment validated");
+    if reporter.events() {
+        sp.finish_and_clear();
+    } else {
+        sp.finish_with_message("Environment validated");
+    }
 
     let ai_apps = if let Some(apps) = override_apps {
         apps
     } else if !project_config.ai_apps.is_empty() {
         project_config.ai_apps.clone()
+    } else if reporter.events() {
+        // The picker would draw over the event stream
+        return Err(MultiAiError::Config(
+            "No ai_apps configured for this project; --events needs them in the config".to_string(),
+        ));
     } else {
         // No apps in config — launch interactive picker with prefilled env name
         let result = picker::run_app_picker(Some(&branch_prefix))?;
@@ -678,17 +1537,79 @@ fn create_command(
         branch_prefix = result.env_name;
         result.selected_apps
     };
+    let ai_apps = apply_app_order(ai_apps, options.app_order, |app| app)?;
+    let ai_apps = apply_app_commands(ai_apps, options.app_commands)?;
+    let ai_apps = apply_extra_args(ai_apps, options.extra_args)?;
+    let env = project_config.env_vars(&project_path, options.env_file)?;
+    let ai_apps: Vec<config::AiApp> = ai_apps.iter().map(|app| app.with_env(&env)).collect();
+
+    // Slashes are fine (feature/login nests the worktrees under feature/), but a
+    // name git rejects would only fail once gwt is creating the worktrees
+    if let Some(branch) = ai_apps
+        .iter()
+        .map(|app| format!("{}-{}", branch_prefix, app.slug()))
+        .find(|branch| !git::is_valid_branch_name(branch))
+    {
+        return Err(MultiAiError::Config(format!(
+            "'{}' is not a valid git branch name; choose a different branch prefix",
+            branch
+        )));
+    }
+
+    // Determine mode: CLI override > legacy --tmux > config file > system default
+    let mut mode = mode_override.map(Into::into);
+    if mode.is_none() && cli_tmux {
+        mode = Some(Mode::TmuxMultiWindow);
+    }
+    if mode.is_none() {
+        mode = project_config.mode.clone();
+    }
+    let mode = mode.unwrap_or_else(system_default_mode);
+
+    // A session left over from an earlier run would make create_session fail, so
+    // settle what to do with it before any worktree is created
+    let mut recreate_session = false;
+    if mode != Mode::Iterm2 {
+        let tmux_manager = TmuxManager::new(&project_name, &branch_prefix);
+        if tmux_manager.session_exists()? {
+            let session = tmux_manager.session_name().to_string();
+            if !attach || !io::stdin().is_terminal() {
+                return Err(MultiAiError::Tmux(format!(
+                    "tmux session '{}' is already running. Attach with 'mai attach {}', or kill it and run 'mai continue {}' for a fresh layout",
+                    session, branch_prefix, branch_prefix
+                )));
+            }
+            println!("\ntmux session '{}' is already running.", session);
+            match ask_existing_session()? {
+                ExistingSession::Attach => {
+                    if !confirm_attach_if_in_use(&tmux_manager)? {
+                        println!("Not attaching.");
+                        return Ok(());
+                    }
+                    println!("\nAttaching to session...");
+                    return tmux_manager.attach_session();
+                }
+                ExistingSession::Recreate => recreate_session = true,
+                ExistingSession::Keep => {
+                    println!("Kept tmux session '{}'; no worktrees were created.", session);
+                    return Ok(());
+                }
+            }
+        }
+    }
 
     // Fetch once before parallel worktree creation to avoid concurrent fetch race conditions
     // (gwt internally fetches, and parallel fetches race to update the same remote refs)
-    print!("Fetching latest changes from origin... ");
-    io::stdout().flush().ok();
+    if !reporter.events() {
+        print!("Fetching latest changes from origin... ");
+        io::stdout().flush().ok();
+    }
     match std::process::Command::new("git")
         .args(["fetch", "origin"])
         .current_dir(&project_path)
         .output()
     {
-        Ok(output) if output.status.success() => println!("ok"),
+        Ok(output) if output.status.success() => reporter.say("ok"),
         Ok(output) => {
             let stderr = String::from_utf8_lossy(&output.stderr);
             eprintln!("warning: git fetch failed: {}", stderr.trim());
@@ -698,52 +1619,88 @@ fn create_command(
         }
     }
 
+    // Ctrl+C during creation cancels pending worktrees and rolls back the created ones.
+    // Outside the creation phase (or on a second Ctrl+C) it exits immediately.
+    let creating = util::catch_interrupts(Some(
+        "\nInterrupted, waiting for running gwt commands before rolling back...",
+    ));
+
     // Create worktrees in parallel
-    println!("Creating worktrees in parallel...");
+    reporter.say("Creating worktrees in parallel...");
     let worktree_paths = Arc::new(Mutex::new(Vec::new()));
     let errors = Arc::new(Mutex::new(Vec::new()));
+    let started_branches = Arc::new(Mutex::new(Vec::new()));
 
     let mut handles = vec![];
 
     let config_wt_path = project_config.worktrees_path.clone();
-    for ai_app in &ai_apps {
+    for (index, ai_app) in ai_apps.iter().enumerate() {
         let branch_name = format!("{}-{}", branch_prefix, ai_app.slug());
         let ai_app_clone = ai_app.clone();
         let project_path_clone = project_path.clone();
         let config_wt_path_clone = config_wt_path.clone();
         let worktree_paths_clone = Arc::clone(&worktree_paths);
         let errors_clone = Arc::clone(&errors);
+        let started_clone = Arc::clone(&started_branches);
 
         let handle = thread::spawn(move || {
-            println!(
+            if util::interrupted() {
+                return;
+            }
+            started_clone.lock().unwrap().push(branch_name.clone());
+
+            reporter.say(format!(
                 "  Creating worktree for {} with branch '{}'...",
-                ai_app_clone.command(),
+                ai_app_clone.as_str(),
                 branch_name
-            );
+            ));
 
             let worktree_manager = if let Some(wt_path) = config_wt_path_clone {
                 WorktreeManager::with_worktrees_path(project_path_clone, wt_path)
             } else {
                 WorktreeManager::new(project_path_clone)
             };
-            match worktree_manager.add_worktree(&branch_name) {
+            let result = if reporter.events() {
+                worktree_manager.add_worktree_quiet(&branch_name)
+            } else {
+                worktree_manager.add_worktree(&branch_name)
+            };
+            match result {
                 Ok(worktree_path) => {
-                    println!(
-                        "  ✓ Created worktree for {}: {}",
-                        ai_app_clone.command(),
+                    reporter.say(format!(
+                        "  {}Created worktree for {}: {}",
+                        util::icon("✓ "),
+                        ai_app_clone.as_str(),
                         worktree_path.display()
-                    );
+                    ));
+                    reporter.emit(Event::WorktreeCreated {
+                        app: ai_app_clone.name.clone(),
+                        branch: branch_name.clone(),
+                        path: worktree_path.to_string_lossy().to_string(),
+                    });
                     let mut paths = worktree_paths_clone.lock().unwrap();
-                    paths.push((ai_app_clone, worktree_path.to_string_lossy().to_string()));
+                    paths.push((
+                        index,
+                        ai_app_clone,
+                        worktree_path.to_string_lossy().to_string(),
+                    ));
                 }
                 Err(e) => {
-                    eprintln!(
-                        "  ✗ Failed to create worktree for {}: {}",
-                        ai_app_clone.command(),
-                        e
-                    );
+                    if reporter.events() {
+                        reporter.emit(Event::Error {
+                            app: Some(ai_app_clone.name.clone()),
+                            message: e.to_string(),
+                        });
+                    } else {
+                        eprintln!(
+                            "  {}Failed to create worktree for {}: {}",
+                            util::icon("✗ "),
+                            ai_app_clone.as_str(),
+                            e
+                        );
+                    }
                     let mut errs = errors_clone.lock().unwrap();
-                    errs.push(format!("{}: {}", ai_app_clone.command(), e));
+                    errs.push(format!("{}: {}", ai_app_clone.as_str(), e));
                 }
             }
         });
@@ -756,35 +1713,51 @@ fn create_command(
         handle.join().expect("Thread panicked");
     }
 
-    // Check if there were any errors
+    if util::interrupted() {
+        let started = started_branches.lock().unwrap().clone();
+        rollback_worktrees(&worktree_manager, &started, reporter);
+        reporter.say(format!("Worktree creation for '{}' cancelled.", branch_prefix));
+        reporter.emit(Event::Cancelled {
+            branch_prefix: branch_prefix.clone(),
+        });
+        std::process::exit(130);
+    }
+    drop(creating);
+
+    // Get the final worktree paths in app order; threads finish in any order, so
+    // sort by the index each one was spawned with
+    let mut indexed_paths = worktree_paths.lock().unwrap().clone();
+    indexed_paths.sort_by_key(|(index, _, _)| *index);
+    let worktree_paths: Vec<(config::AiApp, String)> = indexed_paths
+        .into_iter()
+        .map(|(_, app, path)| (app, path))
+        .collect();
+
+    // Check if there were any errors; --keep-going lays out the apps that did get one
     let errors = errors.lock().unwrap();
-    if !errors.is_empty() {
+    let ai_apps = if errors.is_empty() {
+        reporter.say(format!("{}All worktrees created successfully!", util::icon("✓ ")));
+        ai_apps
+    } else if options.keep_going && !worktree_paths.is_empty() {
+        let (created, skipped): (Vec<_>, Vec<_>) = ai_apps
+            .into_iter()
+            .partition(|app| worktree_paths.iter().any(|(a, _)| a.name == app.name));
+        eprintln!(
+            "warning: continuing without {} (worktree creation failed)",
+            skipped
+                .iter()
+                .map(|app| app.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        created
+    } else {
         return Err(MultiAiError::Worktree(format!(
             "Failed to create some worktrees:\n{}",
             errors.join("\n")
         )));
-    }
-
-    // Get the final worktree paths, sorted by app order
-    let mut worktree_paths = worktree_paths.lock().unwrap().clone();
-    worktree_paths.sort_by_key(|a| {
-        ai_apps
-            .iter()
-            .position(|app| app.name == a.0.name)
-            .unwrap_or(0)
-    });
-
-    println!("✓ All worktrees created successfully!");
-
-    // Determine mode: CLI override > legacy --tmux > config file > system default
-    let mut mode = mode_override.map(Into::into);
-    if mode.is_none() && cli_tmux {
-        mode = Some(Mode::TmuxMultiWindow);
-    }
-    if mode.is_none() {
-        mode = project_config.mode.clone();
-    }
-    let mode = mode.unwrap_or_else(system_default_mode);
+    };
+    let worktree_paths = without_missing_subdirs(worktree_paths);
 
     match mode {
         Mode::Iterm2 => {
@@ -796,27 +1769,43 @@ fn create_command(
             }
             #[cfg(target_os = "macos")]
             {
+                if options.group {
+                    eprintln!("{}--group only applies to tmux sessions; ignoring it for iTerm2", util::icon("⚠ "));
+                }
                 let iterm2_manager = ITerm2Manager::new(
                     &project_name,
                     &branch_prefix,
                     project_config.terminals_per_column,
-                );
-                println!("\nCreating iTerm2 tabs for AI applications...");
-                println!(
+                )
+                .with_initial_prompt(options.initial_prompt)
+                .with_label(options.label)
+                .with_max_columns(project_config.iterm2_max_columns())
+                .with_max_columns_per_window(project_config.max_columns_per_window)
+                .with_pane_delay(project_config.iterm2_pane_delay());
+                reporter.say("\nCreating iTerm2 tabs for AI applications...");
+                reporter.say(format!(
                     "  Apps to create tabs for: {:?}",
                     worktree_paths
                         .iter()
                         .map(|(app, _)| app.as_str())
                         .collect::<Vec<_>>()
-                );
-                println!(
+                ));
+                reporter.say(format!(
                     "  Terminals per column: {}",
                     project_config.terminals_per_column
-                );
+                ));
                 match iterm2_manager.create_tabs_per_app(&ai_apps, &worktree_paths) {
-                    Ok(_) => println!("✓ iTerm2 tabs created successfully!"),
+                    Ok(_) => {
+                        reporter.say(format!("{}iTerm2 tabs created successfully!", util::icon("✓ ")));
+                        reporter.emit(Event::SessionCreated {
+                            session: format!("{}-{}", project_name, branch_prefix),
+                            mode: Mode::Iterm2,
+                        });
+                    }
                     Err(e) => {
-                        eprintln!("✗ Failed to create iTerm2 tabs: {}", e);
+                        if !reporter.events() {
+                            eprintln!("{}Failed to create iTerm2 tabs: {}", util::icon("✗ "), e);
+                        }
                         return Err(e);
                     }
                 }
@@ -827,29 +1816,130 @@ fn create_command(
                 Mode::TmuxSingleWindow => TmuxLayout::SingleWindow,
                 _ => TmuxLayout::MultiWindow,
             };
-            let tmux_manager = TmuxManager::new(&project_name, &branch_prefix);
-            println!(
+            let mut tmux_manager = TmuxManager::new(&project_name, &branch_prefix)
+                .with_log_dir(project_config.log_dir.as_deref())
+                .with_label(options.label)
+                .with_pane_split_ratio(project_config.pane_split_ratio)
+                .with_pane_init_delay(project_config.pane_init_delay())
+                .with_focus_app(project_config.focus_app.as_deref());
+            if recreate_session && tmux_manager.session_exists()? {
+                tmux_manager.kill_session()?;
+                reporter.say(format!(
+                    "{}Killed tmux session '{}'",
+                    util::icon("✓ "),
+                    tmux_manager.session_name()
+                ));
+            }
+            reporter.say(format!(
                 "\nCreating tmux session '{}-{}' (layout: {:?})...",
                 project_name, branch_prefix, layout
-            );
-            tmux_manager.create_session(&ai_apps, &worktree_paths, layout)?;
-            println!("✓ Tmux session created successfully!");
-            println!("\nAttaching to session...");
-            tmux_manager.attach_session()?;
+            ));
+            let ai_panes = tmux_manager.create_session(&ai_apps, &worktree_paths, layout)?;
+            reporter.say(format!("{}Tmux session created successfully!", util::icon("✓ ")));
+            reporter.emit(Event::SessionCreated {
+                session: format!("{}-{}", project_name, branch_prefix),
+                mode: mode.clone(),
+            });
+            if options.group {
+                match tmux_manager.create_grouped_session() {
+                    Ok(view) => reporter.say(format!(
+                        "{}Grouped session '{}' created. From another terminal: tmux attach -t {}",
+                        util::icon("✓ "),
+                        view,
+                        view
+                    )),
+                    Err(e) => eprintln!("{}{}", util::icon("⚠ "), e),
+                }
+            }
+            if let Some(prompt) = options.initial_prompt {
+                send_initial_prompt(&ai_panes, prompt, reporter);
+            }
+            if attach {
+                reporter.say("\nAttaching to session...");
+                tmux_manager.attach_session()?;
+            }
         }
     }
 
     Ok(())
 }
 
-fn remove_command(
-    branch_prefix: String,
-    cli_tmux: bool,
-    mode_override: Option<ModeOverride>,
-    force: bool,
+/// Pastes `prompt` into each AI pane once the apps have had time to start.
+/// Failures are reported per pane so one slow app doesn't block the others.
+fn send_initial_prompt(ai_panes: &[String], prompt: &str, reporter: Reporter) {
+    reporter.say("Waiting for AI apps to start before sending the initial prompt...");
+    thread::sleep(INITIAL_PROMPT_DELAY);
+    for pane_id in ai_panes {
+        match send::paste_text_to_pane(pane_id, prompt) {
+            Ok(()) => reporter.say(format!("  {}Sent initial prompt to {}", util::icon("✓ "), pane_id)),
+            Err(e) => eprintln!("  {}Could not send initial prompt to {}: {}", util::icon("⚠ "), pane_id, e),
+        }
+    }
+}
+
+/// Clears the `subdir` of apps whose subdirectory is missing from their worktree, with
+/// a warning, so they start in the worktree root instead of failing their launch `cd`.
+fn without_missing_subdirs(
+    worktree_paths: Vec<(config::AiApp, String)>,
+) -> Vec<(config::AiApp, String)> {
+    worktree_paths
+        .into_iter()
+        .map(|(mut app, path)| {
+            if let Some(subdir) = &app.subdir
+                && !Path::new(&app.launch_dir(&path)).is_dir()
+            {
+                eprintln!(
+                    "  {}{}: subdir '{}' does not exist in {}; starting it in the worktree root",
+                    util::icon("⚠ "),
+                    app.as_str(),
+                    subdir,
+                    path
+                );
+                app.subdir = None;
+            }
+            (app, path)
+        })
+        .collect()
+}
+
+/// Removes worktrees whose creation was started before a cancellation. Branches
+/// interrupted mid-creation may not exist, so failures are reported but not fatal.
+fn rollback_worktrees(
+    worktree_manager: &WorktreeManager,
+    branch_names: &[String],
+    reporter: Reporter,
+) {
+    if branch_names.is_empty() {
+        return;
+    }
+
+    reporter.say(format!("Rolling back {} worktree(s)...", branch_names.len()));
+    for branch_name in branch_names {
+        match worktree_manager.remove_worktree_quiet(branch_name) {
+            Ok(_) => {
+                reporter.say(format!("  {}Removed worktree: {}", util::icon("✓ "), branch_name));
+                reporter.emit(Event::WorktreeRemoved {
+                    branch: branch_name.clone(),
+                });
+            }
+            Err(e) => eprintln!("  {}Could not remove worktree '{}': {}", util::icon("⚠ "), branch_name, e),
+        }
+    }
+
+    if let Err(e) = worktree_manager.prune_worktrees() {
+        eprintln!("  {}{}", util::icon("⚠ "), e);
+    }
+}
+
+fn remove_command(
+    branch_prefix: String,
+    cli_tmux: bool,
+    mode_override: Option<ModeOverride>,
+    force: bool,
+    no_kill_session: bool,
+    reporter: Reporter,
 ) -> Result<()> {
-    let current_dir = std::env::current_dir()
-        .map_err(|e| MultiAiError::Config(format!("Failed to get current directory: {}", e)))?;
+    let current_dir = config::current_dir()?;
 
     // Find config using the new search order
     let (config_path, project_config, project_path) = ProjectConfig::find_config(&current_dir)
@@ -858,7 +1948,7 @@ fn remove_command(
             "Config not found in ~/.config/multi-ai-cli/. Run 'mai init' from your project directory to create one.".to_string()
         ))?;
 
-    println!("Using config: {}", config_path.display());
+    reporter.say(format!("Using config: {}", config_path.display()));
     let sp = spinner("Validating environment...");
 
     // Check for git-worktree-config.jsonc (at the project path)
@@ -874,13 +1964,17 @@ fn remove_command(
         .to_string();
     let worktree_manager = make_worktree_manager(&project_config, project_path.clone());
 
-    if !worktree_manager.has_gwt_cli() {
+    if !WorktreeManager::has_gwt_cli() {
         return Err(MultiAiError::Worktree(
             "gwt CLI is not installed. Please install from https://github.com/mikko-kohtala/git-worktree-cli".to_string()
         ));
     }
 
-    sp.finish_with_message("Environment validated");
+    if reporter.events() {
+        sp.finish_and_clear();
+    } else {
+        sp.finish_with_message("Environment validated");
+    }
 
     // Determine which worktree branches to remove
     let branch_names: Vec<String> = if !project_config.ai_apps.is_empty() {
@@ -894,15 +1988,15 @@ fn remove_command(
     };
 
     if branch_names.is_empty() {
-        println!("No worktrees found for prefix '{}'.", branch_prefix);
+        reporter.say(format!("No worktrees found for prefix '{}'.", branch_prefix));
         return Ok(());
     }
 
     // Ask for confirmation
-    println!("⚠️  You are about to remove:");
-    println!("  - Worktrees for branches:");
+    reporter.say(format!("{}You are about to remove:", util::icon("⚠️  ")));
+    reporter.say("  - Worktrees for branches:");
     for branch_name in &branch_names {
-        println!("    • {}", branch_name);
+        reporter.say(format!("    • {}", branch_name));
     }
     // Determine mode for cleanup (optional)
     let mut mode = mode_override.map(Into::into);
@@ -913,60 +2007,210 @@ fn remove_command(
         mode = project_config.mode.clone();
     }
 
+    let kill_session = project_config.remove_kills_session() && !no_kill_session;
+
     match mode {
-        Some(Mode::TmuxMultiWindow) | Some(Mode::TmuxSingleWindow) => {
-            println!("  - Tmux session: {}-{}", project_name, branch_prefix);
-        }
         Some(Mode::Iterm2) => {
-            println!("  - Note: iTerm2 tabs must be closed manually");
+            reporter.say("  - Note: iTerm2 tabs must be closed manually");
+        }
+        _ if !kill_session => {
+            reporter.say(format!("  - Tmux session '{}-{}' will be kept", project_name, branch_prefix));
+        }
+        Some(Mode::TmuxMultiWindow) | Some(Mode::TmuxSingleWindow) => {
+            reporter.say(format!("  - Tmux session: {}-{}", project_name, branch_prefix));
         }
         None => {
-            println!(
+            reporter.say(format!(
                 "  - Will attempt to remove tmux session '{}-{}' if present; iTerm2 tabs must be closed manually",
                 project_name, branch_prefix
-            );
+            ));
         }
     }
-    println!();
+    reporter.say("");
 
     if !force {
-        if !ask_confirmation("Are you sure you want to remove these worktrees and session?")? {
+        let question = if kill_session {
+            "Are you sure you want to remove these worktrees and session?"
+        } else {
+            "Are you sure you want to remove these worktrees?"
+        };
+        if !ask_confirmation(question)? {
             println!("Removal cancelled.");
             return Ok(());
         }
     } else {
-        println!("Forcing removal without confirmation (--force).");
+        reporter.say("Forcing removal without confirmation (--force).");
     }
 
     // Best-effort: try to kill tmux session regardless of configured mode.
     // If tmux isn't installed or the session doesn't exist, this will no-op or warn.
-    let tmux_manager = TmuxManager::new(&project_name, &branch_prefix);
-    println!(
-        "Removing tmux session '{}-{}' (if present)...",
-        project_name, branch_prefix
-    );
-    match tmux_manager.kill_session() {
-        Ok(_) => println!("  ✓ Tmux session removed or not present"),
-        Err(e) => eprintln!("  ⚠ Tmux cleanup skipped: {}", e),
+    if kill_session {
+        let tmux_manager = TmuxManager::new(&project_name, &branch_prefix);
+        reporter.say(format!(
+            "Removing tmux session '{}-{}' (if present)...",
+            project_name, branch_prefix
+        ));
+        match tmux_manager.kill_session() {
+            Ok(_) => {
+                reporter.say(format!("  {}Tmux session removed or not present", util::icon("✓ ")));
+                reporter.emit(Event::SessionRemoved {
+                    session: format!("{}-{}", project_name, branch_prefix),
+                });
+            }
+            Err(e) => eprintln!("  {}Tmux cleanup skipped: {}", util::icon("⚠ "), e),
+        }
     }
 
     // For iTerm2, we can't programmatically close tabs, just notify the user
-    println!(
+    reporter.say(format!(
         "Note: If you previously used iTerm2 mode, please close the iTerm2 tabs for '{}-{}' manually.",
         project_name, branch_prefix
-    );
+    ));
 
     // Remove worktrees
     for branch_name in &branch_names {
-        println!("Removing worktree for branch '{}'...", branch_name);
+        reporter.say(format!("Removing worktree for branch '{}'...", branch_name));
+
+        let result = if reporter.events() {
+            worktree_manager.remove_worktree_quiet(branch_name)
+        } else {
+            worktree_manager.remove_worktree(branch_name)
+        };
+        match result {
+            Ok(_) => {
+                reporter.say(format!("  {}Removed worktree: {}", util::icon("✓ "), branch_name));
+                reporter.emit(Event::WorktreeRemoved {
+                    branch: branch_name.clone(),
+                });
+            }
+            Err(e) => {
+                if reporter.events() {
+                    reporter.emit(Event::Error {
+                        app: None,
+                        message: format!("{}: {}", branch_name, e),
+                    });
+                } else {
+                    eprintln!("  {}Failed to remove worktree: {}", util::icon("✗ "), e);
+                }
+            }
+        }
+    }
+
+    reporter.say(format!("\n{}Cleanup completed!", util::icon("✓ ")));
+    Ok(())
+}
+
+fn move_command(branch_prefix: String, new_path: PathBuf) -> Result<()> {
+    let current_dir = config::current_dir()?;
+
+    let (config_path, project_config, project_path) = ProjectConfig::find_config(&current_dir)
+        .map_err(|e| MultiAiError::Config(format!("Failed to find config: {}", e)))?
+        .ok_or_else(|| MultiAiError::Config(
+            "Config not found in ~/.config/multi-ai-cli/. Run 'mai init' from your project directory to create one.".to_string()
+        ))?;
+
+    println!("Using config: {}", config_path.display());
+
+    let project_name = project_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| MultiAiError::Config("Invalid project path".to_string()))?
+        .to_string();
+    let worktree_manager = make_worktree_manager(&project_config, project_path.clone());
+    let old_base = worktree_manager.worktrees_path();
 
-        match worktree_manager.remove_worktree(branch_name) {
-            Ok(_) => println!("  ✓ Removed worktree: {}", branch_name),
-            Err(e) => eprintln!("  ✗ Failed to remove worktree: {}", e),
+    let branch_names = discover_worktree_branches(&worktree_manager, &branch_prefix);
+    if branch_names.is_empty() {
+        return Err(MultiAiError::Worktree(format!(
+            "No worktrees found for prefix '{}' in {}",
+            branch_prefix,
+            old_base.display()
+        )));
+    }
+
+    // Agents and shells still running in the worktrees would be left in deleted directories
+    let session = format!("{}-{}", project_name, branch_prefix);
+    if TmuxManager::new(&project_name, &branch_prefix).session_exists().unwrap_or(false) {
+        return Err(MultiAiError::Tmux(format!(
+            "tmux session '{}' is still running. Stop it first (tmux kill-session -t {}), then move the worktrees.",
+            session, session
+        )));
+    }
+
+    let new_base = PathBuf::from(shellexpand::tilde(&new_path.to_string_lossy()).as_ref());
+    let new_base = current_dir.join(new_base);
+    if new_base.exists() && !new_base.is_dir() {
+        return Err(MultiAiError::Config(format!(
+            "{} exists and is not a directory",
+            new_base.display()
+        )));
+    }
+    if new_base.canonicalize().ok() == old_base.canonicalize().ok() {
+        return Err(MultiAiError::Config(format!(
+            "The worktrees for '{}' are already in {}",
+            branch_prefix,
+            new_base.display()
+        )));
+    }
+
+    let moves: Vec<(PathBuf, PathBuf)> = branch_names
+        .iter()
+        .map(|name| (old_base.join(name), new_base.join(name)))
+        .collect();
+    if let Some((from, _)) = moves.iter().find(|(from, _)| new_base.starts_with(from)) {
+        return Err(MultiAiError::Config(format!(
+            "{} is inside {}, which is one of the worktrees being moved",
+            new_base.display(),
+            from.display()
+        )));
+    }
+    if let Some((_, to)) = moves.iter().find(|(_, to)| to.exists()) {
+        return Err(MultiAiError::Config(format!("{} already exists", to.display())));
+    }
+
+    std::fs::create_dir_all(&new_base).map_err(|e| {
+        MultiAiError::Config(format!("Failed to create {}: {}", new_base.display(), e))
+    })?;
+    // Print and record `~/x` or `../x` as the resolved directory
+    let new_base = new_base.canonicalize().unwrap_or(new_base);
+    let moves: Vec<(PathBuf, PathBuf)> = branch_names
+        .iter()
+        .zip(moves)
+        .map(|(name, (from, _))| (from, new_base.join(name)))
+        .collect();
+
+    println!("Moving {} worktree(s) to {}:", moves.len(), new_base.display());
+    for (moved, (from, to)) in moves.iter().enumerate() {
+        // Slashed branches (feature/login-claude) live in nested directories
+        let result = match to.parent() {
+            Some(parent) => std::fs::create_dir_all(parent)
+                .map_err(MultiAiError::from)
+                .and_then(|()| worktree_manager.move_worktree(from, to)),
+            None => worktree_manager.move_worktree(from, to),
+        };
+        if let Err(e) = result {
+            eprintln!("  {}Failed to move {}: {}", util::icon("✗ "), from.display(), e);
+            return Err(MultiAiError::Worktree(format!(
+                "Stopped after moving {} of {} worktrees",
+                moved,
+                moves.len()
+            )));
+        }
+        println!("  {}{} -> {}", util::icon("✓ "), from.display(), to.display());
+        // Drop the nested directory (e.g. `feature/`) if the move emptied it
+        if let Some(parent) = from.parent()
+            && parent != old_base
+        {
+            let _ = std::fs::remove_dir(parent);
         }
     }
 
-    println!("\n✓ Cleanup completed!");
+    println!(
+        "\n`mai continue` and `mai remove` look for worktrees in {}. To use the new location for this project, set \"worktrees_path\": \"{}\" in {}.",
+        old_base.display(),
+        new_base.display(),
+        config_path.display()
+    );
     Ok(())
 }
 
@@ -974,9 +2218,11 @@ fn continue_command(
     branch_prefix: String,
     cli_tmux: bool,
     mode_override: Option<ModeOverride>,
+    app_order: &[String],
+    select: bool,
+    env_file: Option<&Path>,
 ) -> Result<()> {
-    let current_dir = std::env::current_dir()
-        .map_err(|e| MultiAiError::Config(format!("Failed to get current directory: {}", e)))?;
+    let current_dir = config::current_dir()?;
 
     // Find config using the new search order
     let (config_path, project_config, project_path) = ProjectConfig::find_config(&current_dir)
@@ -1011,14 +2257,15 @@ fn continue_command(
             .map(|app| app.slug())
             .collect();
 
-        if !worktree_manager.worktrees_exist(&branch_prefix, &ai_app_slugs) {
+        // With --select, offer whichever worktrees exist instead of requiring all of them
+        if !select && !worktree_manager.worktrees_exist(&branch_prefix, &ai_app_slugs) {
             return Err(MultiAiError::Worktree(format!(
                 "Worktrees for '{}' do not exist. Run 'mai add {}' first.",
                 branch_prefix, branch_prefix
             )));
         }
 
-        project_config
+        let paths: Vec<(config::AiApp, String)> = project_config
             .ai_apps
             .iter()
             .map(|ai_app| {
@@ -1026,7 +2273,16 @@ fn continue_command(
                 let worktree_path = worktree_manager.worktrees_path().join(&branch_name);
                 (ai_app.clone(), worktree_path.to_string_lossy().to_string())
             })
-            .collect()
+            .filter(|(_, path)| !select || Path::new(path).is_dir())
+            .collect();
+
+        if paths.is_empty() {
+            return Err(MultiAiError::Worktree(format!(
+                "No worktrees found for prefix '{}'. Run 'mai add {}' first.",
+                branch_prefix, branch_prefix
+            )));
+        }
+        paths
     } else {
         // No ai_apps in config — discover from directory and match against apps.jsonc
         let all_apps = init::load_apps().unwrap_or_default();
@@ -1053,6 +2309,11 @@ fn continue_command(
                         ultrathink: None,
                         default: false,
                         meta_review: false,
+                        auto_submit: false,
+                        kind: config::AppKind::Ai,
+                        commands: Default::default(),
+                        subdir: None,
+                        columns: None,
                         description: None,
                     });
                 let worktree_path = worktree_manager.worktrees_path().join(branch_name);
@@ -1061,101 +2322,385 @@ fn continue_command(
             .collect()
     };
 
-    println!("✓ Found existing worktrees for '{}'", branch_prefix);
+    let worktree_paths = if select {
+        let apps: Vec<config::AiApp> = worktree_paths.iter().map(|(app, _)| app.clone()).collect();
+        let selected = vec![true; apps.len()];
+        // The env name field is shown for context only; the prefix can't change here
+        let Some(result) = picker::run_app_picker_with(Some(&branch_prefix), apps, selected)? else {
+            println!("Cancelled.");
+            return Ok(());
+        };
+        if result.selected_apps.is_empty() {
+            println!("No tools selected.");
+            return Ok(());
+        }
+        worktree_paths
+            .into_iter()
+            .filter(|(app, _)| result.selected_apps.iter().any(|s| s.slug() == app.slug()))
+            .collect()
+    } else {
+        worktree_paths
+    };
+    let worktree_paths = apply_app_order(worktree_paths, app_order, |(app, _)| app)?;
+    let env = project_config.env_vars(&project_path, env_file)?;
+    let worktree_paths: Vec<(config::AiApp, String)> = worktree_paths
+        .into_iter()
+        .map(|(app, path)| (app.with_env(&env), path))
+        .collect();
+
+    println!("{}Found existing worktrees for '{}'", util::icon("✓ "), branch_prefix);
+    let worktree_paths = without_missing_subdirs(worktree_paths);
+
+    // Determine mode: CLI override > legacy --tmux > config file > system default
+    let mut mode = mode_override.map(Into::into);
+    if mode.is_none() && cli_tmux {
+        mode = Some(Mode::TmuxMultiWindow);
+    }
+    if mode.is_none() {
+        mode = project_config.mode.clone();
+    }
+    let mode = mode.unwrap_or_else(system_default_mode);
+
+    // Avoid a second, iTerm2-backed environment next to a running tmux one. iTerm2 tabs
+    // aren't named after the prefix, so the reverse case can't be detected.
+    if matches!(mode, Mode::Iterm2) {
+        let tmux_manager = TmuxManager::new(&project_name, &branch_prefix);
+        if tmux_manager.session_exists().unwrap_or(false) {
+            println!(
+                "{}tmux session '{}-{}' is already running for these worktrees, but the mode is iTerm2.",
+                util::icon("⚠ "),
+                project_name, branch_prefix
+            );
+            if ask_confirmation("Attach to the tmux session instead?")?
+                && confirm_attach_if_in_use(&tmux_manager)?
+            {
+                return tmux_manager.attach_session();
+            }
+        }
+    }
+
+    match mode {
+        Mode::Iterm2 => {
+            #[cfg(not(target_os = "macos"))]
+            {
+                return Err(MultiAiError::Config(
+                    "iTerm2 mode is only supported on macOS".to_string(),
+                ));
+            }
+            #[cfg(target_os = "macos")]
+            {
+                let iterm2_manager = ITerm2Manager::new(
+                    &project_name,
+                    &branch_prefix,
+                    project_config.terminals_per_column,
+                )
+                .with_max_columns(project_config.iterm2_max_columns())
+                .with_max_columns_per_window(project_config.max_columns_per_window)
+                .with_pane_delay(project_config.iterm2_pane_delay());
+                println!("\nCreating new iTerm2 tab for existing worktrees...");
+                println!(
+                    "  Apps to create tabs for: {:?}",
+                    worktree_paths
+                        .iter()
+                        .map(|(app, _)| app.as_str())
+                        .collect::<Vec<_>>()
+                );
+                println!(
+                    "  Terminals per column: {}",
+                    project_config.terminals_per_column
+                );
+                let ai_apps: Vec<config::AiApp> = worktree_paths.iter().map(|(app, _)| app.clone()).collect();
+                match iterm2_manager.create_tabs_per_app(&ai_apps, &worktree_paths) {
+                    Ok(_) => println!("{}iTerm2 tab created successfully!", util::icon("✓ ")),
+                    Err(e) => {
+                        eprintln!("{}Failed to create iTerm2 tab: {}", util::icon("✗ "), e);
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        Mode::TmuxMultiWindow | Mode::TmuxSingleWindow => {
+            let layout = match mode {
+                Mode::TmuxSingleWindow => TmuxLayout::SingleWindow,
+                _ => TmuxLayout::MultiWindow,
+            };
+            let mut tmux_manager = TmuxManager::new(&project_name, &branch_prefix)
+                .with_log_dir(project_config.log_dir.as_deref())
+                .with_pane_split_ratio(project_config.pane_split_ratio)
+                .with_pane_init_delay(project_config.pane_init_delay())
+                .with_focus_app(project_config.focus_app.as_deref());
+            // The session is still running (e.g. detached or open in another terminal);
+            // rejoin it rather than failing to create a duplicate
+            if tmux_manager.session_exists()? {
+                println!(
+                    "tmux session '{}-{}' is already running.",
+                    project_name, branch_prefix
+                );
+                if !confirm_attach_if_in_use(&tmux_manager)? {
+                    println!("Not attaching.");
+                    return Ok(());
+                }
+                println!("\nAttaching to session...");
+                return tmux_manager.attach_session();
+            }
+            println!(
+                "\nCreating new tmux session '{}-{}' (layout: {:?})...",
+                project_name, branch_prefix, layout
+            );
+            let ai_apps: Vec<config::AiApp> = worktree_paths.iter().map(|(app, _)| app.clone()).collect();
+            tmux_manager.create_session(&ai_apps, &worktree_paths, layout)?;
+            println!("{}Tmux session created successfully!", util::icon("✓ "));
+            println!("\nAttaching to session...");
+            tmux_manager.attach_session()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Asks before attaching to a session that is already attached in another terminal.
+/// tmux sizes a shared session to its smallest client, so attaching can shrink it there.
+fn confirm_attach_if_in_use(tmux_manager: &TmuxManager) -> Result<bool> {
+    let clients = tmux_manager.attached_clients()?;
+    if clients.is_empty() {
+        return Ok(true);
+    }
+    println!(
+        "{}The session is already attached elsewhere ({}). Attaching here too may resize it for the other terminal.",
+        util::icon("⚠ "),
+        clients.join(", ")
+    );
+    ask_confirmation("Attach anyway?")
+}
+
+fn send_command(options: send::SendOptions) -> Result<()> {
+    let current_dir = config::current_dir()?;
+
+    // Find config using the new search order
+    let (config_path, project_config, project_path) = ProjectConfig::find_config(&current_dir)
+        .map_err(|e| MultiAiError::Config(format!("Failed to find config: {}", e)))?
+        .ok_or_else(|| MultiAiError::Config(
+            "Config not found in ~/.config/multi-ai-cli/. Run 'mai init' from your project directory to create one.".to_string()
+        ))?;
+
+    println!("Using config: {}", config_path.display());
+
+    let project_name = project_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| MultiAiError::Config("Invalid project path".to_string()))?
+        .to_string();
+
+    if options.list_panes {
+        let session = send::pick_session(&project_name, options.session.as_deref())?;
+        return send::list_panes(&project_config, &session);
+    }
+
+    if let Some(text) = &options.text {
+        let session = send::pick_session(&project_name, options.session.as_deref())?;
+        return send::send_to_matching_apps(&project_config, &session, text, &options);
+    }
+
+    // Offer to start a session first instead of failing with "no sessions"
+    if project_config.mode != Some(Mode::Iterm2) && send::find_project_sessions(&project_name)?.is_empty() {
+        println!("No tmux sessions running for '{}'.", project_name);
+        if ask_confirmation("Create one now with 'mai add'?")? {
+            let branch_prefix = ask_input("Branch prefix")?;
+            create_command(
+                branch_prefix,
+                false,
+                Some(ModeOverride::TmuxSingleWindow),
+                None,
+                &AddOptions::default(),
+                false,
+            )?;
+            println!();
+        }
+    }
+
+    let worktrees_path = make_worktree_manager(&project_config, project_path)
+        .worktrees_path()
+        .to_path_buf();
+    send::run_send_command(project_config, project_name, worktrees_path)
+}
+
+fn tail_command(branch_prefix: String) -> Result<()> {
+    let current_dir = config::current_dir()?;
+
+    let (_config_path, _project_config, project_path) = ProjectConfig::find_config(&current_dir)
+        .map_err(|e| MultiAiError::Config(format!("Failed to find config: {}", e)))?
+        .ok_or_else(|| MultiAiError::Config(
+            "Config not found in ~/.config/multi-ai-cli/. Run 'mai init' from your project directory to create one.".to_string()
+        ))?;
+
+    let project_name = project_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| MultiAiError::Config("Invalid project path".to_string()))?
+        .to_string();
+
+    tail::run_tail(&project_name, &branch_prefix)
+}
+
+fn attach_command(branch_prefix: String) -> Result<()> {
+    let current_dir = config::current_dir()?;
+
+    let (_config_path, _project_config, project_path) = ProjectConfig::find_config(&current_dir)
+        .map_err(|e| MultiAiError::Config(format!("Failed to find config: {}", e)))?
+        .ok_or_else(|| MultiAiError::Config(
+            "Config not found in ~/.config/multi-ai-cli/. Run 'mai init' from your project directory to create one.".to_string()
+        ))?;
+
+    let project_name = project_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| MultiAiError::Config("Invalid project path".to_string()))?
+        .to_string();
+
+    let tmux = TmuxManager::new(&project_name, &branch_prefix);
+    if !tmux.session_exists()? {
+        return Err(MultiAiError::Tmux(format!(
+            "tmux session '{}' is not running. Use 'mai continue {}' to start one for the existing worktrees, or 'mai add {}' to create them",
+            tmux.session_name(),
+            branch_prefix,
+            branch_prefix
+        )));
+    }
+    if !confirm_attach_if_in_use(&tmux)? {
+        println!("Not attaching.");
+        return Ok(());
+    }
+    tmux.attach_session()
+}
+
+fn relayout_command(branch_prefix: String, panes: Option<usize>, force: bool) -> Result<()> {
+    let current_dir = config::current_dir()?;
+
+    let (_config_path, project_config, project_path) = ProjectConfig::find_config(&current_dir)
+        .map_err(|e| MultiAiError::Config(format!("Failed to find config: {}", e)))?
+        .ok_or_else(|| MultiAiError::Config(
+            "Config not found in ~/.config/multi-ai-cli/. Run 'mai init' from your project directory to create one.".to_string()
+        ))?;
+
+    let project_name = project_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| MultiAiError::Config("Invalid project path".to_string()))?
+        .to_string();
 
-    // Determine mode: CLI override > legacy --tmux > config file > system default
-    let mut mode = mode_override.map(Into::into);
-    if mode.is_none() && cli_tmux {
-        mode = Some(Mode::TmuxMultiWindow);
-    }
-    if mode.is_none() {
-        mode = project_config.mode.clone();
+    let tmux = TmuxManager::new(&project_name, &branch_prefix)
+        .with_pane_split_ratio(project_config.pane_split_ratio);
+    if !tmux.session_exists()? {
+        return Err(MultiAiError::Tmux(format!(
+            "tmux session '{}' is not running (relayout only works on tmux sessions)",
+            tmux.session_name()
+        )));
     }
-    let mode = mode.unwrap_or_else(system_default_mode);
 
-    match mode {
-        Mode::Iterm2 => {
-            #[cfg(not(target_os = "macos"))]
-            {
-                return Err(MultiAiError::Config(
-                    "iTerm2 mode is only supported on macOS".to_string(),
-                ));
-            }
-            #[cfg(target_os = "macos")]
-            {
-                let iterm2_manager = ITerm2Manager::new(
-                    &project_name,
-                    &branch_prefix,
-                    project_config.terminals_per_column,
-                );
-                println!("\nCreating new iTerm2 tab for existing worktrees...");
-                println!(
-                    "  Apps to create tabs for: {:?}",
-                    worktree_paths
-                        .iter()
-                        .map(|(app, _)| app.as_str())
-                        .collect::<Vec<_>>()
-                );
-                println!(
-                    "  Terminals per column: {}",
-                    project_config.terminals_per_column
-                );
-                let ai_apps: Vec<config::AiApp> = worktree_paths.iter().map(|(app, _)| app.clone()).collect();
-                match iterm2_manager.create_tabs_per_app(&ai_apps, &worktree_paths) {
-                    Ok(_) => println!("✓ iTerm2 tab created successfully!"),
-                    Err(e) => {
-                        eprintln!("✗ Failed to create iTerm2 tab: {}", e);
-                        return Err(e);
-                    }
-                }
-            }
-        }
-        Mode::TmuxMultiWindow | Mode::TmuxSingleWindow => {
-            let layout = match mode {
-                Mode::TmuxSingleWindow => TmuxLayout::SingleWindow,
-                _ => TmuxLayout::MultiWindow,
-            };
-            let tmux_manager = TmuxManager::new(&project_name, &branch_prefix);
-            println!(
-                "\nCreating new tmux session '{}-{}' (layout: {:?})...",
-                project_name, branch_prefix, layout
-            );
-            let ai_apps: Vec<config::AiApp> = worktree_paths.iter().map(|(app, _)| app.clone()).collect();
-            tmux_manager.create_session(&ai_apps, &worktree_paths, layout)?;
-            println!("✓ Tmux session created successfully!");
-            println!("\nAttaching to session...");
-            tmux_manager.attach_session()?;
-        }
+    let panes_per_app = panes.unwrap_or(project_config.terminals_per_column);
+    let apps = tmux.app_panes()?;
+    let to_remove: usize = apps
+        .iter()
+        .map(|panes| panes.len().saturating_sub(panes_per_app.max(1)))
+        .sum();
+    if to_remove > 0
+        && !force
+        && !ask_confirmation(&format!(
+            "Kill {} shell pane{}? Anything running in them stops.",
+            to_remove,
+            if to_remove == 1 { "" } else { "s" }
+        ))?
+    {
+        println!("Relayout cancelled.");
+        return Ok(());
     }
 
+    let (added, removed) = tmux.relayout(&apps, panes_per_app)?;
+    if added == 0 && removed == 0 {
+        println!(
+            "Every app in '{}' already has {} pane{}.",
+            tmux.session_name(),
+            panes_per_app,
+            if panes_per_app == 1 { "" } else { "s" }
+        );
+    } else {
+        println!(
+            "{}Relayout of '{}': {} shell pane{} added, {} removed ({} per app)",
+            util::icon("✓ "),
+            tmux.session_name(),
+            added,
+            if added == 1 { "" } else { "s" },
+            removed,
+            panes_per_app
+        );
+    }
     Ok(())
 }
 
-fn send_command() -> Result<()> {
-    let current_dir = std::env::current_dir()
-        .map_err(|e| MultiAiError::Config(format!("Failed to get current directory: {}", e)))?;
+fn sync_command(branch_prefix: String, state: Option<SyncState>) -> Result<()> {
+    let current_dir = config::current_dir()?;
 
-    // Find config using the new search order
-    let (config_path, project_config, project_path) = ProjectConfig::find_config(&current_dir)
+    let (_config_path, _project_config, project_path) = ProjectConfig::find_config(&current_dir)
         .map_err(|e| MultiAiError::Config(format!("Failed to find config: {}", e)))?
         .ok_or_else(|| MultiAiError::Config(
             "Config not found in ~/.config/multi-ai-cli/. Run 'mai init' from your project directory to create one.".to_string()
         ))?;
 
-    println!("Using config: {}", config_path.display());
-
     let project_name = project_path
         .file_name()
         .and_then(|n| n.to_str())
         .ok_or_else(|| MultiAiError::Config("Invalid project path".to_string()))?
         .to_string();
 
-    send::run_send_command(project_config, project_name)
+    let tmux = TmuxManager::new(&project_name, &branch_prefix);
+    let session = format!("{}-{}", project_name, branch_prefix);
+    if !tmux.session_exists()? {
+        return Err(MultiAiError::Tmux(format!(
+            "tmux session '{}' is not running",
+            session
+        )));
+    }
+
+    // Single-window sessions keep every app in "apps"; multi-window ones have a window
+    // per app, and tmux only synchronizes panes within a window
+    let windows = tmux.window_names()?;
+    let windows: Vec<String> = if windows.iter().any(|w| w == "apps") {
+        vec!["apps".to_string()]
+    } else {
+        windows
+    };
+    let Some(first) = windows.first() else {
+        return Err(MultiAiError::Tmux(format!("Session '{}' has no windows", session)));
+    };
+
+    let on = match state {
+        Some(state) => state == SyncState::On,
+        None => !tmux.synchronize(first)?,
+    };
+    for window in &windows {
+        tmux.set_synchronize(window, on)?;
+    }
+
+    let state = if on { "on" } else { "off" };
+    if windows.len() == 1 {
+        println!("synchronize-panes {} for {}:{}", state, session, first);
+    } else {
+        println!(
+            "synchronize-panes {} for {} windows of {} (each window syncs only its own panes)",
+            state,
+            windows.len(),
+            session
+        );
+    }
+    Ok(())
 }
 
-fn review_command(branch: Option<String>) -> Result<()> {
-    let current_dir = std::env::current_dir()
-        .map_err(|e| MultiAiError::Config(format!("Failed to get current directory: {}", e)))?;
+fn review_command(
+    cleanup: Option<String>,
+    force: bool,
+    options: review::ReviewOptions,
+) -> Result<()> {
+    let current_dir = config::current_dir()?;
 
     // Find config
     let (config_path, project_config, project_path) = ProjectConfig::find_config(&current_dir)
@@ -1181,7 +2726,7 @@ fn review_command(branch: Option<String>) -> Result<()> {
 
     let worktree_manager = make_worktree_manager(&project_config, project_path.clone());
 
-    if !worktree_manager.has_gwt_cli() {
+    if !WorktreeManager::has_gwt_cli() {
         return Err(MultiAiError::Worktree(
             "gwt CLI is not installed. Please install from https://github.com/mikko-kohtala/git-worktree-cli".to_string()
         ));
@@ -1195,7 +2740,81 @@ fn review_command(branch: Option<String>) -> Result<()> {
 
     sp.finish_with_message("Environment validated");
 
-    review::run_review(project_config, project_name, project_path, worktree_manager, branch)
+    if let Some(target) = cleanup {
+        return review_cleanup_command(&worktree_manager, &project_name, &target, force);
+    }
+
+    review::run_review(
+        project_config,
+        project_name,
+        project_path,
+        worktree_manager,
+        options,
+    )
+}
+
+fn review_cleanup_command(
+    worktree_manager: &WorktreeManager,
+    project_name: &str,
+    target: &str,
+    force: bool,
+) -> Result<()> {
+    let names = collect_worktree_entries(worktree_manager.worktrees_path());
+    let groups = review::group_review_worktrees(&names, target);
+
+    if groups.is_empty() {
+        println!("No review worktrees found for '{}'.", target);
+        return Ok(());
+    }
+
+    println!("{}You are about to remove:", util::icon("⚠️  "));
+    for (prefix, worktrees) in &groups {
+        println!("  - Review '{}':", prefix);
+        for name in worktrees {
+            println!("    • {}", name);
+        }
+    }
+    println!("  along with each review's iTerm2 tab or tmux session.");
+    println!();
+
+    if !force {
+        if !ask_confirmation("Are you sure you want to remove these review worktrees?")? {
+            println!("Removal cancelled.");
+            return Ok(());
+        }
+    } else {
+        println!("Forcing removal without confirmation (--force).");
+    }
+
+    for name in groups.values().flatten() {
+        println!("Removing worktree for branch '{}'...", name);
+        match worktree_manager.remove_worktree(name) {
+            Ok(_) => println!("  {}Removed worktree: {}", util::icon("✓ "), name),
+            Err(e) => eprintln!("  {}Failed to remove worktree: {}", util::icon("✗ "), e),
+        }
+    }
+
+    // Reviews open in an iTerm2 tab named after the prefix; `mai continue` may also
+    // have opened one in tmux
+    for prefix in groups.keys() {
+        let tmux = TmuxManager::new(project_name, prefix);
+        if TmuxManager::is_tmux_installed() && tmux.session_exists().unwrap_or(false) {
+            match tmux.kill_session() {
+                Ok(()) => println!("  {}Killed tmux session: {}", util::icon("✓ "), tmux.session_name()),
+                Err(e) => eprintln!("  {}Failed to kill tmux session: {}", util::icon("✗ "), e),
+            }
+        }
+        if applescript::is_osascript_available() {
+            match iterm2::close_tabs_named(prefix) {
+                Ok(0) => {}
+                Ok(_) => println!("  {}Closed iTerm2 tab: {}", util::icon("✓ "), prefix),
+                Err(e) => eprintln!("  {}Failed to close iTerm2 tab '{}': {}", util::icon("✗ "), prefix, e),
+            }
+        }
+    }
+
+    println!("\n{}Review cleanup completed!", util::icon("✓ "));
+    Ok(())
 }
 
 fn format_relative_time(time: SystemTime) -> String {
@@ -1214,9 +2833,8 @@ fn format_relative_time(time: SystemTime) -> String {
     }
 }
 
-fn list_command() -> Result<()> {
-    let current_dir = std::env::current_dir()
-        .map_err(|e| MultiAiError::Config(format!("Failed to get current directory: {}", e)))?;
+fn list_command(since: Option<Duration>, json: bool) -> Result<()> {
+    let current_dir = config::current_dir()?;
 
     let (_config_path, project_config, project_path) = ProjectConfig::find_config(&current_dir)
         .map_err(|e| MultiAiError::Config(format!("Failed to find config: {}", e)))?
@@ -1224,15 +2842,15 @@ fn list_command() -> Result<()> {
             "Config not found in ~/.config/multi-ai-cli/. Run 'mai init' from your project directory to create one.".to_string()
         ))?;
 
+    let project_name = project_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| MultiAiError::Config("Invalid project path".to_string()))?
+        .to_string();
     let worktree_manager = make_worktree_manager(&project_config, project_path);
     let wt_dir = worktree_manager.worktrees_path();
     let groups = discover_all_prefixes(&worktree_manager, &project_config);
 
-    if groups.is_empty() {
-        println!("No worktrees found.");
-        return Ok(());
-    }
-
     // Collect groups with their most recent mtime
     let mut timed_groups: Vec<(String, Vec<String>, SystemTime)> = groups
         .into_iter()
@@ -1250,41 +2868,94 @@ fn list_command() -> Result<()> {
         })
         .collect();
 
+    if let Some(since) = since {
+        // A modification time in the future (clock skew) counts as recent
+        timed_groups.retain(|(_, _, mtime)| mtime.elapsed().unwrap_or_default() <= since);
+    }
+
     // Sort newest first
-    timed_groups.sort_by(|a, b| b.2.cmp(&a.2));
+    timed_groups.sort_by_key(|g| std::cmp::Reverse(g.2));
+
+    if json {
+        let environments: Vec<serde_json::Value> = timed_groups
+            .iter()
+            .map(|(prefix, worktrees, mtime)| {
+                let tmux = TmuxManager::new(&project_name, prefix);
+                let worktrees: Vec<serde_json::Value> = worktrees
+                    .iter()
+                    .map(|wt| {
+                        let path = wt_dir.join(wt);
+                        serde_json::json!({
+                            "name": wt,
+                            "app": worktree_app(prefix, wt),
+                            "path": path,
+                            "branch": git::current_branch(&path),
+                        })
+                    })
+                    .collect();
+                serde_json::json!({
+                    "prefix": prefix,
+                    "session": tmux.session_name(),
+                    "session_running": tmux.session_exists().unwrap_or(false),
+                    "modified": mtime.duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_secs()).ok(),
+                    "worktrees": worktrees,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&environments)?);
+        return Ok(());
+    }
+
+    if timed_groups.is_empty() {
+        if since.is_some() {
+            println!("No worktrees modified in that time.");
+        } else {
+            println!("No worktrees found.");
+        }
+        return Ok(());
+    }
 
     // Find max prefix length for alignment
     let max_prefix_len = timed_groups.iter().map(|(p, _, _)| p.len()).max().unwrap_or(0);
 
     for (prefix, worktrees, mtime) in &timed_groups {
         let time_str = format_relative_time(*mtime);
-        let is_standalone = worktrees.len() == 1 && worktrees[0] == *prefix;
-        if is_standalone {
-            println!("{:<width$}  {}", prefix, time_str, width = max_prefix_len);
+        let session = if TmuxManager::new(&project_name, prefix).session_exists().unwrap_or(false) {
+            "  session running"
         } else {
-            let slugs: Vec<&str> = worktrees
-                .iter()
-                .map(|wt| {
-                    let suffix = format!("{}-", prefix);
-                    wt.strip_prefix(&suffix).unwrap_or(wt.as_str())
-                })
-                .collect();
-            println!(
-                "{:<width$}  {}  {}",
-                prefix,
-                time_str,
-                slugs.join(", "),
-                width = max_prefix_len
-            );
+            ""
+        };
+        println!("{:<width$}  {}{}", prefix, time_str, session, width = max_prefix_len);
+
+        let apps: Vec<&str> = worktrees
+            .iter()
+            .map(|wt| worktree_app(prefix, wt).unwrap_or(wt))
+            .collect();
+        let app_width = apps.iter().map(|app| app.len()).max().unwrap_or(0);
+        for (wt, app) in worktrees.iter().zip(apps) {
+            let branch = git::current_branch(&wt_dir.join(wt)).unwrap_or_else(|| "?".to_string());
+            println!("  {:<width$}  {}", app, branch, width = app_width);
         }
     }
 
     Ok(())
 }
 
+/// App slug of worktree `wt` in the `prefix` environment; None for a standalone
+/// worktree named just `prefix`
+fn worktree_app<'a>(prefix: &str, wt: &'a str) -> Option<&'a str> {
+    wt.strip_prefix(prefix)?.strip_prefix('-')
+}
+
 fn config_command() -> Result<()> {
-    let current_dir = std::env::current_dir()
-        .map_err(|e| MultiAiError::Config(format!("Failed to get current directory: {}", e)))?;
+    if std::env::var(config::CONFIG_ENV_VAR).is_ok_and(|v| !v.trim().is_empty()) {
+        return Err(MultiAiError::Config(format!(
+            "Config is provided inline via {}; there is no file to open.",
+            config::CONFIG_ENV_VAR
+        )));
+    }
+
+    let current_dir = config::current_dir()?;
 
     let (config_path, _, _) = ProjectConfig::find_config(&current_dir)
         .map_err(|e| MultiAiError::Config(format!("Failed to find config: {}", e)))?
@@ -1307,11 +2978,230 @@ fn config_command() -> Result<()> {
     Ok(())
 }
 
+fn config_edit_command() -> Result<()> {
+    if std::env::var(config::CONFIG_ENV_VAR).is_ok_and(|v| !v.trim().is_empty()) {
+        return Err(MultiAiError::Config(format!(
+            "Config is provided inline via {}; there is no file to edit.",
+            config::CONFIG_ENV_VAR
+        )));
+    }
+
+    let current_dir = config::current_dir()?;
+
+    let config_path = match ProjectConfig::find_config_file(&current_dir)
+        .map_err(|e| MultiAiError::Config(format!("Failed to find config: {}", e)))?
+    {
+        Some(path) => path,
+        None => {
+            println!("No config found for this project in ~/.config/multi-ai-cli/.");
+            if ask_confirmation("Run 'mai init' to create one?")? {
+                return init::run_init(None);
+            }
+            return Ok(());
+        }
+    };
+
+    let editor = std::env::var("EDITOR")
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+
+    loop {
+        println!("Editing config: {}", config_path.display());
+
+        // Through the shell, so EDITOR can carry flags like `code --wait`
+        let status = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(format!("{} {}", editor, config::shell_quote(&config_path.to_string_lossy())))
+            .status()
+            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to run {}: {}", editor, e)))?;
+        if !status.success() {
+            return Err(MultiAiError::CommandFailed(format!(
+                "{} exited with {}",
+                editor, status
+            )));
+        }
+
+        let content = std::fs::read_to_string(&config_path)?;
+        // gwt's config only holds the mai config in its `multi_ai` section
+        let parsed = if config_path.ends_with(config::GWT_CONFIG_FILE) {
+            ProjectConfig::from_gwt_json(&content).and_then(|config| {
+                config.ok_or_else(|| anyhow::anyhow!("the '{}' section is gone", config::GWT_CONFIG_SECTION))
+            })
+        } else {
+            ProjectConfig::from_json(&content)
+        };
+        match parsed {
+            Ok(_) => {
+                println!("{}Config is valid", util::icon("✓ "));
+                return Ok(());
+            }
+            Err(e) => {
+                println!("{}Config has errors: {}", util::icon("✗ "), e);
+                if !ask_confirmation("Edit it again?")? {
+                    return Err(MultiAiError::Config(format!(
+                        "{} is not valid: {}",
+                        config_path.display(),
+                        e
+                    )));
+                }
+            }
+        }
+    }
+}
+
+/// One line of `mai status`: what was checked and what was found, or what's wrong
+struct StatusCheck {
+    label: String,
+    outcome: std::result::Result<String, String>,
+    // A missing optional item is only a warning and doesn't fail the command
+    required: bool,
+}
+
+impl StatusCheck {
+    fn new(label: &str, outcome: std::result::Result<String, String>, required: bool) -> Self {
+        Self { label: label.to_string(), outcome, required }
+    }
+
+    fn mark(&self) -> String {
+        use ratatui::crossterm::style::Stylize;
+        match (&self.outcome, self.required, util::color_enabled()) {
+            (Ok(_), _, true) => "✓".green().to_string(),
+            (Err(_), true, true) => "✗".red().to_string(),
+            (Err(_), false, true) => "!".yellow().to_string(),
+            (Ok(_), _, false) => "[ok]".to_string(),
+            (Err(_), true, false) => "[missing]".to_string(),
+            (Err(_), false, false) => "[warning]".to_string(),
+        }
+    }
+}
+
+/// The program a launch command runs: its first word after any `VAR=value`
+/// assignments, with `~` and `$VAR` expanded. None for an empty command.
+fn command_program(command: &str) -> Option<String> {
+    let is_assignment = |word: &str| {
+        word.split_once('=').is_some_and(|(name, _)| {
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+    };
+    let word = command.split_whitespace().find(|word| !is_assignment(word))?;
+    let word = word.trim_matches(|c| c == '"' || c == '\'');
+    Some(shellexpand::full(word).map_or_else(|_| word.to_string(), |word| word.into_owned()))
+}
+
+fn status_command() -> Result<()> {
+    let current_dir = config::current_dir()?;
+    let mut checks = vec![StatusCheck::new(
+        "gwt",
+        if WorktreeManager::has_gwt_cli() {
+            Ok("installed".to_string())
+        } else {
+            Err("not installed; see https://github.com/mikko-kohtala/git-worktree-cli".to_string())
+        },
+        true,
+    )];
+
+    let (config_check, found) = match ProjectConfig::find_config(&current_dir) {
+        Ok(Some((config_path, project_config, project_path))) => (
+            Ok(config_path.display().to_string()),
+            Some((project_config, project_path)),
+        ),
+        Ok(None) => (
+            Err("not found in ~/.config/multi-ai-cli/; run 'mai init' from your project".to_string()),
+            None,
+        ),
+        Err(e) => (Err(e.to_string()), None),
+    };
+    let mode = found
+        .as_ref()
+        .and_then(|(project_config, _)| project_config.mode.clone())
+        .unwrap_or_else(system_default_mode);
+
+    // tmux is always needed for `mai send` and friends, but only required by tmux layouts
+    let tmux_required = mode != Mode::Iterm2;
+    checks.push(StatusCheck::new(
+        "tmux",
+        if TmuxManager::is_tmux_installed() {
+            Ok("installed".to_string())
+        } else if tmux_required {
+            Err("not installed; the configured mode needs it".to_string())
+        } else {
+            Err("not installed; needed for tmux modes and 'mai send --app'".to_string())
+        },
+        tmux_required,
+    ));
+    #[cfg(target_os = "macos")]
+    checks.push(StatusCheck::new(
+        "osascript",
+        if applescript::is_osascript_available() {
+            Ok("installed".to_string())
+        } else {
+            Err("not found; iTerm2 mode and 'mai review' need it".to_string())
+        },
+        mode == Mode::Iterm2,
+    ));
+
+    checks.push(StatusCheck::new("mai config", config_check, true));
+    if let Some((project_config, project_path)) = &found {
+        checks.push(StatusCheck::new(
+            "gwt config",
+            find_gwt_config_file(project_path)
+                .map(|path| path.display().to_string())
+                .ok_or_else(|| {
+                    format!(
+                        "git-worktree-config.jsonc not found in {} or its ./main/ subdirectory",
+                        project_path.display()
+                    )
+                }),
+            true,
+        ));
+
+        for app in &project_config.ai_apps {
+            let program = match app.kind {
+                config::AppKind::Shell => continue,
+                config::AppKind::Editor if app.command_for(&mode).trim().is_empty() => {
+                    std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string())
+                }
+                _ => match command_program(app.command_for(&mode)) {
+                    Some(program) => program,
+                    None => continue,
+                },
+            };
+            let outcome = util::find_program(&program)
+                .map(|path| path.display().to_string())
+                .ok_or_else(|| format!("'{}' not found on PATH", program));
+            checks.push(StatusCheck::new(&app.name, outcome, true));
+        }
+    }
+
+    let width = checks.iter().map(|check| check.label.chars().count()).max().unwrap_or(0);
+    for check in &checks {
+        let detail = match &check.outcome {
+            Ok(detail) | Err(detail) => detail,
+        };
+        println!("{} {:<width$}  {}", check.mark(), check.label, detail, width = width);
+    }
+
+    let failed = checks
+        .iter()
+        .filter(|check| check.required && check.outcome.is_err())
+        .count();
+    if failed > 0 {
+        return Err(MultiAiError::CommandFailed(format!(
+            "{} required check{} failed",
+            failed,
+            if failed == 1 { "" } else { "s" }
+        )));
+    }
+    Ok(())
+}
+
 fn apps_command() -> Result<()> {
     let config_dir = ProjectConfig::config_dir()
         .map_err(|e| MultiAiError::Config(format!("Could not determine config directory: {}", e)))?;
 
-    let apps_path = config_dir.join("apps.jsonc");
+    // Open the file discovery picks; create the global one if there is none
+    let apps_path = init::find_apps_file()?.unwrap_or_else(|| config_dir.join("apps.jsonc"));
 
     if !apps_path.exists() {
         std::fs::create_dir_all(&config_dir)?;
@@ -1355,7 +3245,7 @@ fn spinner(msg: &str) -> ProgressBar {
     sp.set_style(
         ProgressStyle::default_spinner()
             .tick_strings(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏", "✓"])
-            .template("{spinner:.cyan} {msg}")
+            .template(if util::color_enabled() { "{spinner:.cyan} {msg}" } else { "{spinner} {msg}" })
             .unwrap(),
     );
     sp.set_message(msg.to_string());
@@ -1363,6 +3253,48 @@ fn spinner(msg: &str) -> ProgressBar {
     sp
 }
 
+fn ask_input(question: &str) -> Result<String> {
+    loop {
+        print!("{}: ", question);
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+
+        let input = input.trim();
+        if !input.is_empty() {
+            return Ok(input.to_string());
+        }
+    }
+}
+
+/// What `mai add` does when the prefix's tmux session is already running
+#[derive(Debug, PartialEq)]
+enum ExistingSession {
+    Attach,
+    Recreate,
+    Keep,
+}
+
+fn ask_existing_session() -> Result<ExistingSession> {
+    loop {
+        print!("[a]ttach to it, [r]ecreate it with the new worktrees, or [k]eep it and exit: ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        match input.trim().to_lowercase().as_str() {
+            "a" | "attach" => return Ok(ExistingSession::Attach),
+            "r" | "recreate" => return Ok(ExistingSession::Recreate),
+            "k" | "keep" | "" => return Ok(ExistingSession::Keep),
+            _ => println!("Please enter 'a', 'r' or 'k'"),
+        }
+    }
+}
+
 fn ask_confirmation(question: &str) -> Result<bool> {
     loop {
         print!("{} [y/n]: ", question);
@@ -1378,3 +3310,22 @@ fn ask_confirmation(question: &str) -> Result<bool> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{command_program, Args};
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Args::command().debug_assert();
+    }
+
+    #[test]
+    fn test_command_program() {
+        assert_eq!(command_program("claude --resume").as_deref(), Some("claude"));
+        assert_eq!(command_program("FOO=1 BAR=x codex").as_deref(), Some("codex"));
+        assert_eq!(command_program("\"/opt/agent\" -p").as_deref(), Some("/opt/agent"));
+        assert_eq!(command_program("  "), None);
+    }
+}
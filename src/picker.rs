@@ -1,6 +1,7 @@
 use crate::config::AiApp;
 use crate::error::Result;
 use crate::init;
+use crate::util;
 use ratatui::crossterm::{
     event::{self, Event, KeyCode, KeyModifiers},
     execute,
@@ -38,9 +39,17 @@ struct PickerState {
 }
 
 pub fn run_app_picker(prefill_env_name: Option<&str>) -> Result<Option<PickerResult>> {
-    let apps = init::load_apps().unwrap_or_default();
+    let apps = init::load_picker_apps().unwrap_or_default();
     let selected: Vec<bool> = apps.iter().map(|a| a.default).collect();
+    run_app_picker_with(prefill_env_name, apps, selected)
+}
 
+/// Run the app picker over a given app list with an initial selection
+pub fn run_app_picker_with(
+    prefill_env_name: Option<&str>,
+    apps: Vec<AiApp>,
+    selected: Vec<bool>,
+) -> Result<Option<PickerResult>> {
     let has_prefill = prefill_env_name.is_some();
     let mut state = PickerState {
         focus: if has_prefill {
@@ -147,15 +156,11 @@ fn handle_input(state: &mut PickerState) -> Result<()> {
         },
         Section::AppList => match key.code {
             KeyCode::Up => state.focused = state.focused.saturating_sub(1),
-            KeyCode::Down => {
-                if state.focused < state.apps.len().saturating_sub(1) {
-                    state.focused += 1;
-                }
+            KeyCode::Down if state.focused < state.apps.len().saturating_sub(1) => {
+                state.focused += 1;
             }
-            KeyCode::Char(' ') => {
-                if state.focused < state.selected.len() {
-                    state.selected[state.focused] = !state.selected[state.focused];
-                }
+            KeyCode::Char(' ') if state.focused < state.selected.len() => {
+                state.selected[state.focused] = !state.selected[state.focused];
             }
             KeyCode::Char('q') => state.cancelled = true,
             _ => {}
@@ -179,9 +184,7 @@ fn render(f: &mut Frame, state: &PickerState) {
     // Header
     let header = Paragraph::new(" New Environment ")
         .style(
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
+            util::fg(Color::Cyan).add_modifier(Modifier::BOLD),
         )
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
@@ -189,9 +192,9 @@ fn render(f: &mut Frame, state: &PickerState) {
 
     // Env name input
     let name_border = if state.focus == Section::EnvName {
-        Style::default().fg(Color::Cyan)
+        util::fg(Color::Cyan)
     } else {
-        Style::default().fg(Color::DarkGray)
+        util::fg(Color::DarkGray)
     };
 
     let name_input = Paragraph::new(state.env_name.as_str()).block(
@@ -214,26 +217,25 @@ fn render(f: &mut Frame, state: &PickerState) {
 
     // App list
     let app_border = if state.focus == Section::AppList {
-        Style::default().fg(Color::Cyan)
+        util::fg(Color::Cyan)
     } else {
-        Style::default().fg(Color::DarkGray)
+        util::fg(Color::DarkGray)
     };
 
+    // Long commands are cut to the list's inner width; the focused one shows in full
+    let list_width = chunks[2].width.saturating_sub(2);
     let items: Vec<ListItem> = state
         .apps
         .iter()
         .enumerate()
         .map(|(i, app)| {
-            let checkbox = if state.selected[i] { "[x]" } else { "[ ]" };
-            ListItem::new(format!(" {} {}", checkbox, app.command))
+            let expanded = state.focus == Section::AppList && i == state.focused;
+            util::checkbox_item(state.selected[i], &app.command, list_width, expanded)
         })
         .collect();
 
     let highlight = if state.focus == Section::AppList {
-        Style::default()
-            .fg(Color::Black)
-            .bg(Color::Gray)
-            .add_modifier(Modifier::BOLD)
+        util::selected().add_modifier(Modifier::BOLD)
     } else {
         Style::default()
     };
@@ -260,7 +262,7 @@ fn render(f: &mut Frame, state: &PickerState) {
     };
 
     let footer = Paragraph::new(hints)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(util::fg(Color::DarkGray))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(footer, chunks[3]);
@@ -340,15 +342,11 @@ fn handle_prefix_input(state: &mut PrefixPickerState) -> Result<()> {
     match key.code {
         KeyCode::Esc | KeyCode::Char('q') => state.cancelled = true,
         KeyCode::Up => state.focused = state.focused.saturating_sub(1),
-        KeyCode::Down => {
-            if state.focused < state.groups.len().saturating_sub(1) {
-                state.focused += 1;
-            }
+        KeyCode::Down if state.focused < state.groups.len().saturating_sub(1) => {
+            state.focused += 1;
         }
-        KeyCode::Char(' ') => {
-            if state.focused < state.selected.len() {
-                state.selected[state.focused] = !state.selected[state.focused];
-            }
+        KeyCode::Char(' ') if state.focused < state.selected.len() => {
+            state.selected[state.focused] = !state.selected[state.focused];
         }
         KeyCode::Char('a') => {
             let all_selected = state.selected.iter().all(|&s| s);
@@ -356,10 +354,8 @@ fn handle_prefix_input(state: &mut PrefixPickerState) -> Result<()> {
                 *s = !all_selected;
             }
         }
-        KeyCode::Enter => {
-            if state.selected.iter().any(|&s| s) {
-                state.confirmed = true;
-            }
+        KeyCode::Enter if state.selected.iter().any(|&s| s) => {
+            state.confirmed = true;
         }
         _ => {}
     }
@@ -380,9 +376,7 @@ fn render_prefix_picker(f: &mut Frame, state: &PrefixPickerState) {
     // Header
     let header = Paragraph::new(" Remove Worktrees ")
         .style(
-            Style::default()
-                .fg(Color::Red)
-                .add_modifier(Modifier::BOLD),
+            util::fg(Color::Red).add_modifier(Modifier::BOLD),
         )
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
@@ -405,27 +399,24 @@ fn render_prefix_picker(f: &mut Frame, state: &PrefixPickerState) {
                 ),
                 Span::styled(
                     format!("  ({} worktrees)", worktrees.len()),
-                    Style::default().fg(Color::DarkGray),
+                    util::fg(Color::DarkGray),
                 ),
             ]);
             let detail_line = Line::from(Span::styled(
                 format!("     {}", worktrees.join(", ")),
-                Style::default().fg(Color::DarkGray),
+                util::fg(Color::DarkGray),
             ));
             ListItem::new(vec![header_line, detail_line])
         })
         .collect();
 
-    let highlight = Style::default()
-        .fg(Color::Black)
-        .bg(Color::Gray)
-        .add_modifier(Modifier::BOLD);
+    let highlight = util::selected().add_modifier(Modifier::BOLD);
 
     let list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
+                .border_style(util::fg(Color::Cyan))
                 .title(" Select environments to remove ")
                 .title_bottom(" Space: toggle | a: all "),
         )
@@ -438,8 +429,131 @@ fn render_prefix_picker(f: &mut Frame, state: &PrefixPickerState) {
     let footer = Paragraph::new(
         "↑/↓: navigate | Space: toggle | a: select all | Enter: remove | Esc: cancel",
     )
-    .style(Style::default().fg(Color::DarkGray))
+    .style(util::fg(Color::DarkGray))
     .alignment(Alignment::Center)
     .block(Block::default().borders(Borders::ALL));
     f.render_widget(footer, chunks[2]);
 }
+
+struct SinglePickerState {
+    focused: usize,
+    cancelled: bool,
+    confirmed: bool,
+}
+
+/// Shows a single-choice list of (label, detail) rows.
+/// Returns the index of the chosen row.
+pub fn run_single_picker(title: &str, items: &[(String, String)]) -> Result<Option<usize>> {
+    if items.is_empty() {
+        return Ok(None);
+    }
+    let mut state = SinglePickerState {
+        focused: 0,
+        cancelled: false,
+        confirmed: false,
+    };
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    while !state.cancelled && !state.confirmed {
+        terminal.draw(|f| render_single_picker(f, title, items, &state))?;
+        handle_single_input(&mut state, items.len())?;
+    }
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+
+    if state.cancelled {
+        return Ok(None);
+    }
+    Ok(Some(state.focused))
+}
+
+fn handle_single_input(state: &mut SinglePickerState, count: usize) -> Result<()> {
+    if !event::poll(Duration::from_millis(16))? {
+        return Ok(());
+    }
+    let Event::Key(key) = event::read()? else {
+        return Ok(());
+    };
+
+    if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        state.cancelled = true;
+        return Ok(());
+    }
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => state.cancelled = true,
+        KeyCode::Up | KeyCode::Char('k') => state.focused = state.focused.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') if state.focused < count.saturating_sub(1) => {
+            state.focused += 1;
+        }
+        KeyCode::Enter => state.confirmed = true,
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn render_single_picker(
+    f: &mut Frame,
+    title: &str,
+    items: &[(String, String)],
+    state: &SinglePickerState,
+) {
+    use ratatui::text::{Line, Span};
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),   // List
+            Constraint::Length(3), // Footer
+        ])
+        .split(f.area());
+
+    let header = Paragraph::new(format!(" {} ", title))
+        .style(
+            util::fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(header, chunks[0]);
+
+    let label_width = items.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let list_items: Vec<ListItem> = items
+        .iter()
+        .map(|(label, detail)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {:<width$}", label, width = label_width),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("  {}", detail), util::fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let highlight = util::selected().add_modifier(Modifier::BOLD);
+
+    let list = List::new(list_items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(util::fg(Color::Cyan)),
+        )
+        .highlight_style(highlight);
+
+    let mut list_state = ListState::default().with_selected(Some(state.focused));
+    f.render_stateful_widget(list, chunks[1], &mut list_state);
+
+    let footer = Paragraph::new("↑/↓: navigate | Enter: select | Esc/q: cancel")
+        .style(util::fg(Color::DarkGray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+}
@@ -1,7 +1,9 @@
-use crate::config::AiApp;
+use crate::applescript;
+use crate::config::{shell_quote, AiApp, Mode};
 use crate::error::{MultiAiError, Result};
 use crate::git::{self, BranchInfo};
 use crate::init;
+use crate::util;
 use crate::worktree::WorktreeManager;
 use ratatui::crossterm::{
     event::{self, Event, KeyCode, KeyModifiers},
@@ -19,6 +21,8 @@ use ratatui::{
     },
     Frame, Terminal,
 };
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io::{self, Write as _};
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -45,6 +49,26 @@ struct SelectedTool {
 const DEFAULT_REVIEW_PROMPT: &str =
     "Review changes in this branch against the base branch. Once done with the review, write findings to REVIEW.md";
 
+/// File with the reviewed branch's changes in `--base-only` reviews
+const CHANGES_DIFF_FILE: &str = "CHANGES.diff";
+
+const BASE_ONLY_REVIEW_PROMPT: &str =
+    "This checkout is the base branch. Review the changes in CHANGES.diff, which is the diff of the branch under review against it. Once done with the review, write findings to REVIEW.md";
+
+const WORKING_TREE_REVIEW_PROMPT: &str =
+    "Review the uncommitted changes in this worktree: work in progress on top of the last commit, staged here so `git diff --cached` shows all of it. Once done with the review, write findings to REVIEW.md";
+
+/// What each review worktree is checked out to
+#[derive(Clone)]
+enum ReviewCheckout {
+    /// The branch under review (`git reset --hard <branch>`)
+    Branch(String),
+    /// The base branch, with the reviewed branch's changes written to CHANGES.diff
+    BaseWithDiff { base: String, diff: String },
+    /// The reviewed worktree's HEAD, with its uncommitted changes applied and staged
+    WorkingTree { head: String, patch: String },
+}
+
 // ---------------------------------------------------------------------------
 // Wizard state
 // ---------------------------------------------------------------------------
@@ -122,53 +146,79 @@ struct ReviewWizardState {
     review_prompt: String,
     send_prompts: bool,
     selected_tools: Vec<SelectedTool>,
+    /// Set by `--no-meta`: meta reviewers are cleared and can't be toggled.
+    no_meta: bool,
+    /// Recently reviewed branches (most recent first), pinned above the full list
+    recent_branches: Vec<String>,
+    /// Reviewers from `--replay`: picking a branch completes the wizard
+    replay_tools: Option<Vec<SelectedTool>>,
+}
+
+/// Pre-selects entries marked with `meta_review` in apps.jsonc, or nothing with `--no-meta`.
+fn initial_meta_selection(review_services: &[AiApp], no_meta: bool) -> Vec<bool> {
+    review_services
+        .iter()
+        .map(|a| a.meta_review && !no_meta)
+        .collect()
 }
 
 impl ReviewWizardState {
-    fn new(branches: Vec<BranchInfo>, branch: Option<&str>) -> Self {
-        let review_services = init::load_apps().unwrap_or_default();
+    fn new(
+        branches: Vec<BranchInfo>,
+        branch: Option<&str>,
+        no_meta: bool,
+        recent_branches: Vec<String>,
+    ) -> Self {
+        // Editor and shell entries have nothing to review with
+        let review_services: Vec<AiApp> = init::load_picker_apps()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(AiApp::is_ai)
+            .collect();
 
         // If a branch argument was given and matches exactly, skip to Configure
-        if let Some(b) = branch {
-            if let Some(matched) = branches.iter().find(|bi| bi.name == b) {
-                let source_branch = matched.name.clone();
-                let source_branch_ref = if matched.remote_only {
-                    format!("origin/{}", matched.name)
-                } else {
-                    matched.name.clone()
-                };
-
-                let mut ai_selected: Vec<bool> =
-                    review_services.iter().map(|app| app.default).collect();
-                if !ai_selected.iter().any(|&s| s) && !ai_selected.is_empty() {
-                    ai_selected[0] = true;
-                }
-                let meta_selected: Vec<bool> =
-                    review_services.iter().map(|a| a.meta_review).collect();
+        if let Some(b) = branch
+            && let Some(matched) = branches.iter().find(|bi| bi.name == b)
+        {
+            let source_branch = matched.name.clone();
+            let source_branch_ref = if matched.remote_only {
+                format!("origin/{}", matched.name)
+            } else {
+                matched.name.clone()
+            };
 
-                let prompt = DEFAULT_REVIEW_PROMPT.to_string();
-                let len = prompt.len();
-                return Self {
-                    current_step: ReviewStep::Configure {
-                        focus: ConfigSection::AiReviewers,
-                        prompt_text: prompt,
-                        prompt_cursor: len,
-                        send_prompts: true,
-                        ai_selected,
-                        ai_focused: 0,
-                        meta_selected,
-                        meta_focused: 0,
-                    },
-                    history: Vec::new(),
-                    app_state: AppState::Running,
-                    review_services,
-                    source_branch,
-                    source_branch_ref,
-                    review_prompt: DEFAULT_REVIEW_PROMPT.to_string(),
-                    send_prompts: true,
-                    selected_tools: Vec::new(),
-                };
+            let mut ai_selected: Vec<bool> =
+                review_services.iter().map(|app| app.default).collect();
+            if !ai_selected.iter().any(|&s| s) && !ai_selected.is_empty() {
+                ai_selected[0] = true;
             }
+            let meta_selected = initial_meta_selection(&review_services, no_meta);
+
+            let prompt = DEFAULT_REVIEW_PROMPT.to_string();
+            let len = prompt.len();
+            return Self {
+                current_step: ReviewStep::Configure {
+                    focus: ConfigSection::AiReviewers,
+                    prompt_text: prompt,
+                    prompt_cursor: len,
+                    send_prompts: true,
+                    ai_selected,
+                    ai_focused: 0,
+                    meta_selected,
+                    meta_focused: 0,
+                },
+                history: Vec::new(),
+                app_state: AppState::Running,
+                review_services,
+                source_branch,
+                source_branch_ref,
+                review_prompt: DEFAULT_REVIEW_PROMPT.to_string(),
+                send_prompts: true,
+                selected_tools: Vec::new(),
+                no_meta,
+                recent_branches,
+                replay_tools: None,
+            };
         }
 
         Self {
@@ -185,7 +235,38 @@ impl ReviewWizardState {
             review_prompt: DEFAULT_REVIEW_PROMPT.to_string(),
             send_prompts: true,
             selected_tools: Vec::new(),
+            no_meta,
+            recent_branches,
+            replay_tools: None,
+        }
+    }
+
+    /// Replace the default review prompt, including one already shown in Configure
+    fn with_review_prompt(mut self, prompt: &str) -> Self {
+        self.review_prompt = prompt.to_string();
+        if let ReviewStep::Configure {
+            prompt_text,
+            prompt_cursor,
+            ..
+        } = &mut self.current_step
+        {
+            *prompt_text = prompt.to_string();
+            *prompt_cursor = prompt_text.len();
         }
+        self
+    }
+
+    /// Reuse a saved setup, completing right away if the branch is already chosen
+    fn with_replay(mut self, tools: Vec<SelectedTool>, prompt: &str, send_prompts: bool) -> Self {
+        self.review_prompt = prompt.to_string();
+        self.send_prompts = send_prompts;
+        if matches!(self.current_step, ReviewStep::Configure { .. }) {
+            self.selected_tools = tools;
+            self.app_state = AppState::Completed;
+        } else {
+            self.replay_tools = Some(tools);
+        }
+        self
     }
 
     fn next(&mut self, next_step: ReviewStep) {
@@ -228,99 +309,405 @@ fn filtered_branches<'a>(branches: &'a [BranchInfo], filter: &str) -> Vec<(usize
     }
 }
 
+/// Picker entries: the "Recent" group (recently reviewed branches that still
+/// exist) followed by the full list. Returns the entries and the Recent count.
+fn branch_choices<'a>(
+    branches: &'a [BranchInfo],
+    recent: &[String],
+    filter: &str,
+) -> (Vec<&'a BranchInfo>, usize) {
+    let filtered = filtered_branches(branches, filter);
+    let mut choices: Vec<&BranchInfo> = recent
+        .iter()
+        .filter_map(|name| filtered.iter().find(|(_, b)| &b.name == name).map(|(_, b)| *b))
+        .collect();
+    let recent_count = choices.len();
+    choices.extend(filtered.into_iter().map(|(_, b)| b));
+    (choices, recent_count)
+}
+
+// ---------------------------------------------------------------------------
+// Recent review history (~/.config/multi-ai-cli/review-history.json)
+// ---------------------------------------------------------------------------
+
+const MAX_RECENT_BRANCHES: usize = 5;
+
+fn review_history_path() -> Option<PathBuf> {
+    crate::config::ProjectConfig::config_dir()
+        .ok()
+        .map(|dir| dir.join("review-history.json"))
+}
+
+fn load_review_history() -> HashMap<String, Vec<String>> {
+    review_history_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn load_recent_branches(project_path: &Path) -> Vec<String> {
+    load_review_history()
+        .remove(&project_path.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Moves `branch` to the front of the list, dropping duplicates and old entries.
+fn push_recent_branch(recent: &mut Vec<String>, branch: &str) {
+    recent.retain(|b| b != branch);
+    recent.insert(0, branch.to_string());
+    recent.truncate(MAX_RECENT_BRANCHES);
+}
+
+fn record_reviewed_branch(project_path: &Path, branch: &str) -> Result<()> {
+    let Some(path) = review_history_path() else {
+        return Ok(());
+    };
+    let mut history = load_review_history();
+    let recent = history
+        .entry(project_path.to_string_lossy().to_string())
+        .or_default();
+    push_recent_branch(recent, branch);
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_string_pretty(&history)
+        .map_err(|e| MultiAiError::Config(format!("Failed to serialize review history: {}", e)))?;
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Review manifest (--save / --replay)
+// ---------------------------------------------------------------------------
+
+/// A review setup saved with `mai review --save` and re-run with `--replay`.
+/// Reviewers are referenced by their apps.jsonc name.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ReviewManifest {
+    pub reviewers: Vec<String>,
+    #[serde(default)]
+    pub meta_reviewers: Vec<String>,
+    pub prompt: String,
+    #[serde(default = "default_send_prompts")]
+    pub send_prompts: bool,
+    /// Base ref of a `--base-only` review
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+}
+
+fn default_send_prompts() -> bool {
+    true
+}
+
+impl ReviewManifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            MultiAiError::Review(format!(
+                "Failed to read review manifest {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            MultiAiError::Review(format!("Invalid review manifest {}: {}", path.display(), e))
+        })
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| {
+            MultiAiError::Review(format!("Failed to serialize review manifest: {}", e))
+        })?;
+        std::fs::write(path, content + "\n")?;
+        Ok(())
+    }
+
+    fn from_wizard(wizard: &ReviewWizardState, base: Option<&str>) -> Self {
+        let names = |tag: ReviewTag| {
+            wizard
+                .selected_tools
+                .iter()
+                .filter(|t| t.tag == tag)
+                .map(|t| wizard.review_services[t.service_index].name.clone())
+                .collect()
+        };
+        Self {
+            reviewers: names(ReviewTag::Ai),
+            meta_reviewers: names(ReviewTag::Meta),
+            prompt: wizard.review_prompt.clone(),
+            send_prompts: wizard.send_prompts,
+            base: base.map(str::to_string),
+        }
+    }
+
+    /// `--reviewers N [--meta APP]`: the first `count` apps marked `default` as
+    /// reviewers, `meta` as the meta-reviewer, and `prompt`.
+    fn quick(review_services: &[AiApp], quick: &QuickReview, prompt: &str) -> Result<Self> {
+        let defaults: Vec<&str> = review_services
+            .iter()
+            .filter(|app| app.default)
+            .map(|app| app.name.as_str())
+            .collect();
+        if quick.reviewers == 0 || quick.reviewers > defaults.len() {
+            return Err(MultiAiError::Review(format!(
+                "--reviewers {}: pick between 1 and {}, the number of apps marked default in apps.jsonc ({})",
+                quick.reviewers,
+                defaults.len(),
+                defaults.join(", ")
+            )));
+        }
+        if let Some(meta) = &quick.meta
+            && !review_services.iter().any(|app| &app.name == meta)
+        {
+            return Err(MultiAiError::Review(format!(
+                "--meta: no app named '{}' in apps.jsonc",
+                meta
+            )));
+        }
+        Ok(Self {
+            reviewers: defaults[..quick.reviewers].iter().map(|name| name.to_string()).collect(),
+            meta_reviewers: quick.meta.iter().cloned().collect(),
+            prompt: prompt.to_string(),
+            send_prompts: true,
+            base: None,
+        })
+    }
+
+    /// Map reviewer names onto apps.jsonc entries; meta reviewers are dropped with `no_meta`
+    fn resolve(&self, review_services: &[AiApp], no_meta: bool) -> Result<Vec<SelectedTool>> {
+        if self.reviewers.is_empty() {
+            return Err(MultiAiError::Review(
+                "Review manifest lists no reviewers".to_string(),
+            ));
+        }
+        let meta: &[String] = if no_meta { &[] } else { &self.meta_reviewers };
+        let ai = self.reviewers.iter().map(|n| (n, ReviewTag::Ai));
+        let meta = meta.iter().map(|n| (n, ReviewTag::Meta));
+        ai.chain(meta)
+            .map(|(name, tag)| {
+                review_services
+                    .iter()
+                    .position(|a| &a.name == name)
+                    .map(|service_index| SelectedTool { service_index, tag })
+                    .ok_or_else(|| {
+                        MultiAiError::Review(format!(
+                            "Reviewer '{}' from the manifest is not in apps.jsonc",
+                            name
+                        ))
+                    })
+            })
+            .collect()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Public entry point
 // ---------------------------------------------------------------------------
 
+/// `--reviewers N [--meta APP]`: review without the wizard
+pub struct QuickReview {
+    pub reviewers: usize,
+    pub meta: Option<String>,
+}
+
+/// Per-run options for `mai review` that don't come from the project config
+#[derive(Default)]
+pub struct ReviewOptions {
+    pub branch: Option<String>,
+    pub no_meta: bool,
+    /// `--base-only`: review the branch's diff against its base
+    pub base_only: bool,
+    /// `--base`: base ref for `--base-only`, detected when not given
+    pub base: Option<String>,
+    /// `--working-tree`: review the current worktree's uncommitted changes
+    pub working_tree: bool,
+    /// Where `--save` writes the manifest
+    pub save: Option<PathBuf>,
+    /// `--replay`: manifest saved by an earlier `--save`
+    pub replay: Option<PathBuf>,
+    pub quick: Option<QuickReview>,
+    /// `--explain`: print what the review would do and stop before creating anything
+    pub explain: bool,
+}
+
 pub fn run_review(
-    _project_config: crate::config::ProjectConfig,
+    project_config: crate::config::ProjectConfig,
     project_name: String,
     project_path: PathBuf,
     worktree_manager: WorktreeManager,
-    branch: Option<String>,
+    options: ReviewOptions,
 ) -> Result<()> {
-    // 1. Fetch branches (may involve network I/O) before entering TUI
-    print!("Fetching branches...");
-    io::stdout().flush().ok();
-    let branches = git::list_all_branches(&project_path);
-    println!(" {} branches found.", branches.len());
+    let ReviewOptions {
+        branch,
+        no_meta,
+        base_only,
+        base,
+        working_tree,
+        save,
+        replay,
+        quick,
+        explain,
+    } = options;
+
+    let replay = replay.map(|path| ReviewManifest::load(&path)).transpose()?;
+    let working_tree = if working_tree {
+        let current_dir = crate::config::current_dir()?;
+        Some(git::get_repo_root(&current_dir).ok_or_else(|| {
+            MultiAiError::Review("--working-tree must be run inside a git worktree".to_string())
+        })?)
+    } else {
+        None
+    };
+    // A replayed --base-only review keeps its base unless --base-only is given again
+    // (or the working tree is reviewed instead)
+    let (base_only, base) = match replay.as_ref().and_then(|m| m.base.clone()) {
+        Some(saved) if !base_only && working_tree.is_none() => (true, Some(saved)),
+        _ => (base_only, base),
+    };
+    let diff_base = if base_only {
+        Some(base.or_else(|| git::default_base_ref(&project_path)).ok_or_else(|| {
+            MultiAiError::Review("Could not detect the base branch; pass --base <REF>".to_string())
+        })?)
+    } else {
+        None
+    };
+
+    // Review sessions are driven through AppleScript; fail before creating anything
+    if !explain && !applescript::is_osascript_available() {
+        return Err(MultiAiError::Review(
+            "osascript not found; mai review requires iTerm2 on macOS".to_string(),
+        ));
+    }
 
-    // 2. Run TUI wizard
-    let mut terminal = setup_terminal()?;
-    let mut wizard = ReviewWizardState::new(branches, branch.as_deref());
-    let result = run_wizard(&mut terminal, &mut wizard);
-    cleanup_terminal(&mut terminal)?;
+    // Reviewing the working tree needs no branch, so the branch step is skipped
+    // through a branch named after the worktree's
+    let working_tree = match working_tree {
+        Some(dir) => {
+            let head = git_output(&dir, &["rev-parse", "HEAD"])?.trim().to_string();
+            let patch = working_tree_patch(&dir)?;
+            let name = git::current_branch(&dir)
+                .filter(|name| name != "HEAD")
+                .unwrap_or_else(|| "working-tree".to_string());
+            Some((name, ReviewCheckout::WorkingTree { head, patch }))
+        }
+        None => None,
+    };
 
-    result?;
+    // 1. Fetch branches (may involve network I/O) before entering TUI
+    let branches = match &working_tree {
+        Some((name, _)) => vec![BranchInfo {
+            name: name.clone(),
+            date: String::new(),
+            remote_only: false,
+        }],
+        None => {
+            print!("Fetching branches...");
+            io::stdout().flush().ok();
+            let branches = git::list_all_branches(&project_path, project_config.review.fetch_prune());
+            println!(" {} branches found.", branches.len());
+            branches
+        }
+    };
+    let branch = working_tree.as_ref().map(|(name, _)| name.clone()).or(branch);
+
+    // 2. Run TUI wizard (a replay with a matching branch needs no input)
+    let mut wizard = ReviewWizardState::new(
+        branches,
+        branch.as_deref(),
+        no_meta,
+        load_recent_branches(&project_path),
+    );
+    let replay = match &quick {
+        Some(quick) => {
+            let prompt = match (&diff_base, &working_tree) {
+                (Some(_), _) => BASE_ONLY_REVIEW_PROMPT,
+                (None, Some(_)) => WORKING_TREE_REVIEW_PROMPT,
+                (None, None) => DEFAULT_REVIEW_PROMPT,
+            };
+            Some(ReviewManifest::quick(&wizard.review_services, quick, prompt)?)
+        }
+        None => replay,
+    };
+    if let Some(manifest) = &replay {
+        let tools = manifest.resolve(&wizard.review_services, no_meta)?;
+        wizard = wizard.with_replay(tools, &manifest.prompt, manifest.send_prompts);
+    } else if diff_base.is_some() {
+        wizard = wizard.with_review_prompt(BASE_ONLY_REVIEW_PROMPT);
+    } else if working_tree.is_some() {
+        wizard = wizard.with_review_prompt(WORKING_TREE_REVIEW_PROMPT);
+    }
+    // The quick mode is for scripts, so it never falls back to the branch picker
+    if quick.is_some() && wizard.app_state == AppState::Running {
+        return Err(MultiAiError::Review(format!(
+            "--reviewers needs an existing branch; '{}' was not found",
+            branch.unwrap_or_default()
+        )));
+    }
+    if wizard.app_state == AppState::Running {
+        let mut terminal = setup_terminal()?;
+        let result = run_wizard(&mut terminal, &mut wizard);
+        cleanup_terminal(&mut terminal)?;
+        result?;
+    }
 
     if wizard.app_state != AppState::Completed {
         println!("Review cancelled.");
         return Ok(());
     }
 
+    let checkout = match (working_tree, diff_base.clone()) {
+        (Some((_, checkout)), _) => checkout,
+        (None, Some(base)) => {
+            let diff = branch_diff(&project_path, &base, &wizard.source_branch_ref)?;
+            ReviewCheckout::BaseWithDiff { base, diff }
+        }
+        (None, None) => ReviewCheckout::Branch(wizard.source_branch_ref.clone()),
+    };
+
+    if explain {
+        return explain_review(&wizard, &worktree_manager, &checkout, no_meta);
+    }
+
+    if let Some(path) = &save {
+        ReviewManifest::from_wizard(&wizard, diff_base.as_deref()).save(path)?;
+        println!("Saved review manifest to {}", path.display());
+    }
+
     // 2. Generate branch prefix
     let branch_prefix =
         generate_review_prefix(worktree_manager.worktrees_path(), &wizard.source_branch);
     println!("Review prefix: {}", branch_prefix);
 
     // 3. Build AiApp list
-    let review_apps: Vec<AiApp> = wizard
-        .selected_tools
-        .iter()
-        .map(|t| {
-            let app = &wizard.review_services[t.service_index];
-            if t.tag == ReviewTag::Meta {
-                AiApp {
-                    name: format!("meta-{}", app.name),
-                    command: app.command.clone(),
-                    slug: app.slug.as_ref().map(|s| format!("meta-{}", s)),
-                    ultrathink: app.ultrathink.clone(),
-                    default: false,
-                    meta_review: false,
-                    description: app.description.clone(),
-                }
-            } else {
-                app.clone()
-            }
-        })
-        .collect();
+    let review_apps = review_apps(&wizard);
 
     // 4. Create worktrees in parallel
+    match &checkout {
+        ReviewCheckout::Branch(_) => {}
+        ReviewCheckout::BaseWithDiff { base, .. } => {
+            println!("Reviewers get {} on top of {}", CHANGES_DIFF_FILE, base)
+        }
+        ReviewCheckout::WorkingTree { head, .. } => {
+            println!("Reviewers get the uncommitted changes on top of {}", short_commit(head))
+        }
+    }
+    let worktrees_path = worktree_manager.worktrees_path();
+    std::fs::create_dir_all(worktrees_path).map_err(|e| {
+        MultiAiError::Worktree(format!(
+            "Failed to create worktrees directory {}: {}",
+            worktrees_path.display(),
+            e
+        ))
+    })?;
     println!("Creating review worktrees...");
-    let worktree_paths = create_review_worktrees(
-        &worktree_manager,
-        &branch_prefix,
-        &review_apps,
-        &wizard.source_branch_ref,
-    )?;
+    let worktree_paths =
+        create_review_worktrees(&worktree_manager, &branch_prefix, &review_apps, &checkout)?;
     println!("All review worktrees created.");
 
     // 5. Build review & meta prompts
     let review_prompt = &wizard.review_prompt;
 
-    // Build review locations for meta prompt
-    let mut review_locations = Vec::new();
-    for (i, tool) in wizard.selected_tools.iter().enumerate() {
-        if tool.tag == ReviewTag::Ai {
-            if let Some((_app, path)) = worktree_paths.get(i) {
-                let app = &wizard.review_services[tool.service_index];
-                review_locations.push(format!("- {}: {}/REVIEW.md", app.name, path));
-            }
-        }
-    }
-    let meta_prompt = if !review_locations.is_empty() {
-        Some(format!(
-            "Your task is to review the code reviews made by other AI tools. \
-             You will find the review markdown files from these locations:\n\
-             {}\n\n\
-             Please wait for the REVIEW.md files to appear, then read all of the reviews, \
-             and create a comprehensive summary of the review results. You can investigate the repo and review results if needed. Use sub-agents if needed.\n\n\
-             Once you are done, create REVIEW_SUMMARY.md with your consolidated findings.",
-            review_locations.join("\n")
-        ))
-    } else {
-        None
-    };
+    let meta_prompt = meta_prompt(&wizard, &worktree_paths, no_meta);
 
     if !wizard.send_prompts {
         println!("Note: AI review prompts will NOT be sent automatically.");
@@ -337,6 +724,14 @@ pub fn run_review(
         &branch_prefix,
     )?;
 
+    // Recorded only once the review is running, so a failed start isn't offered
+    // as a recent branch; only branches are offered again in the picker
+    if !matches!(checkout, ReviewCheckout::WorkingTree { .. })
+        && let Err(e) = record_reviewed_branch(&project_path, &wizard.source_branch)
+    {
+        eprintln!("warning: could not save review history: {}", e);
+    }
+
     println!(
         "\nReview session '{}-{}' started in iTerm2.",
         project_name, branch_prefix
@@ -344,6 +739,151 @@ pub fn run_review(
     Ok(())
 }
 
+/// The apps to launch, in column order; meta reviewers get a `meta-` name and slug so
+/// their worktrees don't collide with a reviewer running the same app
+fn review_apps(wizard: &ReviewWizardState) -> Vec<AiApp> {
+    wizard
+        .selected_tools
+        .iter()
+        .map(|t| {
+            let app = &wizard.review_services[t.service_index];
+            if t.tag == ReviewTag::Meta {
+                AiApp {
+                    name: format!("meta-{}", app.name),
+                    command: app.command.clone(),
+                    slug: app.slug.as_ref().map(|s| format!("meta-{}", s)),
+                    ultrathink: app.ultrathink.clone(),
+                    default: false,
+                    meta_review: false,
+                    auto_submit: app.auto_submit,
+                    kind: app.kind,
+                    commands: app.commands.clone(),
+                    subdir: app.subdir.clone(),
+                    columns: app.columns,
+                    description: app.description.clone(),
+                }
+            } else {
+                app.clone()
+            }
+        })
+        .collect()
+}
+
+/// Prompt pointing a meta reviewer at every reviewer's REVIEW.md, if there is one
+fn meta_prompt(
+    wizard: &ReviewWizardState,
+    worktree_paths: &[(AiApp, String)],
+    no_meta: bool,
+) -> Option<String> {
+    let mut review_locations = Vec::new();
+    for (i, tool) in wizard.selected_tools.iter().enumerate() {
+        if tool.tag == ReviewTag::Ai
+            && let Some((_app, path)) = worktree_paths.get(i)
+        {
+            let app = &wizard.review_services[tool.service_index];
+            review_locations.push(format!("- {}: {}/REVIEW.md", app.name, path));
+        }
+    }
+    if no_meta || review_locations.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "Your task is to review the code reviews made by other AI tools. \
+         You will find the review markdown files from these locations:\n\
+         {}\n\n\
+         Please wait for the REVIEW.md files to appear, then read all of the reviews, \
+         and create a comprehensive summary of the review results. You can investigate the repo and review results if needed. Use sub-agents if needed.\n\n\
+         Once you are done, create REVIEW_SUMMARY.md with your consolidated findings.",
+        review_locations.join("\n")
+    ))
+}
+
+/// `--explain`: print the review `run_review` would start, without creating anything
+fn explain_review(
+    wizard: &ReviewWizardState,
+    worktree_manager: &WorktreeManager,
+    checkout: &ReviewCheckout,
+    no_meta: bool,
+) -> Result<()> {
+    let branch_prefix =
+        generate_review_prefix(worktree_manager.worktrees_path(), &wizard.source_branch);
+    let review_apps = review_apps(wizard);
+    let worktree_paths: Vec<(AiApp, String)> = review_apps
+        .iter()
+        .map(|app| {
+            let branch_name = format!("{}-{}-01", branch_prefix, app.slug());
+            let path = worktree_manager.worktrees_path().join(&branch_name);
+            (app.clone(), path.to_string_lossy().to_string())
+        })
+        .collect();
+
+    println!("Review plan (nothing has been created)");
+    println!();
+    println!("Source branch: {} ({})", wizard.source_branch, wizard.source_branch_ref);
+    match checkout {
+        ReviewCheckout::BaseWithDiff { base, diff } => {
+            println!(
+                "Checkout:      {} with the branch's changes in {} ({} lines)",
+                base,
+                CHANGES_DIFF_FILE,
+                diff.lines().count()
+            );
+        }
+        ReviewCheckout::WorkingTree { head, patch } => {
+            println!(
+                "Checkout:      {} with the uncommitted changes applied ({} patch lines)",
+                short_commit(head),
+                patch.lines().count()
+            );
+        }
+        ReviewCheckout::Branch(branch) => println!("Checkout:      {}", branch),
+    }
+    println!("Review prefix: {}", branch_prefix);
+    let backend = if applescript::is_osascript_available() {
+        "iTerm2 (one tab, one column per app)"
+    } else {
+        "iTerm2 (osascript not found, so the review itself would fail here)"
+    };
+    println!("Backend:       {}", backend);
+
+    println!();
+    for ((app, path), tool) in worktree_paths.iter().zip(&wizard.selected_tools) {
+        let role = match tool.tag {
+            ReviewTag::Ai => "reviewer",
+            ReviewTag::Meta => "meta reviewer",
+        };
+        println!("{} ({})", app.name, role);
+        println!("  branch:   {}-{}-01", branch_prefix, app.slug());
+        println!("  worktree: {}", path);
+        println!("  command:  {}", app.command);
+    }
+
+    println!();
+    if wizard.send_prompts {
+        println!("Prompt sent to each reviewer:");
+    } else {
+        println!("Reviewer prompt (not sent automatically):");
+    }
+    print_indented(&wizard.review_prompt);
+
+    if let Some(prompt) = meta_prompt(wizard, &worktree_paths, no_meta) {
+        println!();
+        println!("Meta review prompt (meta reviewers start without a prompt; paste it once the reviews are in):");
+        print_indented(&prompt);
+    }
+    Ok(())
+}
+
+fn print_indented(text: &str) {
+    for line in text.lines() {
+        if line.is_empty() {
+            println!();
+        } else {
+            println!("  {}", line);
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Terminal setup
 // ---------------------------------------------------------------------------
@@ -411,8 +951,8 @@ fn handle_branch_input(wizard: &mut ReviewWizardState, key: KeyCode) {
                 filter,
             } = &wizard.current_step
             {
-                let filtered = filtered_branches(branches, filter);
-                if let Some((_orig_idx, branch)) = filtered.get(*focused) {
+                let (choices, _) = branch_choices(branches, &wizard.recent_branches, filter);
+                if let Some(branch) = choices.get(*focused) {
                     wizard.source_branch = branch.name.clone();
                     wizard.source_branch_ref = if branch.remote_only {
                         format!("origin/{}", branch.name)
@@ -420,6 +960,12 @@ fn handle_branch_input(wizard: &mut ReviewWizardState, key: KeyCode) {
                         branch.name.clone()
                     };
 
+                    if let Some(tools) = wizard.replay_tools.clone() {
+                        wizard.selected_tools = tools;
+                        wizard.app_state = AppState::Completed;
+                        return;
+                    }
+
                     let mut ai_selected: Vec<bool> = wizard
                         .review_services
                         .iter()
@@ -430,12 +976,8 @@ fn handle_branch_input(wizard: &mut ReviewWizardState, key: KeyCode) {
                         ai_selected[0] = true;
                     }
 
-                    // Pre-select entries marked with meta_review in apps.jsonc
-                    let meta_selected: Vec<bool> = wizard
-                        .review_services
-                        .iter()
-                        .map(|a| a.meta_review)
-                        .collect();
+                    let meta_selected =
+                        initial_meta_selection(&wizard.review_services, wizard.no_meta);
 
                     let prompt = wizard.review_prompt.clone();
                     let len = prompt.len();
@@ -464,7 +1006,9 @@ fn handle_branch_input(wizard: &mut ReviewWizardState, key: KeyCode) {
                 filter,
             } = &mut wizard.current_step
             {
-                let count = filtered_branches(branches, filter).len();
+                let count = branch_choices(branches, &wizard.recent_branches, filter)
+                    .0
+                    .len();
                 if count > 0 && *focused < count - 1 {
                     *focused += 1;
                 }
@@ -577,29 +1121,21 @@ fn handle_configure_input(wizard: &mut ReviewWizardState, key: KeyCode, modifier
         }
         ConfigSection::AiReviewers => match key {
             KeyCode::Up => *ai_focused = ai_focused.saturating_sub(1),
-            KeyCode::Down => {
-                if *ai_focused < ai_selected.len().saturating_sub(1) {
-                    *ai_focused += 1;
-                }
+            KeyCode::Down if *ai_focused < ai_selected.len().saturating_sub(1) => {
+                *ai_focused += 1;
             }
-            KeyCode::Char(' ') => {
-                if *ai_focused < ai_selected.len() {
-                    ai_selected[*ai_focused] = !ai_selected[*ai_focused];
-                }
+            KeyCode::Char(' ') if *ai_focused < ai_selected.len() => {
+                ai_selected[*ai_focused] = !ai_selected[*ai_focused];
             }
             _ => {}
         },
         ConfigSection::MetaReviewer => match key {
             KeyCode::Up => *meta_focused = meta_focused.saturating_sub(1),
-            KeyCode::Down => {
-                if *meta_focused < meta_selected.len().saturating_sub(1) {
-                    *meta_focused += 1;
-                }
+            KeyCode::Down if *meta_focused < meta_selected.len().saturating_sub(1) => {
+                *meta_focused += 1;
             }
-            KeyCode::Char(' ') => {
-                if *meta_focused < meta_selected.len() {
-                    meta_selected[*meta_focused] = !meta_selected[*meta_focused];
-                }
+            KeyCode::Char(' ') if !wizard.no_meta && *meta_focused < meta_selected.len() => {
+                meta_selected[*meta_focused] = !meta_selected[*meta_focused];
             }
             _ => {}
         },
@@ -617,44 +1153,36 @@ fn handle_prompt_keys(
             text.insert(*cursor, '\n');
             *cursor += 1;
         }
-        KeyCode::Backspace => {
-            if *cursor > 0 {
-                let prev = text[..*cursor]
-                    .char_indices()
-                    .next_back()
-                    .map(|(i, _)| i)
-                    .unwrap_or(0);
-                text.drain(prev..*cursor);
-                *cursor = prev;
-            }
+        KeyCode::Backspace if *cursor > 0 => {
+            let prev = text[..*cursor]
+                .char_indices()
+                .next_back()
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+            text.drain(prev..*cursor);
+            *cursor = prev;
         }
-        KeyCode::Delete => {
-            if *cursor < text.len() {
-                let next = text[*cursor..]
-                    .char_indices()
-                    .nth(1)
-                    .map(|(i, _)| *cursor + i)
-                    .unwrap_or(text.len());
-                text.drain(*cursor..next);
-            }
+        KeyCode::Delete if *cursor < text.len() => {
+            let next = text[*cursor..]
+                .char_indices()
+                .nth(1)
+                .map(|(i, _)| *cursor + i)
+                .unwrap_or(text.len());
+            text.drain(*cursor..next);
         }
-        KeyCode::Left => {
-            if *cursor > 0 {
-                *cursor = text[..*cursor]
-                    .char_indices()
-                    .next_back()
-                    .map(|(i, _)| i)
-                    .unwrap_or(0);
-            }
+        KeyCode::Left if *cursor > 0 => {
+            *cursor = text[..*cursor]
+                .char_indices()
+                .next_back()
+                .map(|(i, _)| i)
+                .unwrap_or(0);
         }
-        KeyCode::Right => {
-            if *cursor < text.len() {
-                *cursor = text[*cursor..]
-                    .char_indices()
-                    .nth(1)
-                    .map(|(i, _)| *cursor + i)
-                    .unwrap_or(text.len());
-            }
+        KeyCode::Right if *cursor < text.len() => {
+            *cursor = text[*cursor..]
+                .char_indices()
+                .nth(1)
+                .map(|(i, _)| *cursor + i)
+                .unwrap_or(text.len());
         }
         KeyCode::Home => *cursor = 0,
         KeyCode::End => *cursor = text.len(),
@@ -698,9 +1226,7 @@ fn render_header(f: &mut Frame, area: Rect, wizard: &ReviewWizardState) {
     );
     let header = Paragraph::new(title)
         .style(
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
+            util::fg(Color::Cyan).add_modifier(Modifier::BOLD),
         )
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
@@ -713,7 +1239,7 @@ fn render_content(f: &mut Frame, area: Rect, wizard: &ReviewWizardState) {
             branches,
             focused,
             filter,
-        } => render_branch_select(f, area, branches, *focused, filter),
+        } => render_branch_select(f, area, branches, &wizard.recent_branches, *focused, filter),
         ReviewStep::Configure { .. } => render_configure(f, area, wizard),
     }
 }
@@ -722,52 +1248,53 @@ fn render_branch_select(
     f: &mut Frame,
     area: Rect,
     branches: &[BranchInfo],
+    recent: &[String],
     focused: usize,
     filter: &str,
 ) {
-    let filtered = filtered_branches(branches, filter);
+    let (choices, recent_count) = branch_choices(branches, recent, filter);
+    let full_count = choices.len() - recent_count;
 
     // Find the longest branch name for alignment
-    let max_name_len = filtered
+    let max_name_len = choices
         .iter()
-        .map(|(_, b)| b.name.len())
+        .map(|b| b.name.len())
         .max()
         .unwrap_or(0)
         .min(50);
 
-    let items: Vec<ListItem> = filtered
+    let items: Vec<ListItem> = choices
         .iter()
         .enumerate()
-        .map(|(i, (_orig_idx, branch))| {
-            let is_focused = i == focused;
-            let style = if is_focused {
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Gray)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
-            };
-            let origin_tag = if branch.remote_only { " (origin)" } else { "" };
-            let origin_style = if is_focused {
-                Style::default().fg(Color::Black).bg(Color::Gray)
-            } else {
-                Style::default().fg(Color::Yellow)
-            };
-            let date_style = if is_focused {
-                Style::default().fg(Color::Black).bg(Color::Gray)
-            } else {
-                Style::default().fg(Color::DarkGray)
-            };
-            let line = Line::from(vec![
-                Span::raw(format!("  {:<width$}", branch.name, width = max_name_len)),
-                Span::styled(origin_tag, origin_style),
-                Span::raw("  "),
-                Span::styled(&branch.date, date_style),
-            ]);
-            ListItem::new(line).style(style)
-        })
+        .map(|(i, branch)| branch_list_item(branch, i == focused, max_name_len))
         .collect();
+    let mut items = items.into_iter();
+    let recent_items: Vec<ListItem> = items.by_ref().take(recent_count).collect();
+    let full_items: Vec<ListItem> = items.collect();
+
+    let (recent_area, list_area) = if recent_count > 0 {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(recent_count as u16 + 2),
+                Constraint::Min(0),
+            ])
+            .split(area);
+        (Some(rows[0]), rows[1])
+    } else {
+        (None, area)
+    };
+
+    let highlight = util::selected().add_modifier(Modifier::BOLD);
+
+    if let Some(recent_area) = recent_area {
+        let recent_list = List::new(recent_items)
+            .block(Block::default().borders(Borders::ALL).title(" Recent "))
+            .highlight_style(highlight);
+        let mut recent_state =
+            ListState::default().with_selected((focused < recent_count).then_some(focused));
+        f.render_stateful_widget(recent_list, recent_area, &mut recent_state);
+    }
 
     let filter_display = if filter.is_empty() {
         String::new()
@@ -777,34 +1304,29 @@ fn render_branch_select(
 
     let title = format!(
         " Select Branch to Review{} [{} branches] ",
-        filter_display,
-        filtered.len()
+        filter_display, full_count
     );
 
-    let item_count = filtered.len();
-    let list = List::new(items)
+    let list = List::new(full_items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .title(title)
                 .title_bottom(" Type to filter | ↑/↓: navigate | Enter: select "),
         )
-        .highlight_style(
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Gray)
-                .add_modifier(Modifier::BOLD),
-        );
+        .highlight_style(highlight);
 
-    let mut list_state = ListState::default().with_selected(Some(focused));
-    f.render_stateful_widget(list, area, &mut list_state);
+    let list_focused = focused.checked_sub(recent_count);
+    let mut list_state = ListState::default().with_selected(list_focused);
+    f.render_stateful_widget(list, list_area, &mut list_state);
 
     // Scrollbar
-    let scrollbar_area = area.inner(ratatui::layout::Margin {
+    let scrollbar_area = list_area.inner(ratatui::layout::Margin {
         vertical: 1,
         horizontal: 0,
     });
-    let mut scrollbar_state = ScrollbarState::new(item_count.saturating_sub(1)).position(focused);
+    let mut scrollbar_state =
+        ScrollbarState::new(full_count.saturating_sub(1)).position(list_focused.unwrap_or(0));
     f.render_stateful_widget(
         Scrollbar::new(ScrollbarOrientation::VerticalRight),
         scrollbar_area,
@@ -812,6 +1334,32 @@ fn render_branch_select(
     );
 }
 
+fn branch_list_item(branch: &BranchInfo, is_focused: bool, max_name_len: usize) -> ListItem<'_> {
+    let style = if is_focused {
+        util::selected().add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    let origin_tag = if branch.remote_only { " (origin)" } else { "" };
+    let origin_style = if is_focused {
+        util::selected()
+    } else {
+        util::fg(Color::Yellow)
+    };
+    let date_style = if is_focused {
+        util::selected()
+    } else {
+        util::fg(Color::DarkGray)
+    };
+    let line = Line::from(vec![
+        Span::raw(format!("  {:<width$}", branch.name, width = max_name_len)),
+        Span::styled(origin_tag, origin_style),
+        Span::raw("  "),
+        Span::styled(&branch.date, date_style),
+    ]);
+    ListItem::new(line).style(style)
+}
+
 fn render_configure(f: &mut Frame, area: Rect, wizard: &ReviewWizardState) {
     let ReviewStep::Configure {
         focus,
@@ -846,9 +1394,9 @@ fn render_configure(f: &mut Frame, area: Rect, wizard: &ReviewWizardState) {
 
     // -- Prompt section --
     let prompt_border = if *focus == ConfigSection::Prompt {
-        Style::default().fg(Color::Cyan)
+        util::fg(Color::Cyan)
     } else {
-        Style::default().fg(Color::DarkGray)
+        util::fg(Color::DarkGray)
     };
 
     let paragraph = Paragraph::new(prompt_text.as_str())
@@ -882,8 +1430,8 @@ fn render_configure(f: &mut Frame, area: Rect, wizard: &ReviewWizardState) {
             };
 
             if line_idx == cursor_line {
-                if inner_width > 0 {
-                    screen_row += (cursor_col / inner_width) as u16;
+                if let Some(row_offset) = cursor_col.checked_div(inner_width) {
+                    screen_row += row_offset as u16;
                     screen_col = (cursor_col % inner_width) as u16;
                 }
                 break;
@@ -901,9 +1449,9 @@ fn render_configure(f: &mut Frame, area: Rect, wizard: &ReviewWizardState) {
 
     // -- Send prompts toggle --
     let toggle_border = if *focus == ConfigSection::SendPrompts {
-        Style::default().fg(Color::Cyan)
+        util::fg(Color::Cyan)
     } else {
-        Style::default().fg(Color::DarkGray)
+        util::fg(Color::DarkGray)
     };
 
     let checkbox = if *send_prompts { "[x]" } else { "[ ]" };
@@ -918,9 +1466,9 @@ fn render_configure(f: &mut Frame, area: Rect, wizard: &ReviewWizardState) {
 
     // -- AI Reviewers section --
     let ai_border = if *focus == ConfigSection::AiReviewers {
-        Style::default().fg(Color::Cyan)
+        util::fg(Color::Cyan)
     } else {
-        Style::default().fg(Color::DarkGray)
+        util::fg(Color::DarkGray)
     };
 
     let ai_items: Vec<ListItem> = wizard
@@ -928,16 +1476,13 @@ fn render_configure(f: &mut Frame, area: Rect, wizard: &ReviewWizardState) {
         .iter()
         .enumerate()
         .map(|(i, app)| {
-            let checkbox = if ai_selected[i] { "[x]" } else { "[ ]" };
-            ListItem::new(format!(" {} {}", checkbox, app.command))
+            let expanded = *focus == ConfigSection::AiReviewers && i == *ai_focused;
+            util::checkbox_item(ai_selected[i], &app.command, columns[0].width.saturating_sub(2), expanded)
         })
         .collect();
 
     let ai_highlight = if *focus == ConfigSection::AiReviewers {
-        Style::default()
-            .fg(Color::Black)
-            .bg(Color::Gray)
-            .add_modifier(Modifier::BOLD)
+        util::selected().add_modifier(Modifier::BOLD)
     } else {
         Style::default()
     };
@@ -970,9 +1515,9 @@ fn render_configure(f: &mut Frame, area: Rect, wizard: &ReviewWizardState) {
 
     // -- Meta Reviewer section --
     let meta_border = if *focus == ConfigSection::MetaReviewer {
-        Style::default().fg(Color::Cyan)
+        util::fg(Color::Cyan)
     } else {
-        Style::default().fg(Color::DarkGray)
+        util::fg(Color::DarkGray)
     };
 
     let meta_items: Vec<ListItem> = wizard
@@ -980,16 +1525,13 @@ fn render_configure(f: &mut Frame, area: Rect, wizard: &ReviewWizardState) {
         .iter()
         .enumerate()
         .map(|(i, app)| {
-            let checkbox = if meta_selected[i] { "[x]" } else { "[ ]" };
-            ListItem::new(format!(" {} {}", checkbox, app.command))
+            let expanded = *focus == ConfigSection::MetaReviewer && i == *meta_focused;
+            util::checkbox_item(meta_selected[i], &app.command, columns[1].width.saturating_sub(2), expanded)
         })
         .collect();
 
     let meta_highlight = if *focus == ConfigSection::MetaReviewer {
-        Style::default()
-            .fg(Color::Black)
-            .bg(Color::Gray)
-            .add_modifier(Modifier::BOLD)
+        util::selected().add_modifier(Modifier::BOLD)
     } else {
         Style::default()
     };
@@ -1000,7 +1542,11 @@ fn render_configure(f: &mut Frame, area: Rect, wizard: &ReviewWizardState) {
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(meta_border)
-                .title(" Meta Reviewers ")
+                .title(if wizard.no_meta {
+                    " Meta Reviewers (disabled via --no-meta) "
+                } else {
+                    " Meta Reviewers "
+                })
                 .title_bottom(" Space: toggle "),
         )
         .highlight_style(meta_highlight);
@@ -1043,7 +1589,7 @@ fn render_footer(f: &mut Frame, area: Rect, wizard: &ReviewWizardState) {
     };
 
     let footer = Paragraph::new(hints)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(util::fg(Color::DarkGray))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
 
@@ -1055,37 +1601,215 @@ fn render_footer(f: &mut Frame, area: Rect, wizard: &ReviewWizardState) {
 // ---------------------------------------------------------------------------
 
 fn generate_review_prefix(worktrees_path: &Path, source_branch: &str) -> String {
+    // Branches with slashes (feature/x) put their worktrees in nested directories
+    let scan_dir = match source_branch.rsplit_once('/') {
+        Some((parent, _)) => worktrees_path.join(parent),
+        None => worktrees_path.to_path_buf(),
+    };
+
+    // Numbering from 01 after a failed read could reuse a round that already exists
+    let names = worktree_names(&scan_dir).unwrap_or_else(|e| {
+        eprintln!(
+            "warning: could not read {} ({}); the review prefix may collide with an existing review",
+            scan_dir.display(),
+            e
+        );
+        Vec::new()
+    });
+
+    next_review_prefix(&names, source_branch)
+}
+
+/// Entry names in `dir`; a directory that doesn't exist yet has none
+fn worktree_names(dir: &Path) -> io::Result<Vec<String>> {
+    match std::fs::read_dir(dir) {
+        Ok(entries) => Ok(entries
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Returns `{source_branch}-review-NN` numbered one past the highest existing
+/// round among `names` (directory names in the branch's worktree directory).
+/// Numbers are zero-padded to at least two digits and keep growing past 99.
+fn next_review_prefix(names: &[String], source_branch: &str) -> String {
     let prefix_base = format!("{}-review", source_branch);
+    let base_name = prefix_base.rsplit('/').next().unwrap_or(&prefix_base);
+    let marker = format!("{}-", base_name);
 
-    // Scan existing directories to find the next number
-    let mut max_num = 0u32;
-    if let Ok(entries) = std::fs::read_dir(worktrees_path) {
-        for entry in entries.flatten() {
-            let name = entry.file_name().to_string_lossy().to_string();
-            // Match pattern: {source_branch}-review-NN-*
-            if let Some(rest) = name.strip_prefix(&format!("{}-", prefix_base)) {
-                // rest should start with NN-
-                if let Some(num_str) = rest.split('-').next()
-                    && let Ok(num) = num_str.parse::<u32>()
-                {
-                    max_num = max_num.max(num);
-                }
+    // Match pattern: {source_branch}-review-NN[-*]
+    let max_num = names
+        .iter()
+        .filter_map(|name| {
+            let rest = name.strip_prefix(&marker)?;
+            let num_str = rest.split('-').next()?;
+            if num_str.is_empty() || !num_str.chars().all(|c| c.is_ascii_digit()) {
+                return None;
             }
+            num_str.parse::<u32>().ok()
+        })
+        .max()
+        .unwrap_or(0);
+
+    format!("{}-{:02}", prefix_base, max_num + 1)
+}
+
+/// Extracts the `{source_branch}-review-NN` prefix from a review worktree name
+/// (`{source_branch}-review-NN-{slug}-01`), or `None` for non-review worktrees.
+fn review_prefix_of(name: &str) -> Option<&str> {
+    name.rmatch_indices("-review-").find_map(|(idx, marker)| {
+        let end = idx + marker.len();
+        let rest = &name[end..];
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        (digits > 0 && rest[digits..].starts_with('-')).then(|| &name[..end + digits])
+    })
+}
+
+/// Groups review worktrees by review prefix. `target` is either a full review
+/// prefix (`feature-review-02`) or a source branch (`feature`), which matches
+/// every review round made from that branch.
+pub fn group_review_worktrees(
+    names: &[String],
+    target: &str,
+) -> std::collections::BTreeMap<String, Vec<String>> {
+    let source_prefix = format!("{}-review-", target);
+    let mut groups: std::collections::BTreeMap<String, Vec<String>> =
+        std::collections::BTreeMap::new();
+
+    for name in names {
+        let Some(prefix) = review_prefix_of(name) else {
+            continue;
+        };
+        let matches = prefix == target
+            || prefix
+                .strip_prefix(&source_prefix)
+                .is_some_and(|num| num.chars().all(|c| c.is_ascii_digit()));
+        if matches {
+            groups
+                .entry(prefix.to_string())
+                .or_default()
+                .push(name.clone());
         }
     }
 
-    format!("{}-{:02}", prefix_base, max_num + 1)
+    for worktrees in groups.values_mut() {
+        worktrees.sort();
+    }
+    groups
 }
 
 // ---------------------------------------------------------------------------
 // Worktree creation
 // ---------------------------------------------------------------------------
 
+/// `git diff base...branch`: the branch's changes since it forked from `base`.
+fn branch_diff(project_path: &Path, base: &str, branch: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["diff", &format!("{}...{}", base, branch)])
+        .current_dir(project_path)
+        .output()
+        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to run git diff: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(MultiAiError::Review(format!(
+            "git diff {}...{} failed: {}",
+            base,
+            branch,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let diff = String::from_utf8_lossy(&output.stdout).to_string();
+    if diff.trim().is_empty() {
+        return Err(MultiAiError::Review(format!(
+            "{} has no changes against {}",
+            branch, base
+        )));
+    }
+    Ok(diff)
+}
+
+/// Stdout of `git args` run in `dir`
+fn git_output(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to run git: {}", e)))?;
+    if !output.status.success() {
+        return Err(MultiAiError::Review(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// The uncommitted changes in `dir` as one patch against HEAD: staged and unstaged
+/// edits, plus untracked files (except ignored ones) as new files
+fn working_tree_patch(dir: &Path) -> Result<String> {
+    let mut patch = git_output(dir, &["diff", "--binary", "HEAD"])?;
+
+    let untracked = git_output(dir, &["ls-files", "--others", "--exclude-standard", "-z"])?;
+    for file in untracked.split('\0').filter(|file| !file.is_empty()) {
+        // `--no-index` exits with 1 when the files differ, which they always do here
+        let output = Command::new("git")
+            .args(["diff", "--binary", "--no-index", "--", "/dev/null", file])
+            .current_dir(dir)
+            .output()
+            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to run git diff: {}", e)))?;
+        if output.status.code() != Some(1) {
+            return Err(MultiAiError::Review(format!(
+                "git diff of untracked {} failed: {}",
+                file,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        patch.push_str(&String::from_utf8_lossy(&output.stdout));
+    }
+
+    if patch.trim().is_empty() {
+        return Err(MultiAiError::Review(format!(
+            "{} has no uncommitted changes to review",
+            dir.display()
+        )));
+    }
+    Ok(patch)
+}
+
+/// Apply `patch` in `worktree` and stage it, so new files show up in `git diff --cached`
+fn apply_patch(worktree: &Path, patch: &str) -> std::result::Result<(), String> {
+    let mut child = Command::new("git")
+        .args(["apply", "--index", "--binary", "-"])
+        .current_dir(worktree)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(patch.as_bytes()).map_err(|e| e.to_string())?;
+    }
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
+fn short_commit(commit: &str) -> &str {
+    &commit[..commit.len().min(12)]
+}
+
 fn create_review_worktrees(
     worktree_manager: &WorktreeManager,
     branch_prefix: &str,
     review_apps: &[AiApp],
-    source_branch: &str,
+    checkout: &ReviewCheckout,
 ) -> Result<Vec<(AiApp, String)>> {
     let worktree_paths = Arc::new(Mutex::new(Vec::new()));
     let errors = Arc::new(Mutex::new(Vec::new()));
@@ -1098,7 +1822,7 @@ fn create_review_worktrees(
         let errors_clone = Arc::clone(&errors);
         let project_path = worktree_manager.project_path().to_path_buf();
         let wt_path = worktree_manager.worktrees_path().to_path_buf();
-        let source_branch = source_branch.to_string();
+        let checkout = checkout.clone();
 
         let handle = thread::spawn(move || {
             println!(
@@ -1110,14 +1834,39 @@ fn create_review_worktrees(
             let wm = WorktreeManager::with_worktrees_path(project_path, wt_path);
             match wm.add_worktree(&branch_name) {
                 Ok(worktree_path) => {
-                    // Reset worktree to source branch content
+                    // Reset worktree to the reviewed branch (or the base) content
+                    let reset_ref = match &checkout {
+                        ReviewCheckout::Branch(branch) => branch,
+                        ReviewCheckout::BaseWithDiff { base, .. } => base,
+                        ReviewCheckout::WorkingTree { head, .. } => head,
+                    };
                     let reset_result = Command::new("git")
-                        .args(["reset", "--hard", &source_branch])
+                        .args(["reset", "--hard", reset_ref])
                         .current_dir(&worktree_path)
                         .output();
 
                     match reset_result {
                         Ok(output) if output.status.success() => {
+                            let prepared = match &checkout {
+                                ReviewCheckout::Branch(_) => Ok(()),
+                                ReviewCheckout::BaseWithDiff { diff, .. } => {
+                                    std::fs::write(worktree_path.join(CHANGES_DIFF_FILE), diff)
+                                        .map_err(|e| format!("writing {} failed: {}", CHANGES_DIFF_FILE, e))
+                                }
+                                ReviewCheckout::WorkingTree { patch, .. } => {
+                                    apply_patch(&worktree_path, patch).map_err(|e| {
+                                        format!("applying the uncommitted changes failed: {}", e)
+                                    })
+                                }
+                            };
+                            if let Err(e) = prepared {
+                                eprintln!("  Failed to prepare worktree for {}: {}", ai_app_clone.as_str(), e);
+                                errors_clone
+                                    .lock()
+                                    .unwrap()
+                                    .push(format!("{}: {}", ai_app_clone.as_str(), e));
+                                return;
+                            }
                             println!(
                                 "  Created worktree for {}: {}",
                                 ai_app_clone.as_str(),
@@ -1201,15 +1950,6 @@ fn create_review_worktrees(
 // iTerm2 layout creation via AppleScript (single invocation)
 // ---------------------------------------------------------------------------
 
-/// Escape a string for embedding in an AppleScript double-quoted string.
-/// Newlines are replaced with `" & return & "` so multi-line text is
-/// concatenated properly instead of breaking the string literal.
-fn applescript_escape(s: &str) -> String {
-    s.replace('\\', "\\\\")
-        .replace('"', "\\\"")
-        .replace('\n', "\" & return & \"")
-}
-
 fn create_iterm2_layout_applescript(
     wizard: &ReviewWizardState,
     review_apps: &[AiApp],
@@ -1262,8 +2002,8 @@ tell application "iTerm"
     // --- Launch AI tools and shells in each column ---
     for (i, (app, path)) in worktree_paths.iter().enumerate() {
         let col_num = i + 1;
-        let escaped_path = applescript_escape(path);
-        let escaped_cmd = applescript_escape(&app.command);
+        let escaped_path = applescript::escape(&shell_quote(path));
+        let escaped_cmd = applescript::escape(app.command_for(&Mode::Iterm2));
 
         if i == 0 {
             // First column: current session is the AI pane
@@ -1314,25 +2054,34 @@ tell application "iTerm"
                 continue;
             }
             let col_num = i + 1;
-            let escaped_prompt = applescript_escape(review_prompt);
+            let escaped_prompt = applescript::escape(review_prompt);
+            // `write text` already ends with a newline; the extra blank line submits
+            // the prompt, except for agents that submit on the first Enter
+            let auto_submit = review_apps.get(i).is_some_and(|app| app.auto_submit);
             if i == 0 {
+                let submit = if auto_submit {
+                    ""
+                } else {
+                    "\n            delay 0.5\n            write text \"\""
+                };
                 script.push_str(&format!(
                     r#"
-            write text "{}"
-            delay 0.5
-            write text """#,
-                    escaped_prompt
+            write text "{}"{}"#,
+                    escaped_prompt, submit
                 ));
             } else {
+                let submit = if auto_submit {
+                    ""
+                } else {
+                    "\n                delay 0.5\n                write text \"\""
+                };
                 script.push_str(&format!(
                     r#"
             tell col{}
                 delay 1
-                write text "{}"
-                delay 0.5
-                write text ""
+                write text "{}"{}
             end tell"#,
-                    col_num, escaped_prompt
+                    col_num, escaped_prompt, submit
                 ));
             }
         }
@@ -1343,7 +2092,7 @@ tell application "iTerm"
     script.push_str(&format!(
         r#"
             set name to "{}""#,
-        applescript_escape(branch_prefix)
+        applescript::escape(branch_prefix)
     ));
 
     script.push_str(
@@ -1370,3 +2119,273 @@ end tell"#,
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn app(name: &str) -> AiApp {
+        serde_json::from_value(serde_json::json!({"name": name, "command": name})).unwrap()
+    }
+
+    #[test]
+    fn test_initial_meta_selection_respects_no_meta() {
+        let apps: Vec<AiApp> = [true, false]
+            .iter()
+            .map(|&meta_review| AiApp { meta_review, ..app("claude") })
+            .collect();
+
+        assert_eq!(initial_meta_selection(&apps, false), vec![true, false]);
+        assert_eq!(initial_meta_selection(&apps, true), vec![false, false]);
+    }
+
+    #[test]
+    fn test_worktree_names_missing_dir_vs_read_error() {
+        let dir = std::env::temp_dir().join(format!("mai-review-names-{}", std::process::id()));
+        assert_eq!(worktree_names(&dir).unwrap(), Vec::<String>::new());
+        assert_eq!(generate_review_prefix(&dir, "feature"), "feature-review-01");
+
+        std::fs::create_dir_all(dir.join("feature-review-01-claude-01")).unwrap();
+        assert_eq!(generate_review_prefix(&dir, "feature"), "feature-review-02");
+
+        // A path that exists but can't be listed is an error, not an empty directory
+        let file = dir.join("not-a-dir");
+        std::fs::write(&file, "").unwrap();
+        assert!(worktree_names(&file).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_working_tree_patch_applies_in_another_worktree() {
+        let dir = std::env::temp_dir().join(format!("mai-review-wip-{}", std::process::id()));
+        let copy = dir.with_extension("copy");
+        std::fs::create_dir_all(&dir).unwrap();
+        let git = |dir: &Path, args: &[&str]| {
+            let status = Command::new("git").args(args).current_dir(dir).output().unwrap().status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        git(&dir, &["init", "-q"]);
+        std::fs::write(dir.join("a.txt"), "one\n").unwrap();
+        git(&dir, &["add", "a.txt"]);
+        git(&dir, &["-c", "user.name=t", "-c", "user.email=t@t", "commit", "-q", "-m", "a"]);
+        assert!(working_tree_patch(&dir).is_err());
+
+        std::fs::write(dir.join("a.txt"), "two\n").unwrap();
+        std::fs::write(dir.join("new.txt"), "fresh\n").unwrap();
+        let patch = working_tree_patch(&dir).unwrap();
+
+        let head = git_output(&dir, &["rev-parse", "HEAD"]).unwrap();
+        git(&dir, &["worktree", "add", "-q", "--detach", copy.to_str().unwrap(), head.trim()]);
+        apply_patch(&copy, &patch).unwrap();
+        assert_eq!(std::fs::read_to_string(copy.join("a.txt")).unwrap(), "two\n");
+        assert_eq!(std::fs::read_to_string(copy.join("new.txt")).unwrap(), "fresh\n");
+        let staged = git_output(&copy, &["diff", "--cached", "--name-only"]).unwrap();
+        assert_eq!(staged, "a.txt\nnew.txt\n");
+
+        std::fs::remove_dir_all(&copy).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_next_review_prefix_empty() {
+        assert_eq!(next_review_prefix(&[], "feature"), "feature-review-01");
+    }
+
+    #[test]
+    fn test_next_review_prefix_sequential() {
+        let existing = names(&["feature-review-01-claude-01", "feature-review-02-codex-01"]);
+        assert_eq!(next_review_prefix(&existing, "feature"), "feature-review-03");
+    }
+
+    #[test]
+    fn test_next_review_prefix_with_gap() {
+        let existing = names(&["feature-review-01-claude-01", "feature-review-03-claude-01"]);
+        assert_eq!(next_review_prefix(&existing, "feature"), "feature-review-04");
+    }
+
+    #[test]
+    fn test_next_review_prefix_three_digits() {
+        let existing = names(&["feature-review-99-claude-01"]);
+        assert_eq!(next_review_prefix(&existing, "feature"), "feature-review-100");
+
+        let existing = names(&["feature-review-100-claude-01"]);
+        assert_eq!(next_review_prefix(&existing, "feature"), "feature-review-101");
+    }
+
+    #[test]
+    fn test_next_review_prefix_ignores_unrelated_names() {
+        let existing = names(&[
+            "main",
+            "feature-review-",
+            "feature-review-claude-01",
+            "feature-review-7x-claude-01",
+            "feature-x-review-05-claude-01",
+            "other-review-09-claude-01",
+            "feature-review-02-claude-01",
+        ]);
+        assert_eq!(next_review_prefix(&existing, "feature"), "feature-review-03");
+    }
+
+    #[test]
+    fn test_next_review_prefix_nested_branch() {
+        // Entries come from the branch's parent directory, so only the last segment is matched
+        let existing = names(&["x-review-01-claude-01"]);
+        assert_eq!(next_review_prefix(&existing, "feat/x"), "feat/x-review-02");
+    }
+
+    #[test]
+    fn test_push_recent_branch() {
+        let mut recent = names(&["a", "b", "c", "d", "e"]);
+        push_recent_branch(&mut recent, "c");
+        assert_eq!(recent, names(&["c", "a", "b", "d", "e"]));
+        push_recent_branch(&mut recent, "f");
+        assert_eq!(recent, names(&["f", "c", "a", "b", "d"]));
+    }
+
+    #[test]
+    fn test_branch_choices_pins_recent() {
+        let branches: Vec<BranchInfo> = ["main", "feature", "fix"]
+            .iter()
+            .map(|name| BranchInfo {
+                name: name.to_string(),
+                date: String::new(),
+                remote_only: false,
+            })
+            .collect();
+        let recent = names(&["fix", "deleted-branch"]);
+
+        let (choices, recent_count) = branch_choices(&branches, &recent, "");
+        assert_eq!(recent_count, 1);
+        let order: Vec<&str> = choices.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(order, vec!["fix", "main", "feature", "fix"]);
+
+        let (choices, recent_count) = branch_choices(&branches, &recent, "feat");
+        assert_eq!(recent_count, 0);
+        assert_eq!(choices.len(), 1);
+    }
+
+    #[test]
+    fn test_review_prefix_of() {
+        assert_eq!(
+            review_prefix_of("feature-review-01-claude-01"),
+            Some("feature-review-01")
+        );
+        assert_eq!(
+            review_prefix_of("feat/x-review-12-meta-codex-01"),
+            Some("feat/x-review-12")
+        );
+        assert_eq!(review_prefix_of("feature-claude"), None);
+        assert_eq!(review_prefix_of("feature-review-claude"), None);
+    }
+
+    #[test]
+    fn test_group_review_worktrees() {
+        let all = names(&[
+            "main",
+            "feature-claude",
+            "feature-review-01-claude-01",
+            "feature-review-01-meta-codex-01",
+            "feature-review-02-gemini-01",
+            "feature-x-review-01-claude-01",
+        ]);
+
+        let by_source = group_review_worktrees(&all, "feature");
+        assert_eq!(
+            by_source.keys().collect::<Vec<_>>(),
+            vec!["feature-review-01", "feature-review-02"]
+        );
+        assert_eq!(by_source["feature-review-01"].len(), 2);
+
+        let by_prefix = group_review_worktrees(&all, "feature-review-02");
+        assert_eq!(
+            by_prefix["feature-review-02"],
+            vec!["feature-review-02-gemini-01".to_string()]
+        );
+        assert_eq!(by_prefix.len(), 1);
+
+        assert!(group_review_worktrees(&all, "other").is_empty());
+    }
+
+    #[test]
+    fn test_with_review_prompt_updates_configure_step() {
+        let branches = vec![BranchInfo {
+            name: "feature".to_string(),
+            date: "1 day ago".to_string(),
+            remote_only: false,
+        }];
+        let wizard = ReviewWizardState::new(branches, Some("feature"), false, Vec::new())
+            .with_review_prompt(BASE_ONLY_REVIEW_PROMPT);
+
+        assert_eq!(wizard.review_prompt, BASE_ONLY_REVIEW_PROMPT);
+        let ReviewStep::Configure {
+            prompt_text,
+            prompt_cursor,
+            ..
+        } = &wizard.current_step
+        else {
+            panic!("expected the Configure step for an exact branch match");
+        };
+        assert_eq!(prompt_text, BASE_ONLY_REVIEW_PROMPT);
+        assert_eq!(*prompt_cursor, BASE_ONLY_REVIEW_PROMPT.len());
+    }
+
+    #[test]
+    fn test_review_manifest_resolve() {
+        let services: Vec<AiApp> = serde_json::from_str(
+            r#"[{"name": "claude", "command": "claude"}, {"name": "codex", "command": "codex"}]"#,
+        )
+        .unwrap();
+        let manifest: ReviewManifest = serde_json::from_str(
+            r#"{"reviewers": ["codex", "claude"], "meta_reviewers": ["claude"], "prompt": "Review"}"#,
+        )
+        .unwrap();
+        assert!(manifest.send_prompts);
+        assert_eq!(manifest.base, None);
+
+        let tools = manifest.resolve(&services, false).unwrap();
+        let picked: Vec<_> = tools.iter().map(|t| (t.service_index, t.tag)).collect();
+        assert!(picked == [(1, ReviewTag::Ai), (0, ReviewTag::Ai), (0, ReviewTag::Meta)]);
+        assert_eq!(manifest.resolve(&services, true).unwrap().len(), 2);
+
+        let missing = ReviewManifest {
+            reviewers: names(&["gemini"]),
+            meta_reviewers: Vec::new(),
+            prompt: "Review".to_string(),
+            send_prompts: true,
+            base: None,
+        };
+        assert!(missing.resolve(&services, false).is_err());
+    }
+
+    #[test]
+    fn test_review_manifest_quick() {
+        let services: Vec<AiApp> = serde_json::from_str(
+            r#"[{"name": "claude", "command": "claude", "default": true},
+                {"name": "gemini", "command": "gemini"},
+                {"name": "codex", "command": "codex", "default": true}]"#,
+        )
+        .unwrap();
+        let quick = |reviewers, meta: Option<&str>| QuickReview {
+            reviewers,
+            meta: meta.map(str::to_string),
+        };
+
+        let manifest = ReviewManifest::quick(&services, &quick(2, Some("gemini")), "Review").unwrap();
+        assert_eq!(manifest.reviewers, names(&["claude", "codex"]));
+        assert_eq!(manifest.meta_reviewers, names(&["gemini"]));
+        assert_eq!(manifest.prompt, "Review");
+
+        let first = ReviewManifest::quick(&services, &quick(1, None), "Review").unwrap();
+        assert_eq!(first.reviewers, names(&["claude"]));
+        assert!(first.meta_reviewers.is_empty());
+
+        assert!(ReviewManifest::quick(&services, &quick(3, None), "Review").is_err());
+        assert!(ReviewManifest::quick(&services, &quick(0, None), "Review").is_err());
+        assert!(ReviewManifest::quick(&services, &quick(1, Some("amp")), "Review").is_err());
+    }
+}
@@ -0,0 +1,199 @@
+use crate::error::{MultiAiError, Result};
+use crate::send::{get_panes, group_panes_into_columns, paste_text_to_pane};
+use crate::tail;
+use crate::tmux::TmuxManager;
+use crate::util;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long the self-test waits for the sent command's output to show up
+const OUTPUT_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a new shell gets to print its prompt; startup files can be slow
+const SHELL_TIMEOUT: Duration = Duration::from_secs(30);
+/// How long a new pane's screen has to stay the same before its shell counts as ready
+const SHELL_SETTLE: Duration = Duration::from_millis(500);
+
+/// A throwaway session and its working directory, removed when dropped so a failed
+/// step doesn't leave them behind
+struct Scratch {
+    session: String,
+    dir: PathBuf,
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = Command::new("tmux").args(["kill-session", "-t", &self.session]).output();
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Run a tmux command and return its trimmed stdout
+fn tmux(args: &[&str]) -> Result<String> {
+    let output = Command::new("tmux")
+        .args(args)
+        .output()
+        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to run tmux: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MultiAiError::Tmux(format!(
+            "tmux {} failed: {}",
+            args.first().unwrap_or(&""),
+            stderr.trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Split `target` and return the new pane's id
+fn split(target: &str, direction: &str, dir: &Path) -> Result<String> {
+    tmux(&[
+        "split-window",
+        direction,
+        "-t",
+        target,
+        "-c",
+        &dir.to_string_lossy(),
+        "-P",
+        "-F",
+        "#{pane_id}",
+    ])
+}
+
+fn passed(step: &str) {
+    println!("{}{}", util::icon("✓ "), step);
+}
+
+/// Wait until the pane shows something and then stops changing for `SHELL_SETTLE`,
+/// which is when a shell has printed its prompt. Returns how long that took.
+fn wait_for_shell(pane_id: &str) -> Result<Duration> {
+    let started = Instant::now();
+    let mut last = String::new();
+    let mut unchanged_since = Instant::now();
+    loop {
+        let content = tail::capture_visible_pane(pane_id)?;
+        if content != last {
+            last = content;
+            unchanged_since = Instant::now();
+        } else if !last.trim().is_empty() && unchanged_since.elapsed() >= SHELL_SETTLE {
+            return Ok(unchanged_since.duration_since(started));
+        }
+        if started.elapsed() > SHELL_TIMEOUT {
+            return Err(MultiAiError::Tmux(format!(
+                "The shell in {} didn't show a prompt within {}s",
+                pane_id,
+                SHELL_TIMEOUT.as_secs()
+            )));
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Check the tmux send pipeline end to end in a scratch session: build a 2x2 layout
+/// like `tmux-single-window`, read it back as columns, paste a command into a pane and
+/// wait for its output. Nothing outside the temp directory is touched.
+pub fn run_selftest() -> Result<()> {
+    if !TmuxManager::is_tmux_installed() {
+        return Err(MultiAiError::Tmux("tmux is not installed".to_string()));
+    }
+    passed(&tmux(&["-V"])?);
+
+    let scratch = Scratch {
+        session: format!("mai-selftest-{}", std::process::id()),
+        dir: std::env::temp_dir().join(format!("mai-selftest-{}", std::process::id())),
+    };
+    std::fs::create_dir_all(&scratch.dir)?;
+
+    let top_left = tmux(&[
+        "new-session",
+        "-d",
+        "-s",
+        &scratch.session,
+        "-n",
+        "apps",
+        "-x",
+        "160",
+        "-y",
+        "40",
+        "-c",
+        &scratch.dir.to_string_lossy(),
+        "-P",
+        "-F",
+        "#{pane_id}",
+    ])?;
+    let top_right = split(&top_left, "-h", &scratch.dir)?;
+    let bottom_left = split(&top_left, "-v", &scratch.dir)?;
+    let bottom_right = split(&top_right, "-v", &scratch.dir)?;
+    passed(&format!("Created scratch session '{}'", scratch.session));
+
+    let columns = group_panes_into_columns(&get_panes(&scratch.session, "apps")?);
+    let ids: Vec<Vec<&str>> = columns
+        .iter()
+        .map(|column| column.iter().map(|pane| pane.id.as_str()).collect())
+        .collect();
+    let expected = vec![
+        vec![top_left.as_str(), bottom_left.as_str()],
+        vec![top_right.as_str(), bottom_right.as_str()],
+    ];
+    if ids != expected {
+        return Err(MultiAiError::Tmux(format!(
+            "Panes were grouped as {:?}, expected {:?}",
+            ids, expected
+        )));
+    }
+    passed("Grouped 4 panes into 2 columns");
+
+    // Slow shell startup files can swallow a paste that arrives too early, as they
+    // would an app's launch command; report how long the shell took
+    let ready_after = wait_for_shell(&bottom_right)?;
+    passed(&format!(
+        "Shell in {} settled after {:.1}s (raise pane_init_delay_ms if apps miss their launch command)",
+        bottom_right,
+        ready_after.as_secs_f64()
+    ));
+
+    // The typed command has a space where the output has a dash, so finding the
+    // output means the shell ran it rather than just echoing the paste
+    paste_text_to_pane(&bottom_right, "printf '%s-%s\\n' mai-selftest ok")?;
+    passed(&format!("Pasted a command into {}", bottom_right));
+
+    let started = Instant::now();
+    loop {
+        let content = tail::capture_pane(&bottom_right)?;
+        if content.lines().any(|line| line.trim() == "mai-selftest-ok") {
+            break;
+        }
+        if started.elapsed() > OUTPUT_TIMEOUT {
+            return Err(MultiAiError::Tmux(format!(
+                "The command's output didn't appear in {} within {}s; pane shows:\n{}",
+                bottom_right,
+                OUTPUT_TIMEOUT.as_secs(),
+                content.trim_end()
+            )));
+        }
+        thread::sleep(Duration::from_millis(200));
+    }
+    passed("Captured the command's output");
+
+    drop(scratch);
+    passed("Killed the scratch session");
+    println!("\nThe tmux send pipeline works.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_selftest;
+    use crate::tmux::TmuxManager;
+
+    #[test]
+    #[ignore = "creates a real tmux session; run with `cargo test -- --ignored`"]
+    fn test_selftest_passes() {
+        if !TmuxManager::is_tmux_installed() {
+            eprintln!("tmux not installed; skipping");
+            return;
+        }
+        run_selftest().unwrap();
+    }
+}
@@ -1,5 +1,9 @@
-use crate::config::{AiApp, ProjectConfig};
+use crate::config::{AiApp, FileReferenceStyle, Mode, ProjectConfig, UltrathinkSeparator};
 use crate::error::{MultiAiError, Result};
+use crate::iterm2::{self, ITerm2Pane};
+use crate::tail;
+use crate::tmux::{PaneRole, PANE_APP_OPTION, PANE_ROLE_OPTION};
+use crate::util;
 use ratatui::crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers,
             KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags},
@@ -11,24 +15,411 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Position, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Frame, Terminal,
 };
-use std::io;
-use std::process::Command;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-#[derive(Clone, Copy, PartialEq)]
-enum TargetType {
+/// Terminal the sessions `mai send` talks to live in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Backend {
+    #[default]
+    Tmux,
+    /// Tabs created in `iterm2` mode, found through the tags on their panes
+    ITerm2,
+}
+
+impl Backend {
+    fn for_mode(mode: Option<&Mode>) -> Self {
+        match mode {
+            Some(Mode::Iterm2) => Backend::ITerm2,
+            _ => Backend::Tmux,
+        }
+    }
+
+    /// Sessions of the project, or all sessions when none match
+    fn sessions(self, project_name: &str) -> Result<Vec<String>> {
+        match self {
+            Backend::Tmux => find_active_sessions(project_name),
+            Backend::ITerm2 => Ok(iterm2_sessions(&iterm2::tagged_panes()?, project_name)),
+        }
+    }
+
+    /// Panes of `session` grouped into columns, left to right and each top to bottom
+    fn columns(self, session: &str) -> Result<Vec<Vec<TmuxPane>>> {
+        match self {
+            Backend::Tmux => Ok(group_panes_into_columns(&get_panes(session, "apps")?)),
+            Backend::ITerm2 => {
+                let columns = iterm2_columns(iterm2::tagged_panes()?, session);
+                if columns.is_empty() {
+                    return Err(MultiAiError::ITerm2(format!(
+                        "No iTerm2 panes found for {}; was its tab closed?",
+                        session
+                    )));
+                }
+                Ok(columns)
+            }
+        }
+    }
+
+    /// Type `text` into a pane and press Enter
+    fn paste(self, pane_id: &str, text: &str) -> Result<()> {
+        match self {
+            Backend::Tmux => paste_text_to_pane(pane_id, text),
+            Backend::ITerm2 => iterm2::write_text(pane_id, text),
+        }
+    }
+
+    /// Type `text` into a pane found through its column and press Enter. tmux sends
+    /// it as keys, as `mai send` always has; `paste` is for raw targets.
+    fn send_keys(self, pane_id: &str, text: &str) -> Result<()> {
+        match self {
+            Backend::Tmux => send_keys_to_pane(pane_id, text),
+            Backend::ITerm2 => iterm2::write_text(pane_id, text),
+        }
+    }
+
+    /// What a pane shows: its whole scrollback, or only the screen with `visible_only`.
+    /// iTerm2 panes only give their screen.
+    fn capture(self, pane_id: &str, visible_only: bool) -> Result<String> {
+        match self {
+            Backend::Tmux if visible_only => tail::capture_visible_pane(pane_id),
+            Backend::Tmux => tail::capture_pane(pane_id),
+            Backend::ITerm2 => iterm2::session_contents(pane_id),
+        }
+    }
+}
+
+/// Session names of tagged iTerm2 panes, like `find_active_sessions` does for tmux
+fn iterm2_sessions(panes: &[ITerm2Pane], project_name: &str) -> Vec<String> {
+    let all: BTreeSet<&str> = panes.iter().map(|pane| pane.session.as_str()).collect();
+    let matched: Vec<String> = all
+        .iter()
+        .filter(|session| session.starts_with(project_name))
+        .map(|session| session.to_string())
+        .collect();
+    if matched.is_empty() {
+        all.into_iter().map(str::to_string).collect()
+    } else {
+        matched
+    }
+}
+
+/// Columns of `session`'s tagged iTerm2 panes. A column whose panes were all closed
+/// stays as an empty gap, so the columns after it still line up with their apps.
+fn iterm2_columns(panes: Vec<ITerm2Pane>, session: &str) -> Vec<Vec<TmuxPane>> {
+    let mut by_column: BTreeMap<usize, Vec<ITerm2Pane>> = BTreeMap::new();
+    for pane in panes.into_iter().filter(|pane| pane.session == session) {
+        by_column.entry(pane.column).or_default().push(pane);
+    }
+    let column_count = by_column.keys().next_back().map_or(0, |&last| last + 1);
+    (0..column_count)
+        .map(|column| {
+            let mut panes = by_column.remove(&column).unwrap_or_default();
+            panes.sort_by_key(|pane| pane.row);
+            panes
+                .into_iter()
+                .map(|pane| TmuxPane { id: pane.id, x: pane.column, y: pane.row, role: pane.role, app: None })
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TargetType {
     Prompt,
     Command,
 }
 
+impl TargetType {
+    fn history_file_name(self) -> &'static str {
+        match self {
+            TargetType::Prompt => "send-history-prompt.json",
+            TargetType::Command => "send-history-command.json",
+        }
+    }
+}
+
+const MAX_HISTORY_ENTRIES: usize = 200;
+
+/// Sent inputs for one target type (oldest first), recalled with Ctrl+Up/Down.
+#[derive(Default)]
+struct InputHistory {
+    entries: Vec<String>,
+    // Index of the recalled entry; None while editing a fresh input
+    cursor: Option<usize>,
+    // Input that was being typed before recall started
+    draft: String,
+}
+
+impl InputHistory {
+    fn path(target_type: TargetType) -> Option<std::path::PathBuf> {
+        ProjectConfig::config_dir()
+            .ok()
+            .map(|dir| dir.join(target_type.history_file_name()))
+    }
+
+    fn load(target_type: TargetType) -> Self {
+        let entries = Self::path(target_type)
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            entries,
+            ..Self::default()
+        }
+    }
+
+    fn save(&self, target_type: TargetType) -> Result<()> {
+        let Some(path) = Self::path(target_type) else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&self.entries)?)?;
+        Ok(())
+    }
+
+    fn push(&mut self, text: &str) {
+        self.cursor = None;
+        if text.trim().is_empty() || self.entries.last().is_some_and(|last| last == text) {
+            return;
+        }
+        self.entries.push(text.to_string());
+        if self.entries.len() > MAX_HISTORY_ENTRIES {
+            let excess = self.entries.len() - MAX_HISTORY_ENTRIES;
+            self.entries.drain(..excess);
+        }
+    }
+
+    fn older(&mut self, current: &str) -> Option<&str> {
+        let index = match self.cursor {
+            None => {
+                self.draft = current.to_string();
+                self.entries.len().checked_sub(1)?
+            }
+            Some(i) => i.saturating_sub(1),
+        };
+        self.cursor = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+
+    fn newer(&mut self) -> Option<&str> {
+        let i = self.cursor?;
+        if i + 1 < self.entries.len() {
+            self.cursor = Some(i + 1);
+            self.entries.get(i + 1).map(String::as_str)
+        } else {
+            self.cursor = None;
+            Some(self.draft.as_str())
+        }
+    }
+}
+
+/// Whether a session's `apps` window has a column for every configured app.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SessionHealth {
+    Healthy,
+    MissingColumns(usize),
+    // No `apps` window (e.g. iTerm2 or multi-window layout) or tmux query failed
+    Unknown,
+}
+
+impl SessionHealth {
+    fn from_column_count(columns: usize, app_count: usize) -> Self {
+        match app_count.saturating_sub(columns) {
+            0 => SessionHealth::Healthy,
+            missing => SessionHealth::MissingColumns(missing),
+        }
+    }
+
+    fn check(backend: Backend, session: &str, app_count: usize) -> Self {
+        match backend.columns(session) {
+            Ok(columns) if !columns.is_empty() => {
+                let present = columns.iter().filter(|column| !column.is_empty()).count();
+                Self::from_column_count(present, app_count)
+            }
+            _ => SessionHealth::Unknown,
+        }
+    }
+
+    fn marker(self) -> Span<'static> {
+        match self {
+            SessionHealth::Healthy => Span::styled("✓ ", util::fg(Color::Green)),
+            SessionHealth::MissingColumns(_) | SessionHealth::Unknown => {
+                Span::styled("⚠ ", util::fg(Color::Yellow))
+            }
+        }
+    }
+
+    fn note(self) -> Option<String> {
+        match self {
+            SessionHealth::Healthy => None,
+            SessionHealth::MissingColumns(n) => Some(format!(" ({} missing)", n)),
+            SessionHealth::Unknown => Some(" (no apps window)".to_string()),
+        }
+    }
+}
+
+/// Largest file Ctrl+O will inline, so one stray pick can't flood every agent
+const MAX_INLINE_FILE_BYTES: u64 = 64 * 1024;
+
+/// Path entry for inserting a file reference into the prompt (Ctrl+O)
+struct FilePrompt {
+    path: String,
+    style: FileReferenceStyle,
+    error: Option<String>,
+}
+
+/// Text to insert for `path`: `@path`, or the file's contents fenced and labeled with
+/// the path. Relative paths are checked against the current directory, whose layout
+/// each worktree mirrors.
+fn file_reference(path: &str, style: FileReferenceStyle) -> std::result::Result<String, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("enter a path".to_string());
+    }
+    let resolved = PathBuf::from(shellexpand::tilde(path).as_ref());
+    let metadata = std::fs::metadata(&resolved).map_err(|_| format!("not found: {}", path))?;
+    // Agents won't expand `~` themselves
+    let shown = if path.starts_with('~') {
+        resolved.display().to_string()
+    } else {
+        path.to_string()
+    };
+
+    match style {
+        FileReferenceStyle::AtPath => Ok(format!("@{}", shown)),
+        FileReferenceStyle::Inline => {
+            if metadata.is_dir() {
+                return Err("can't inline a directory".to_string());
+            }
+            if metadata.len() > MAX_INLINE_FILE_BYTES {
+                return Err(format!("too large to inline (max {} KB)", MAX_INLINE_FILE_BYTES / 1024));
+            }
+            let contents = std::fs::read_to_string(&resolved)
+                .map_err(|_| format!("not a text file: {}", path))?;
+            Ok(format!("```{}\n{}\n```", shown, contents.trim_end_matches('\n')))
+        }
+    }
+}
+
+/// Diff size above which Ctrl+G asks for a second press before inserting it
+const LARGE_DIFF_BYTES: usize = 64 * 1024;
+
+/// Uncommitted changes (staged, then unstaged) in `worktree`, fenced for the prompt.
+/// Untracked files aren't part of `git diff`, so they don't show up.
+fn worktree_diff(worktree: &Path) -> std::result::Result<String, String> {
+    if !worktree.is_dir() {
+        return Err(format!("no worktree at {}", worktree.display()));
+    }
+    let mut diff = String::new();
+    for args in [&["diff", "--staged"][..], &["diff"][..]] {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(worktree)
+            .output()
+            .map_err(|e| format!("git diff failed: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "git diff failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        diff.push_str(&String::from_utf8_lossy(&output.stdout));
+    }
+    if diff.trim().is_empty() {
+        return Err(format!("no uncommitted changes in {}", worktree.display()));
+    }
+    Ok(format!("```diff\n{}\n```", diff.trim_end_matches('\n')))
+}
+
+/// Send failures kept for the Ctrl+E error log
+const MAX_ERROR_LOG_ENTRIES: usize = 10;
+
+/// Recent send failures (oldest first). The live status line clears on the next clean
+/// send; these stay, so intermittent failures can still be looked at afterwards.
+#[derive(Default)]
+struct ErrorLog {
+    // (UTC timestamp, message)
+    entries: VecDeque<(String, String)>,
+    // `<log_dir>/send-errors.log` when `log_dir` is configured
+    file: Option<PathBuf>,
+}
+
+impl ErrorLog {
+    fn push(&mut self, message: String) {
+        let timestamp = utc_timestamp(SystemTime::now());
+        if let Some(path) = &self.file
+            && let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(path)
+        {
+            let _ = writeln!(file, "{} {}", timestamp, message);
+        }
+        if self.entries.len() == MAX_ERROR_LOG_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((timestamp, message));
+    }
+}
+
+/// `YYYY-MM-DD HH:MM:SSZ` in UTC (days-to-date conversion from Howard Hinnant's
+/// `civil_from_days`)
+fn utc_timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (days, rem) = ((secs / 86400) as i64, secs % 86400);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
 #[derive(Clone, Copy, PartialEq)]
 enum FocusedWindow {
     Input,
     SessionList,
     AppList,
     Settings,
+    RawTarget,
+}
+
+/// What the last send delivered, for Ctrl+R to repeat on the next app
+#[derive(Clone)]
+struct LastSend {
+    text: String,
+    target_type: TargetType,
+    ultrathink: bool,
+}
+
+/// How often the monitor view re-captures the panes it shows
+const MONITOR_REFRESH: Duration = Duration::from_secs(1);
+
+/// Latest screen of each app's AI pane in the selected session
+#[derive(Default)]
+struct Monitor {
+    // (app name, captured screen) in display order
+    previews: Vec<(String, String)>,
+    error: Option<String>,
+    refreshed: Option<Instant>,
 }
 
 struct TuiState {
@@ -38,20 +429,65 @@ struct TuiState {
     
     sessions: Vec<String>,
     session_list_state: ListState,
+    // Checked once on load so rendering doesn't shell out to tmux every frame
+    session_health: Vec<SessionHealth>,
     
     apps: Vec<AiApp>,
     app_list_state: ListState,
+    // Indexes into `apps` in display order; Alt+Up/Down reorders the view without touching tmux
+    app_view_order: Vec<usize>,
     
     target_type: TargetType,
     ultrathink: bool,
+    ultrathink_separator: UltrathinkSeparator,
+    copy_sent_text: bool,
+    command_pane: Option<usize>,
+    // Also paste sent prompts into the command pane as shell comments, as a record
+    log_to_command_pane: bool,
     
     focused: FocusedWindow,
     confirm_clear: bool,
     settings_list_state: ListState,
+
+    // Explicit tmux target (e.g. "session:window.pane"); overrides session/app selection when set
+    raw_target: String,
+
+    // Layout from the most recent render, used for mouse hit-testing
+    layout: Option<LayoutRects>,
+
+    // Separate recall histories so shell commands and AI prompts don't mix
+    prompt_history: InputHistory,
+    command_history: InputHistory,
+
+    file_reference: FileReferenceStyle,
+    // Open while picking a file to reference; takes all keys until closed
+    file_prompt: Option<FilePrompt>,
+
+    // Where `{prefix}-{slug}` worktrees live, for Ctrl+G
+    project_name: String,
+    worktrees_path: PathBuf,
+    // Most recent successful send to a session's apps (not a raw target)
+    last_send: Option<LastSend>,
+    // Large diff waiting for a second Ctrl+G
+    pending_diff: Option<String>,
+    // One-off message in the input title; cleared by the next key
+    notice: Option<String>,
+
+    // Failure from the most recent send, shown under the input until a send succeeds
+    last_error: Option<String>,
+    error_log: ErrorLog,
+    // Ctrl+E popup listing `error_log`
+    show_error_log: bool,
+
+    // Read-only view of the selected session's panes in place of the input, while set
+    monitor: Option<Monitor>,
+    // Function key number that toggles `monitor`
+    monitor_key: u8,
+    backend: Backend,
 }
 
 impl TuiState {
-    fn new(sessions: Vec<String>, apps: Vec<AiApp>) -> Self {
+    fn new(sessions: Vec<String>, apps: Vec<AiApp>, ultrathink_separator: UltrathinkSeparator) -> Self {
         let mut session_list_state = ListState::default();
         if !sessions.is_empty() {
             session_list_state.select(Some(0));
@@ -68,34 +504,331 @@ impl TuiState {
         Self {
             input: String::new(),
             cursor_position: 0,
+            session_health: vec![SessionHealth::Unknown; sessions.len()],
             sessions,
             session_list_state,
+            app_view_order: (0..apps.len()).collect(),
             apps,
             app_list_state,
             target_type: TargetType::Prompt,
             ultrathink: false,
+            ultrathink_separator,
+            copy_sent_text: false,
+            command_pane: None,
+            log_to_command_pane: false,
             focused: FocusedWindow::Input,
             confirm_clear: false,
             settings_list_state,
+            raw_target: String::new(),
+            layout: None,
+            prompt_history: InputHistory::default(),
+            command_history: InputHistory::default(),
+            file_reference: FileReferenceStyle::default(),
+            file_prompt: None,
+            project_name: String::new(),
+            worktrees_path: PathBuf::new(),
+            last_send: None,
+            pending_diff: None,
+            notice: None,
+            last_error: None,
+            error_log: ErrorLog::default(),
+            show_error_log: false,
+            monitor: None,
+            monitor_key: 2,
+            backend: Backend::Tmux,
+        }
+    }
+
+    /// Switch between the input and the monitor view. The input can't be typed in
+    /// while it's hidden, so focus moves to the session list.
+    fn toggle_monitor(&mut self) {
+        if self.monitor.take().is_none() {
+            self.monitor = Some(Monitor::default());
+            if self.focused == FocusedWindow::Input {
+                self.focused = FocusedWindow::SessionList;
+            }
+        }
+    }
+
+    /// Re-capture the panes shown by the monitor view if `MONITOR_REFRESH` has passed
+    fn refresh_monitor(&mut self) {
+        let due = self.monitor.as_ref().is_some_and(|monitor| {
+            monitor
+                .refreshed
+                .is_none_or(|refreshed| refreshed.elapsed() >= MONITOR_REFRESH)
+        });
+        if !due {
+            return;
+        }
+        let session = self
+            .session_list_state
+            .selected()
+            .and_then(|i| self.sessions.get(i))
+            .cloned();
+        let previews = match session {
+            Some(session) => self.backend.columns(&session).map(|columns| {
+                let names = column_app_names(&columns, &self.apps);
+                self.app_view_order
+                    .iter()
+                    .filter_map(|&index| {
+                        let app = self.apps.get(index)?;
+                        let column = columns.get(app_column(&names, app)?)?;
+                        let pane = find_target_pane(TargetType::Prompt, column, None)?;
+                        let content = self.backend.capture(&pane.id, true).unwrap_or_else(|e| e.to_string());
+                        Some((app.name.clone(), content))
+                    })
+                    .collect()
+            }),
+            None => Ok(Vec::new()),
+        };
+        if let Some(monitor) = self.monitor.as_mut() {
+            monitor.refreshed = Some(Instant::now());
+            match previews {
+                Ok(previews) => {
+                    monitor.previews = previews;
+                    monitor.error = None;
+                }
+                Err(e) => monitor.error = Some(e.to_string()),
+            }
+        }
+    }
+
+    /// Report a send: how many panes got it when it went to more than one, and its
+    /// failures in the status line and error log
+    fn record_send(&mut self, outcome: SendOutcome) {
+        if outcome.targets > 1 {
+            self.notice = Some(format!("Sent to {} of {} panes", outcome.sent, outcome.targets));
+        }
+        self.record_send_failures(outcome.failures);
+    }
+
+    /// Update the status line from a send's failures and keep them in the error log
+    fn record_send_failures(&mut self, failures: Vec<String>) {
+        self.last_error = match failures.len() {
+            0 => None,
+            1 => Some(failures[0].clone()),
+            n => Some(format!("{} (and {} more)", failures[0], n - 1)),
+        };
+        for failure in failures {
+            self.error_log.push(failure);
+        }
+    }
+
+    /// Move the selected app one row up or down in the list, keeping it selected.
+    /// "All Tools" (row 0) stays pinned at the top.
+    fn move_selected_app(&mut self, up: bool) {
+        let Some(row) = self.app_list_state.selected().filter(|&row| row > 0) else {
+            return;
+        };
+        let position = row - 1;
+        let target = if up {
+            match position.checked_sub(1) {
+                Some(target) => target,
+                None => return,
+            }
+        } else if position + 1 < self.app_view_order.len() {
+            position + 1
+        } else {
+            return;
+        };
+        self.app_view_order.swap(position, target);
+        self.app_list_state.select(Some(target + 1));
+    }
+
+    fn toggle_target_type(&mut self) {
+        self.target_type = match self.target_type {
+            TargetType::Prompt => TargetType::Command,
+            TargetType::Command => TargetType::Prompt,
+        };
+    }
+
+    /// Why the Ultrathink toggle would have no effect for the current target, if it wouldn't
+    fn ultrathink_unavailable_reason(&self) -> Option<&'static str> {
+        if self.target_type == TargetType::Command {
+            return Some("command mode");
+        }
+        if !self.raw_target.trim().is_empty() {
+            return Some("raw target");
+        }
+        let has_hint = match self.app_list_state.selected() {
+            // "All Tools": applies to whichever apps have a hint
+            Some(0) | None => self.apps.iter().any(|app| app.ultrathink().is_some()),
+            Some(i) => self
+                .app_view_order
+                .get(i - 1)
+                .and_then(|&index| self.apps.get(index))
+                .is_some_and(|app| app.ultrathink().is_some()),
+        };
+        (!has_hint).then_some("no hint configured")
+    }
+
+    fn history_mut(&mut self) -> &mut InputHistory {
+        match self.target_type {
+            TargetType::Prompt => &mut self.prompt_history,
+            TargetType::Command => &mut self.command_history,
+        }
+    }
+
+    fn set_input(&mut self, text: String) {
+        self.cursor_position = text.len();
+        self.input = text;
+    }
+
+    fn recall_older(&mut self) {
+        let current = self.input.clone();
+        if let Some(text) = self.history_mut().older(&current).map(str::to_string) {
+            self.set_input(text);
+        }
+    }
+
+    fn recall_newer(&mut self) {
+        if let Some(text) = self.history_mut().newer().map(str::to_string) {
+            self.set_input(text);
         }
     }
 
+    /// Record the current input in the active target's history and persist it.
+    fn remember_sent_input(&mut self) {
+        let text = self.input.clone();
+        let target_type = self.target_type;
+        let history = self.history_mut();
+        history.push(&text);
+        // Best effort: a read-only config dir shouldn't break sending
+        let _ = history.save(target_type);
+    }
+
     fn insert_newline(&mut self) {
         self.input.insert(self.cursor_position, '\n');
         self.cursor_position += 1;
     }
 
+    fn insert_at_cursor(&mut self, text: &str) {
+        self.input.insert_str(self.cursor_position, text);
+        self.cursor_position += text.len();
+    }
+
+    fn open_file_prompt(&mut self) {
+        self.file_prompt = Some(FilePrompt {
+            path: String::new(),
+            style: self.file_reference,
+            error: None,
+        });
+    }
+
+    /// Worktree of the selected app in the selected session: `{prefix}-{slug}` under
+    /// the worktrees directory, with the prefix taken from the session name
+    fn selected_worktree(&self) -> std::result::Result<PathBuf, String> {
+        let session = self
+            .session_list_state
+            .selected()
+            .and_then(|i| self.sessions.get(i))
+            .ok_or("select a session first")?;
+        let app = match self.app_list_state.selected() {
+            Some(row) if row > 0 => self
+                .app_view_order
+                .get(row - 1)
+                .and_then(|&index| self.apps.get(index)),
+            _ => None,
+        }
+        .ok_or("select a single app; each has its own worktree")?;
+        let prefix = session
+            .strip_prefix(&format!("{}-", self.project_name))
+            .ok_or_else(|| format!("can't tell the branch prefix of {}", session))?;
+        Ok(self.worktrees_path.join(format!("{}-{}", prefix, app.slug())))
+    }
+
+    /// Ctrl+G: insert the selected app's uncommitted changes at the cursor. Diffs over
+    /// `LARGE_DIFF_BYTES` need a second press.
+    fn insert_worktree_diff(&mut self) {
+        if let Some(diff) = self.pending_diff.take() {
+            self.insert_at_cursor(&diff);
+            return;
+        }
+        match self.selected_worktree().and_then(|path| worktree_diff(&path)) {
+            Ok(diff) if diff.len() > LARGE_DIFF_BYTES => {
+                self.notice = Some(format!(
+                    "Diff is {} KB; press Ctrl+G again to insert it",
+                    diff.len() / 1024
+                ));
+                self.pending_diff = Some(diff);
+            }
+            Ok(diff) => self.insert_at_cursor(&diff),
+            Err(e) => self.notice = Some(format!("Ctrl+G: {}", e)),
+        }
+    }
+
+    /// Pane the selected app would receive a send in, following the Prompt/Command toggle
+    fn selected_pane(&self) -> std::result::Result<String, String> {
+        let session = self
+            .session_list_state
+            .selected()
+            .and_then(|i| self.sessions.get(i))
+            .ok_or("select a session first")?;
+        let app = match self.app_list_state.selected() {
+            Some(row) if row > 0 => self
+                .app_view_order
+                .get(row - 1)
+                .and_then(|&index| self.apps.get(index)),
+            _ => None,
+        }
+        .ok_or("select a single app")?;
+        let columns = self.backend.columns(session).map_err(|e| e.to_string())?;
+        app_column(&column_app_names(&columns, &self.apps), app)
+            .and_then(|column| columns.get(column))
+            .and_then(|panes| find_target_pane(self.target_type, panes, self.command_pane))
+            .map(|pane| pane.id.clone())
+            .ok_or_else(|| format!("no pane for this app in {}", session))
+    }
+
+    /// Ctrl+Y: insert what was last typed into the selected app's pane, for tweaking
+    /// and sending again
+    fn insert_last_pane_input(&mut self) {
+        let content = self
+            .selected_pane()
+            .and_then(|pane| self.backend.capture(&pane, false).map_err(|e| e.to_string()));
+        match content.map(|content| last_user_input(&content)) {
+            Ok(Some(text)) => self.insert_at_cursor(&text),
+            Ok(None) => self.notice = Some("Ctrl+Y: the pane is empty".to_string()),
+            Err(e) => self.notice = Some(format!("Ctrl+Y: {}", e)),
+        }
+    }
+
+    fn on_file_prompt_key(&mut self, key: KeyCode, modifiers: KeyModifiers) {
+        let Some(prompt) = self.file_prompt.as_mut() else {
+            return;
+        };
+        match key {
+            KeyCode::Esc => self.file_prompt = None,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => self.file_prompt = None,
+            KeyCode::Tab => prompt.style = prompt.style.toggled(),
+            KeyCode::Enter => match file_reference(&prompt.path, prompt.style) {
+                Ok(text) => {
+                    self.file_prompt = None;
+                    self.insert_at_cursor(&text);
+                }
+                Err(e) => prompt.error = Some(e),
+            },
+            KeyCode::Backspace => {
+                prompt.path.pop();
+                prompt.error = None;
+            }
+            KeyCode::Char(c) => {
+                prompt.path.push(c);
+                prompt.error = None;
+            }
+            _ => {}
+        }
+    }
+
     fn on_key(&mut self, key: KeyCode, modifiers: KeyModifiers) {
         match self.focused {
             FocusedWindow::Input => match key {
-                KeyCode::Enter => {
-                    // Only Shift+Enter creates a newline in the input field
-                    // Plain Enter and Ctrl+Enter are handled by the main loop for sending
-                    if modifiers.contains(KeyModifiers::SHIFT) {
-                        self.insert_newline();
-                    }
-                    // Otherwise, do nothing - let main loop handle sending
-                }
+                // Only Shift+Enter creates a newline in the input field
+                // Plain Enter and Ctrl+Enter are handled by the main loop for sending
+                KeyCode::Enter if modifiers.contains(KeyModifiers::SHIFT) => self.insert_newline(),
+                KeyCode::Char('o') if modifiers.contains(KeyModifiers::CONTROL) => self.open_file_prompt(),
+                KeyCode::Char('g') if modifiers.contains(KeyModifiers::CONTROL) => self.insert_worktree_diff(),
+                KeyCode::Char('y') if modifiers.contains(KeyModifiers::CONTROL) => self.insert_last_pane_input(),
                 KeyCode::Char(c) => {
                     if self.cursor_position >= self.input.len() {
                         self.input.push(c);
@@ -104,67 +837,63 @@ impl TuiState {
                     }
                     self.cursor_position += c.len_utf8();
                 }
-                KeyCode::Backspace => {
-                    if self.cursor_position > 0 {
-                        // Find char boundary
-                        let mut prev = self.cursor_position - 1;
-                        while !self.input.is_char_boundary(prev) {
-                            prev -= 1;
-                        }
-                        self.input.remove(prev);
-                        self.cursor_position = prev;
+                KeyCode::Backspace if self.cursor_position > 0 => {
+                    // Find char boundary
+                    let mut prev = self.cursor_position - 1;
+                    while !self.input.is_char_boundary(prev) {
+                        prev -= 1;
                     }
+                    self.input.remove(prev);
+                    self.cursor_position = prev;
                 }
-                KeyCode::Delete => {
-                    if self.cursor_position < self.input.len() {
-                        self.input.remove(self.cursor_position);
-                    }
+                KeyCode::Delete if self.cursor_position < self.input.len() => {
+                    self.input.remove(self.cursor_position);
                 }
-                KeyCode::Left => {
-                    if self.cursor_position > 0 {
-                        let mut prev = self.cursor_position - 1;
-                        while !self.input.is_char_boundary(prev) {
-                            prev -= 1;
-                        }
-                        self.cursor_position = prev;
+                KeyCode::Left if self.cursor_position > 0 => {
+                    let mut prev = self.cursor_position - 1;
+                    while !self.input.is_char_boundary(prev) {
+                        prev -= 1;
                     }
+                    self.cursor_position = prev;
                 }
-                KeyCode::Right => {
-                    if self.cursor_position < self.input.len() {
-                        let mut next = self.cursor_position + 1;
-                        while next < self.input.len() && !self.input.is_char_boundary(next) {
-                            next += 1;
-                        }
-                        self.cursor_position = next;
+                KeyCode::Right if self.cursor_position < self.input.len() => {
+                    let mut next = self.cursor_position + 1;
+                    while next < self.input.len() && !self.input.is_char_boundary(next) {
+                        next += 1;
                     }
+                    self.cursor_position = next;
                 }
+                KeyCode::Up if modifiers.contains(KeyModifiers::CONTROL) => self.recall_older(),
+                KeyCode::Down if modifiers.contains(KeyModifiers::CONTROL) => self.recall_newer(),
                 KeyCode::Tab => self.focused = FocusedWindow::SessionList,
                 _ => {}
             },
             FocusedWindow::SessionList => match key {
                 KeyCode::Up => {
-                    if let Some(selected) = self.session_list_state.selected() {
-                        if selected > 0 {
-                            self.session_list_state.select(Some(selected - 1));
-                        }
+                    if let Some(selected) = self.session_list_state.selected()
+                        && selected > 0
+                    {
+                        self.session_list_state.select(Some(selected - 1));
                     }
                 }
                 KeyCode::Down => {
-                    if let Some(selected) = self.session_list_state.selected() {
-                        if selected < self.sessions.len() - 1 {
-                            self.session_list_state.select(Some(selected + 1));
-                        }
+                    if let Some(selected) = self.session_list_state.selected()
+                        && selected < self.sessions.len() - 1
+                    {
+                        self.session_list_state.select(Some(selected + 1));
                     }
                 }
                 KeyCode::Tab => self.focused = FocusedWindow::AppList,
                 _ => {}
             },
             FocusedWindow::AppList => match key {
+                KeyCode::Up if modifiers.contains(KeyModifiers::ALT) => self.move_selected_app(true),
+                KeyCode::Down if modifiers.contains(KeyModifiers::ALT) => self.move_selected_app(false),
                 KeyCode::Up => {
-                    if let Some(selected) = self.app_list_state.selected() {
-                        if selected > 0 {
-                            self.app_list_state.select(Some(selected - 1));
-                        }
+                    if let Some(selected) = self.app_list_state.selected()
+                        && selected > 0
+                    {
+                        self.app_list_state.select(Some(selected - 1));
                     }
                 }
                 KeyCode::Down => {
@@ -182,7 +911,7 @@ impl TuiState {
                 KeyCode::Up => {
                     if let Some(selected) = self.settings_list_state.selected() {
                          let new_selected = if selected == 0 {
-                             2 // Loop to last item
+                             3 // Loop to last item
                          } else {
                              selected - 1
                          };
@@ -191,7 +920,7 @@ impl TuiState {
                 }
                 KeyCode::Down => {
                     if let Some(selected) = self.settings_list_state.selected() {
-                        let new_selected = if selected >= 2 {
+                        let new_selected = if selected >= 3 {
                             0 // Loop to first item
                         } else {
                             selected + 1
@@ -205,36 +934,88 @@ impl TuiState {
                              0 => self.target_type = TargetType::Prompt,
                              1 => self.target_type = TargetType::Command,
                              2 => self.ultrathink = !self.ultrathink,
+                             3 => self.log_to_command_pane = !self.log_to_command_pane,
                              _ => {}
                          }
                      }
                 }
+                KeyCode::Tab => self.focused = FocusedWindow::RawTarget,
+                _ => {}
+            },
+            FocusedWindow::RawTarget => match key {
+                KeyCode::Char(c) => self.raw_target.push(c),
+                KeyCode::Backspace => {
+                    self.raw_target.pop();
+                }
                 KeyCode::Tab => self.focused = FocusedWindow::Input,
                 _ => {}
-            }
+            },
         }
     }
 
     fn create_send_action(&self) -> Option<SendAction> {
-        if let Some(session_idx) = self.session_list_state.selected() {
-            if let Some(list_idx) = self.app_list_state.selected() {
-                let app_index = if list_idx == 0 {
-                    None
-                } else {
-                    Some(list_idx - 1)
-                };
-
-                return Some(SendAction {
-                    session_name: self.sessions[session_idx].clone(),
-                    app_index,
-                    target_type: self.target_type,
-                    text: self.input.clone(),
-                    ultrathink: self.ultrathink,
-                    apps: self.apps.clone(),
-                });
-            }
+        let raw_target = self.raw_target.trim();
+        if !raw_target.is_empty() {
+            return Some(SendAction {
+                session_name: String::new(),
+                app_index: None,
+                target_type: self.target_type,
+                text: self.input.clone(),
+                ultrathink: false,
+                ultrathink_separator: self.ultrathink_separator,
+                apps: Vec::new(),
+                raw_target: Some(raw_target.to_string()),
+                copy_sent_text: self.copy_sent_text,
+                command_pane: self.command_pane,
+                log_to_command_pane: false,
+                backend: self.backend,
+            });
+        }
+
+        self.app_send_action(LastSend {
+            text: self.input.clone(),
+            target_type: self.target_type,
+            ultrathink: self.ultrathink,
+        })
+    }
+
+    /// `payload` for the selected session and app row ("All Tools" is row 0)
+    fn app_send_action(&self, payload: LastSend) -> Option<SendAction> {
+        let session_idx = self.session_list_state.selected()?;
+        let list_idx = self.app_list_state.selected()?;
+        let app_index = if list_idx == 0 {
+            None
+        } else {
+            self.app_view_order.get(list_idx - 1).copied()
+        };
+
+        Some(SendAction {
+            session_name: self.sessions[session_idx].clone(),
+            app_index,
+            target_type: payload.target_type,
+            text: payload.text,
+            ultrathink: payload.ultrathink,
+            ultrathink_separator: self.ultrathink_separator,
+            apps: self.apps.clone(),
+            raw_target: None,
+            copy_sent_text: self.copy_sent_text,
+            command_pane: self.command_pane,
+            log_to_command_pane: self.log_to_command_pane,
+            backend: self.backend,
+        })
+    }
+
+    /// Ctrl+R: select the next app (wrapping, skipping "All Tools") and send it the
+    /// last payload again
+    fn repeat_to_next_app(&mut self) -> Option<SendAction> {
+        let payload = self.last_send.clone()?;
+        if self.app_view_order.is_empty() {
+            return None;
         }
-        None
+        let row = self.app_list_state.selected().unwrap_or(0);
+        let next = if row >= self.app_view_order.len() { 1 } else { row + 1 };
+        self.app_list_state.select(Some(next));
+        self.app_send_action(payload)
     }
 
     // Handling mouse clicks (simplified)
@@ -249,15 +1030,19 @@ impl TuiState {
             self.focused = FocusedWindow::AppList;
         } else if rects.settings.contains(position) {
             self.focused = FocusedWindow::Settings;
+        } else if rects.raw_target.contains(position) {
+            self.focused = FocusedWindow::RawTarget;
         }
     }
 }
 
+#[derive(Clone, Copy)]
 struct LayoutRects {
     input: Rect,
     sessions: Rect,
     apps: Rect,
     settings: Rect,
+    raw_target: Rect,
 }
 
 impl LayoutRects {
@@ -266,11 +1051,24 @@ impl LayoutRects {
 
 // Removed RectExt impl
 
-pub fn run_send_command(project_config: ProjectConfig, project_name: String) -> Result<()> {
+pub fn run_send_command(
+    project_config: ProjectConfig,
+    project_name: String,
+    worktrees_path: PathBuf,
+) -> Result<()> {
     // 1. Find active sessions matching the project
-    let sessions = find_active_sessions(&project_name)?;
+    let send_backend = Backend::for_mode(project_config.mode.as_ref());
+    let sessions = send_backend.sessions(&project_name)?;
+    if send_backend == Backend::Tmux {
+        prune_stale_send_buffers();
+    }
     if sessions.is_empty() {
-        return Err(MultiAiError::Tmux("No active sessions found for this project".to_string()));
+        return Err(match send_backend {
+            Backend::Tmux => MultiAiError::Tmux("No active sessions found for this project".to_string()),
+            Backend::ITerm2 => MultiAiError::ITerm2(
+                "No iTerm2 tabs created by mai found for this project".to_string(),
+            ),
+        });
     }
 
     // 2. Setup terminal
@@ -299,7 +1097,29 @@ pub fn run_send_command(project_config: ProjectConfig, project_name: String) ->
         .map_err(|e| MultiAiError::CommandFailed(format!("Failed to create terminal: {}", e)))?;
 
     // 3. Create state
-    let mut state = TuiState::new(sessions, project_config.ai_apps.clone());
+    let mut state = TuiState::new(
+        sessions,
+        project_config.ai_apps.clone(),
+        project_config.ultrathink_separator,
+    );
+    state.session_health = state
+        .sessions
+        .iter()
+        .map(|session| SessionHealth::check(send_backend, session, state.apps.len()))
+        .collect();
+    state.backend = send_backend;
+    state.copy_sent_text = project_config.copy_sent_text;
+    state.command_pane = project_config.command_pane;
+    state.file_reference = project_config.file_reference;
+    state.monitor_key = project_config.monitor_key();
+    state.project_name = project_name;
+    state.worktrees_path = worktrees_path;
+    state.error_log.file = project_config
+        .log_dir
+        .as_deref()
+        .map(|dir| PathBuf::from(shellexpand::tilde(dir).as_ref()).join("send-errors.log"));
+    state.prompt_history = InputHistory::load(TargetType::Prompt);
+    state.command_history = InputHistory::load(TargetType::Command);
 
     // 4. Run loop (sends are executed inside the loop now)
     let result = run_app(&mut terminal, &mut state);
@@ -329,18 +1149,24 @@ pub fn run_send_command(project_config: ProjectConfig, project_name: String) ->
 
 struct SendAction {
     session_name: String,
-    app_index: Option<usize>, // None means All
+    app_index: Option<usize>, // Index into `apps`; None means All
     target_type: TargetType,
     text: String,
     ultrathink: bool,
+    ultrathink_separator: UltrathinkSeparator,
     apps: Vec<AiApp>,
+    raw_target: Option<String>, // Explicit tmux target; bypasses column mapping
+    copy_sent_text: bool,
+    command_pane: Option<usize>,
+    log_to_command_pane: bool,
+    backend: Backend,
 }
 
 fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, state: &mut TuiState) -> Result<()> {
     loop {
-        let _layout_rects = terminal.draw(|f| ui(f, state))
-            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to draw TUI: {}", e)))?
-            .clone(); // Recalculate on click instead
+        state.refresh_monitor();
+        terminal.draw(|f| ui(f, state))
+            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to draw TUI: {}", e)))?;
 
         // Handle events
         if event::poll(std::time::Duration::from_millis(100)).map_err(|e| MultiAiError::CommandFailed(format!("Poll error: {}", e)))? {
@@ -352,6 +1178,25 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, state: &mut Tu
                         continue;
                     }
 
+                    if state.file_prompt.is_some() {
+                        state.on_file_prompt_key(key.code, key.modifiers);
+                        continue;
+                    }
+
+                    // Ctrl+E opens the error log; any key closes it
+                    if state.show_error_log {
+                        state.show_error_log = false;
+                        continue;
+                    }
+                    if key.code == KeyCode::Char('e') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                        state.show_error_log = true;
+                        continue;
+                    }
+                    if key.code == KeyCode::F(state.monitor_key) {
+                        state.toggle_monitor();
+                        continue;
+                    }
+
                     // Ctrl+C handling
                     if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
                         if state.focused == FocusedWindow::Input && !state.input.is_empty() {
@@ -373,11 +1218,42 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, state: &mut Tu
                     if state.confirm_clear {
                         state.confirm_clear = false;
                     }
+                    let is_ctrl_g = key.code == KeyCode::Char('g') && key.modifiers.contains(KeyModifiers::CONTROL);
+                    if !is_ctrl_g {
+                        state.pending_diff = None;
+                    }
+                    state.notice = None;
+
+                    // Ctrl+R repeats the last send on the next app, from any window
+                    if key.code == KeyCode::Char('r') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                        match state.repeat_to_next_app() {
+                            Some(action) => {
+                                let outcome = execute_send_action(action)
+                                    .unwrap_or_else(|e| SendOutcome::failed(format!("Failed to send: {}", e)));
+                                state.record_send(outcome);
+                            }
+                            None => state.notice = Some("Ctrl+R: nothing sent to an app yet".to_string()),
+                        }
+                        continue;
+                    }
+
+                    // Ctrl+Tab flips Prompt/Command from any window, keeping focus where it is
+                    // (needs a terminal that reports modifiers on Tab)
+                    if key.code == KeyCode::Tab && key.modifiers.contains(KeyModifiers::CONTROL) {
+                        state.toggle_target_type();
+                        continue;
+                    }
+
+                    // The input is hidden behind the monitor view; only Tab leaves it
+                    if state.monitor.is_some() && state.focused == FocusedWindow::Input && key.code != KeyCode::Tab {
+                        continue;
+                    }
                     
-                    if state.focused != FocusedWindow::Input {
-                         if key.code == KeyCode::Char('q') {
-                             return Ok(());
-                         }
+                    if state.focused != FocusedWindow::Input
+                        && state.focused != FocusedWindow::RawTarget
+                        && key.code == KeyCode::Char('q')
+                    {
+                        return Ok(());
                     }
 
                     // Handle Shift+Enter (and common fallbacks) as newline insertion before send logic
@@ -401,11 +1277,24 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, state: &mut Tu
                         // Shift+Enter is handled by on_key() to insert newline
                         if !key.modifiers.contains(KeyModifiers::SHIFT) {
                             if let Some(action) = state.create_send_action() {
+                                let payload = action.raw_target.is_none().then(|| LastSend {
+                                    text: action.text.clone(),
+                                    target_type: action.target_type,
+                                    ultrathink: action.ultrathink,
+                                });
                                 // Execute send immediately without exiting TUI
-                                if let Err(e) = execute_send_action(action) {
+                                let outcome = match execute_send_action(action) {
+                                    Ok(outcome) => {
+                                        state.remember_sent_input();
+                                        if payload.is_some() {
+                                            state.last_send = payload;
+                                        }
+                                        outcome
+                                    }
                                     // On error, continue running TUI (user can try again)
-                                    eprintln!("Failed to send: {}", e);
-                                }
+                                    Err(e) => SendOutcome::failed(format!("Failed to send: {}", e)),
+                                };
+                                state.record_send(outcome);
                                 // Text stays in input field, TUI stays open for more messages
                             }
                             // Don't pass Enter to on_key() to avoid inserting newline
@@ -416,14 +1305,18 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, state: &mut Tu
                     state.on_key(key.code, key.modifiers);
                 }
                 Event::Mouse(mouse) => {
-                    if mouse.kind == event::MouseEventKind::Down(event::MouseButton::Left) {
-                        let size = terminal.size()
-                            .map(|s| Rect::from((Position::default(), s)))
-                            .unwrap_or(Rect::default());
-                        let rects = calculate_layout(size);
+                    if mouse.kind == event::MouseEventKind::Down(event::MouseButton::Left)
+                        && let Some(rects) = state.layout
+                    {
                         state.on_click(mouse.column, mouse.row, &rects);
                     }
                 }
+                Event::Resize(_, _) => {
+                    // Re-render right away so the cached layout matches the new size
+                    // before any queued mouse click is hit-tested against it
+                    terminal.draw(|f| ui(f, state))
+                        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to draw TUI: {}", e)))?;
+                }
                 _ => {}
             }
         }
@@ -452,10 +1345,18 @@ fn calculate_layout(area: Rect) -> LayoutRects {
         .split(bottom_area);
 
     let left_col = bottom_cols[0];
-    let right_col = bottom_cols[1];
 
-    // Split left column into Sessions (top) and Apps (bottom)
-    let left_rows = Layout::default()
+    // Split right column into Settings (top) and Raw Target input (bottom)
+    let right_rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Settings
+            Constraint::Length(3), // Raw target
+        ].as_ref())
+        .split(bottom_cols[1]);
+
+    // Split left column into Sessions (top) and Apps (bottom)
+    let left_rows = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Percentage(50), // Sessions
@@ -467,64 +1368,96 @@ fn calculate_layout(area: Rect) -> LayoutRects {
         input: input_area,
         sessions: left_rows[0],
         apps: left_rows[1],
-        settings: right_col,
+        settings: right_rows[0],
+        raw_target: right_rows[1],
     }
 }
 
 fn ui(f: &mut Frame, state: &mut TuiState) {
     let rects = calculate_layout(f.area());
+    state.layout = Some(rects);
 
-    // Input Area
-    let input_title = if state.confirm_clear {
-        " Input (Press Ctrl+C again to clear) "
+    // Input Area, or the monitor view in its place
+    if let Some(monitor) = &state.monitor {
+        let session = state
+            .session_list_state
+            .selected()
+            .and_then(|i| state.sessions.get(i))
+            .map_or("", String::as_str);
+        let focused = state.focused == FocusedWindow::Input;
+        draw_monitor(f, monitor, session, state.monitor_key, focused, rects.input);
     } else {
-        " Input (Enter to Send, Shift+Enter for newline) "
-    };
-    
-    let input_block = Block::default()
-        .borders(Borders::ALL)
-        .title(input_title)
-        .border_style(if state.focused == FocusedWindow::Input { 
-            if state.confirm_clear { Style::default().fg(Color::Red).add_modifier(Modifier::BOLD) }
-            else { Style::default().fg(Color::Green).add_modifier(Modifier::BOLD) }
-        } else { Style::default() });
-    
-    // Handle cursor position logic for multiple lines (not implemented in Paragraph directly)
-    // For simplicity, we'll stick with basic rendering but we could add a block cursor character
-    // at the cursor position if we wanted to be fancy, but terminal usually handles it if we set cursor position.
-    
-    let input_text = Paragraph::new(state.input.as_str())
-        .block(input_block)
-        .wrap(Wrap { trim: false });
-    f.render_widget(input_text, rects.input);
-    
-    // Set cursor position
-    if state.focused == FocusedWindow::Input {
-        // We need to calculate the screen coordinates of the cursor.
-        // This is tricky with wrapping.
-        // For now, let's assume no wrapping or handle simple cases.
-        // A better way is to let the user rely on the blinking block cursor if we can position it correctly.
-        // But ratatui doesn't easily give us the layout of the text inside the paragraph.
-        
-        // Let's try a simple approach: Count newlines up to cursor_position.
-        let (cursor_x, cursor_y) = calculate_cursor_pos(&state.input, state.cursor_position, rects.input.width - 2); // -2 for borders
-        
-        f.set_cursor_position(Position::new(
-            rects.input.x + 1 + cursor_x,
-            rects.input.y + 1 + cursor_y,
-        ));
+        let input_title = if state.confirm_clear {
+            " Input (Press Ctrl+C again to clear) ".to_string()
+        } else if let Some(notice) = &state.notice {
+            format!(" Input ({}) ", notice)
+        } else {
+            format!(
+                " Input (Enter to Send, Shift+Enter for newline, Ctrl+O: insert file, Ctrl+G: insert diff, Ctrl+Y: last input, F{}: monitor) ",
+                state.monitor_key
+            )
+        };
+
+        let mut input_block = Block::default()
+            .borders(Borders::ALL)
+            .title(input_title)
+            .border_style(if state.focused == FocusedWindow::Input { 
+                if state.confirm_clear { util::fg(Color::Red).add_modifier(Modifier::BOLD) }
+                else if state.notice.is_some() { util::fg(Color::Yellow).add_modifier(Modifier::BOLD) }
+                else { util::fg(Color::Green).add_modifier(Modifier::BOLD) }
+            } else { Style::default() });
+
+        if let Some(error) = &state.last_error {
+            input_block = input_block.title_bottom(Line::styled(
+                format!(" {} (Ctrl+E: error log) ", error),
+                util::fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        // Handle cursor position logic for multiple lines (not implemented in Paragraph directly)
+        // For simplicity, we'll stick with basic rendering but we could add a block cursor character
+        // at the cursor position if we wanted to be fancy, but terminal usually handles it if we set cursor position.
+
+        let input_text = Paragraph::new(state.input.as_str())
+            .block(input_block)
+            .wrap(Wrap { trim: false });
+        f.render_widget(input_text, rects.input);
+
+        // Set cursor position
+        if state.focused == FocusedWindow::Input {
+            // We need to calculate the screen coordinates of the cursor.
+            // This is tricky with wrapping.
+            // For now, let's assume no wrapping or handle simple cases.
+            // A better way is to let the user rely on the blinking block cursor if we can position it correctly.
+            // But ratatui doesn't easily give us the layout of the text inside the paragraph.
+
+            // Let's try a simple approach: Count newlines up to cursor_position.
+            let (cursor_x, cursor_y) = calculate_cursor_pos(&state.input, state.cursor_position, rects.input.width - 2); // -2 for borders
+
+            f.set_cursor_position(Position::new(
+                rects.input.x + 1 + cursor_x,
+                rects.input.y + 1 + cursor_y,
+            ));
+        }
     }
 
     // Sessions List
     let sessions_items: Vec<ListItem> = state.sessions
         .iter()
-        .map(|s| ListItem::new(Line::from(s.as_str())))
+        .zip(&state.session_health)
+        .map(|(s, health)| {
+            let mut spans = vec![health.marker(), Span::raw(s.as_str())];
+            if let Some(note) = health.note() {
+                spans.push(Span::styled(note, util::fg(Color::DarkGray)));
+            }
+            ListItem::new(Line::from(spans))
+        })
         .collect();
     
     let sessions_list = List::new(sessions_items)
         .block(Block::default().borders(Borders::ALL).title(" Sessions ")
-        .border_style(if state.focused == FocusedWindow::SessionList { Style::default().fg(Color::Green).add_modifier(Modifier::BOLD) } else { Style::default() }))
-        .highlight_style(Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))
+        .border_style(if state.focused == FocusedWindow::SessionList { util::fg(Color::Green).add_modifier(Modifier::BOLD) } else { Style::default() }))
+        .highlight_style(util::fg(Color::Cyan).add_modifier(Modifier::BOLD))
         .highlight_symbol(">> ");
     f.render_stateful_widget(sessions_list, rects.sessions, &mut state.session_list_state);
 
@@ -532,57 +1465,202 @@ fn ui(f: &mut Frame, state: &mut TuiState) {
     let mut apps_items = vec![
         ListItem::new(Line::from("All Tools")).style(Style::default().add_modifier(Modifier::BOLD))
     ];
-    apps_items.extend(state.apps
+    apps_items.extend(state.app_view_order
         .iter()
+        .filter_map(|&index| state.apps.get(index))
         .map(|a| ListItem::new(Line::from(a.name.as_str()))));
 
     let apps_list = List::new(apps_items)
-        .block(Block::default().borders(Borders::ALL).title(" Target App (Column) ")
-        .border_style(if state.focused == FocusedWindow::AppList { Style::default().fg(Color::Green).add_modifier(Modifier::BOLD) } else { Style::default() }))
-        .highlight_style(Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))
+        .block(Block::default().borders(Borders::ALL).title(" Target App (Column, Alt+↑/↓ to reorder) ")
+        .border_style(if state.focused == FocusedWindow::AppList { util::fg(Color::Green).add_modifier(Modifier::BOLD) } else { Style::default() }))
+        .highlight_style(util::fg(Color::Cyan).add_modifier(Modifier::BOLD))
         .highlight_symbol(">> ");
     f.render_stateful_widget(apps_list, rects.apps, &mut state.app_list_state);
 
     // Settings
     let settings_items = vec![
         ListItem::new(Line::from(vec![
-            Span::styled(if state.target_type == TargetType::Prompt { " (•) " } else { " ( ) " }, Style::default().fg(Color::Cyan)),
+            Span::styled(if state.target_type == TargetType::Prompt { " (•) " } else { " ( ) " }, util::fg(Color::Cyan)),
             Span::raw("Target: Prompt (Top Pane)"),
         ])),
         ListItem::new(Line::from(vec![
-            Span::styled(if state.target_type == TargetType::Command { " (•) " } else { " ( ) " }, Style::default().fg(Color::Cyan)),
-            Span::raw("Target: Command (Bottom Pane)"),
-        ])),
-        ListItem::new(Line::from(vec![
-            Span::styled(if state.ultrathink { " [x] " } else { " [ ] " }, Style::default().fg(Color::Cyan)),
-            Span::raw("Ultrathink"),
+            Span::styled(if state.target_type == TargetType::Command { " (•) " } else { " ( ) " }, util::fg(Color::Cyan)),
+            Span::raw(match state.command_pane {
+                Some(index) => format!("Target: Command (Pane {})", index + 1),
+                None => "Target: Command (Bottom Pane)".to_string(),
+            }),
         ])),
+        match state.ultrathink_unavailable_reason() {
+            None => ListItem::new(Line::from(vec![
+                Span::styled(if state.ultrathink { " [x] " } else { " [ ] " }, util::fg(Color::Cyan)),
+                Span::raw("Ultrathink"),
+            ])),
+            Some(reason) => ListItem::new(Line::from(vec![
+                Span::raw(if state.ultrathink { " [x] " } else { " [ ] " }),
+                Span::raw(format!("Ultrathink ({})", reason)),
+            ]))
+            .style(util::fg(Color::DarkGray)),
+        },
+        if state.target_type == TargetType::Prompt && state.raw_target.trim().is_empty() {
+            ListItem::new(Line::from(vec![
+                Span::styled(if state.log_to_command_pane { " [x] " } else { " [ ] " }, util::fg(Color::Cyan)),
+                Span::raw("Also log prompt to command pane"),
+            ]))
+        } else {
+            ListItem::new(Line::from(vec![
+                Span::raw(if state.log_to_command_pane { " [x] " } else { " [ ] " }),
+                Span::raw("Also log prompt to command pane (prompt target only)"),
+            ]))
+            .style(util::fg(Color::DarkGray))
+        },
     ];
 
     let settings_list = List::new(settings_items)
         .block(Block::default().borders(Borders::ALL).title(" Settings (Space to toggle) ")
-        .border_style(if state.focused == FocusedWindow::Settings { Style::default().fg(Color::Green).add_modifier(Modifier::BOLD) } else { Style::default() }))
-        .highlight_style(Style::default().add_modifier(Modifier::BOLD).bg(Color::DarkGray))
+        .border_style(if state.focused == FocusedWindow::Settings { util::fg(Color::Green).add_modifier(Modifier::BOLD) } else { Style::default() }))
+        .highlight_style(util::bg(Color::DarkGray).add_modifier(Modifier::BOLD))
         .highlight_symbol("> ");
     f.render_stateful_widget(settings_list, rects.settings, &mut state.settings_list_state);
+
+    // Raw tmux target
+    let raw_target_title = if state.raw_target.trim().is_empty() {
+        " Raw Target (optional, e.g. session:window.pane) "
+    } else {
+        " Raw Target (overrides session/app) "
+    };
+    let raw_target_input = Paragraph::new(state.raw_target.as_str())
+        .block(Block::default().borders(Borders::ALL).title(raw_target_title)
+        .border_style(if state.focused == FocusedWindow::RawTarget { util::fg(Color::Green).add_modifier(Modifier::BOLD) } else { Style::default() }));
+    f.render_widget(raw_target_input, rects.raw_target);
+
+    if state.focused == FocusedWindow::RawTarget {
+        let cursor_x = (rects.raw_target.x + 1 + state.raw_target.chars().count() as u16)
+            .min(rects.raw_target.x + rects.raw_target.width.saturating_sub(2));
+        f.set_cursor_position(Position::new(cursor_x, rects.raw_target.y + 1));
+    }
+
+    // File reference prompt, drawn over the bottom of the input area
+    if let Some(prompt) = &state.file_prompt {
+        let area = Rect {
+            x: rects.input.x + 2,
+            y: (rects.input.y + rects.input.height).saturating_sub(4).max(rects.input.y),
+            width: rects.input.width.saturating_sub(4),
+            height: 3.min(rects.input.height),
+        };
+        let title = match (&prompt.error, prompt.style) {
+            (Some(error), _) => format!(" Insert file: {} ", error),
+            (None, FileReferenceStyle::AtPath) => {
+                " Insert file as @path (Tab: inline contents, Esc: cancel) ".to_string()
+            }
+            (None, FileReferenceStyle::Inline) => {
+                " Insert file contents (Tab: @path, Esc: cancel) ".to_string()
+            }
+        };
+        let border_style = if prompt.error.is_some() {
+            util::fg(Color::Red).add_modifier(Modifier::BOLD)
+        } else {
+            util::fg(Color::Yellow).add_modifier(Modifier::BOLD)
+        };
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(prompt.path.as_str())
+                .block(Block::default().borders(Borders::ALL).title(title).border_style(border_style)),
+            area,
+        );
+        let cursor_x = (area.x + 1 + prompt.path.chars().count() as u16)
+            .min(area.x + area.width.saturating_sub(2));
+        f.set_cursor_position(Position::new(cursor_x, area.y + 1));
+    }
+
+    if state.show_error_log {
+        let area = f.area();
+        let height = (MAX_ERROR_LOG_ENTRIES as u16 + 2).min(area.height);
+        let area = Rect {
+            x: area.x + 2,
+            y: area.y + (area.height - height) / 2,
+            width: area.width.saturating_sub(4),
+            height,
+        };
+        let lines: Vec<Line> = if state.error_log.entries.is_empty() {
+            vec![Line::styled("No send errors", util::fg(Color::DarkGray))]
+        } else {
+            // Newest first
+            state.error_log.entries.iter().rev().map(|(timestamp, message)| {
+                Line::from(vec![
+                    Span::styled(format!("{} ", timestamp), util::fg(Color::DarkGray)),
+                    Span::raw(message.as_str()),
+                ])
+            }).collect()
+        };
+        let title = match &state.error_log.file {
+            Some(path) => format!(" Send errors (also in {}) ", path.display()),
+            None => " Send errors ".to_string(),
+        };
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_bottom(" any key to close ")
+                    .border_style(util::fg(Color::Red).add_modifier(Modifier::BOLD)),
+            ),
+            area,
+        );
+    }
 }
 
-fn find_active_sessions(project_name: &str) -> Result<Vec<String>> {
-    let output = Command::new("tmux")
-        .args(["list-sessions", "-F", "#{session_name}"])
-        .output()
-        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to list sessions: {}", e)))?;
+/// Side-by-side previews of each app's AI pane, showing the bottom of each screen
+fn draw_monitor(f: &mut Frame, monitor: &Monitor, session: &str, key: u8, focused: bool, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Monitor: {} (read-only, F{}: back to input) ", session, key))
+        .border_style(if focused { util::fg(Color::Green).add_modifier(Modifier::BOLD) } else { Style::default() });
+    let inner = block.inner(area);
+    f.render_widget(block, area);
 
-    if !output.status.success() {
-        // It's possible no sessions exist
-        return Ok(vec![]);
+    let message = match &monitor.error {
+        Some(error) => Some(error.as_str()),
+        None if monitor.refreshed.is_none() => Some("Capturing panes..."),
+        None if monitor.previews.is_empty() => Some("No app panes in this session"),
+        None => None,
+    };
+    if let Some(message) = message {
+        f.render_widget(Paragraph::new(message).style(util::fg(Color::DarkGray)), inner);
+        return;
     }
 
-    let output_str = String::from_utf8_lossy(&output.stdout);
-    let all_sessions: Vec<String> = output_str
-        .lines()
-        .map(|s| s.to_string())
-        .collect();
+    let count = monitor.previews.len() as u32;
+    let cells = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints((0..count).map(|_| Constraint::Ratio(1, count)))
+        .split(inner);
+    for ((name, content), cell) in monitor.previews.iter().zip(cells.iter()) {
+        let lines: Vec<&str> = content.lines().collect();
+        let end = lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map_or(0, |i| i + 1);
+        let height = cell.height.saturating_sub(2) as usize;
+        let visible = lines[end.saturating_sub(height)..end].join("\n");
+        f.render_widget(
+            Paragraph::new(visible)
+                .block(Block::default().borders(Borders::ALL).title(format!(" {} ", name))),
+            *cell,
+        );
+    }
+}
+
+/// Returns the running tmux sessions that belong to the project (no fallback).
+pub fn find_project_sessions(project_name: &str) -> Result<Vec<String>> {
+    Ok(list_tmux_sessions()?
+        .into_iter()
+        .filter(|s| s.starts_with(project_name))
+        .collect())
+}
+
+fn find_active_sessions(project_name: &str) -> Result<Vec<String>> {
+    let all_sessions = list_tmux_sessions()?;
 
     // First try to find sessions starting with project_name
     let matched_sessions: Vec<String> = all_sessions
@@ -599,6 +1677,33 @@ fn find_active_sessions(project_name: &str) -> Result<Vec<String>> {
     }
 }
 
+fn list_tmux_sessions() -> Result<Vec<String>> {
+    let output = Command::new("tmux")
+        .args(["list-sessions", "-F", "#{session_name}\t#{session_group}"])
+        .output()
+        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to list sessions: {}", e)))?;
+
+    if !output.status.success() {
+        // It's possible no sessions exist
+        return Ok(vec![]);
+    }
+
+    Ok(primary_sessions(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Session names from `list-sessions` output (`name<TAB>group`), leaving out sessions
+/// grouped onto another one (`add --group`). They share the original's windows, so
+/// sending to the original is enough.
+fn primary_sessions(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, group) = line.split_once('\t').unwrap_or((line, ""));
+            (group.is_empty() || group == name).then(|| name.to_string())
+        })
+        .collect()
+}
+
 fn calculate_cursor_pos(input: &str, cursor_idx: usize, max_width: u16) -> (u16, u16) {
     if input.is_empty() {
         return (0, 0);
@@ -626,17 +1731,388 @@ fn calculate_cursor_pos(input: &str, cursor_idx: usize, max_width: u16) -> (u16,
     (x, y)
 }
 
-fn execute_send_action(action: SendAction) -> Result<()> {
-    let window = "apps"; // Assuming standard single window layout
-    
-    let panes = get_panes(&action.session_name, window)?;
-    
-    if panes.is_empty() {
+/// Panes a send was meant for, how many got the text, and what went wrong for the rest
+struct SendOutcome {
+    targets: usize,
+    sent: usize,
+    failures: Vec<String>,
+}
+
+impl SendOutcome {
+    fn failed(failure: String) -> Self {
+        Self { targets: 0, sent: 0, failures: vec![failure] }
+    }
+}
+
+/// Send `action`. Errors that stop the whole send are returned as `Err`; failures
+/// for individual panes, including apps without a pane to send to, are collected
+/// in the outcome so the rest still get the text.
+fn execute_send_action(action: SendAction) -> Result<SendOutcome> {
+    if let Some(target) = &action.raw_target {
+        // In iTerm2 the target is a session id, which `paste` checks itself
+        if action.backend == Backend::Tmux {
+            validate_tmux_target(target)?;
+        }
+        action.backend.paste(target, &action.text)?;
+        if action.copy_sent_text {
+            copy_sent_text(&action.text);
+        }
+        return Ok(SendOutcome { targets: 1, sent: 1, failures: Vec::new() });
+    }
+
+    let mut failures = Vec::new();
+
+    let column_panes_map = action.backend.columns(&action.session_name)?;
+
+    if column_panes_map.is_empty() {
          return Err(MultiAiError::Tmux("No panes found in session".to_string()));
     }
     
+    let targets = send_targets(action.app_index, action.target_type, &action.apps, &column_panes_map);
+
+    let mut sent = 0;
+    for target in &targets {
+        let Some(target_column) = target.column.and_then(|column| column_panes_map.get(column)) else {
+            failures.push(format!("{}: no column for it in {}", target.label, action.session_name));
+            continue;
+        };
+        
+        let Some(target_pane) = find_target_pane(action.target_type, target_column, action.command_pane)
+        else {
+            let pane = match action.target_type {
+                TargetType::Prompt => "prompt",
+                TargetType::Command => "command",
+            };
+            failures.push(format!("{}: no {} pane to send to", target.label, pane));
+            continue;
+        };
+        
+        let mut final_text = action.text.clone();
+        
+        // Apply ultrathink if needed
+        if action.ultrathink && action.target_type == TargetType::Prompt
+            && let Some(ultra) = target.app.and_then(|app| app.ultrathink())
+        {
+            final_text = action.ultrathink_separator.join(&final_text, ultra);
+        }
+        
+        match action.backend.send_keys(&target_pane.id, &final_text) {
+            Ok(()) => sent += 1,
+            Err(e) => failures.push(format!("Failed to send to pane {}: {}", target_pane.id, e)),
+        }
+
+        if action.log_to_command_pane && action.target_type == TargetType::Prompt
+            && let Some(command_pane) =
+                find_target_pane(TargetType::Command, target_column, action.command_pane)
+            && let Err(e) = action.backend.send_keys(&command_pane.id, &as_shell_comment(&action.text))
+        {
+            failures.push(format!("Failed to log to pane {}: {}", command_pane.id, e));
+        }
+    }
+
+    // The text as typed, without per-app ultrathink hints
+    if action.copy_sent_text {
+        copy_sent_text(&action.text);
+    }
+
+    Ok(SendOutcome { targets: targets.len(), sent, failures })
+}
+
+/// A column a send goes to: its name in reports, the configured app running in it
+/// (for the ultrathink hint), and its index in the layout, if the session has one
+struct SendTarget<'a> {
+    label: String,
+    app: Option<&'a AiApp>,
+    column: Option<usize>,
+}
+
+/// Where a send to `app_index` (an index into `apps`; None for all tools) goes.
+/// Columns are matched to apps by their tags, so a session whose columns were
+/// reordered, or that only runs some of the apps, still gets each text right.
+fn send_targets<'a>(
+    app_index: Option<usize>,
+    target_type: TargetType,
+    apps: &'a [AiApp],
+    columns: &[Vec<TmuxPane>],
+) -> Vec<SendTarget<'a>> {
+    let names = column_app_names(columns, apps);
+    match app_index {
+        Some(index) => {
+            let app = apps.get(index);
+            vec![SendTarget {
+                label: app.map_or_else(|| format!("app {}", index + 1), |app| app.name.clone()),
+                app,
+                column: app.and_then(|app| app_column(&names, app)),
+            }]
+        }
+        // Editor and shell columns have no prompt pane, so a prompt for all tools skips them
+        None => names
+            .into_iter()
+            .enumerate()
+            .map(|(column, name)| SendTarget {
+                app: name.as_ref().and_then(|name| apps.iter().find(|app| &app.name == name)),
+                label: name.unwrap_or_else(|| format!("column {}", column + 1)),
+                column: Some(column),
+            })
+            .filter(|target| target_type == TargetType::Command || target.app.is_none_or(AiApp::is_ai))
+            .collect(),
+    }
+}
+
+/// Turn sent text into shell comment lines, so pasting it into a shell records it
+/// in the scrollback and history without running anything.
+fn as_shell_comment(text: &str) -> String {
+    text.lines()
+        .map(|line| if line.is_empty() { "#".to_string() } else { format!("# {}", line) })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Check that a user-supplied tmux target resolves to an existing pane.
+fn validate_tmux_target(target: &str) -> Result<()> {
+    let output = Command::new("tmux")
+        .args(["display-message", "-p", "-t", target, "#{pane_id}"])
+        .output()
+        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to resolve target: {}", e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MultiAiError::Tmux(format!(
+            "Invalid tmux target '{}': {}",
+            target,
+            stderr.trim()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Clipboard tools tried in order; the first one that runs wins.
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// Run `program` with `text` on stdin; true if it exited successfully.
+fn pipe_to_command(program: &str, args: &[&str], text: &str) -> bool {
+    let Ok(mut child) = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take()
+        && stdin.write_all(text.as_bytes()).is_err()
+    {
+        return false;
+    }
+    child.wait().is_ok_and(|status| status.success())
+}
+
+/// Put sent text on top of tmux's paste buffer stack (prefix + ] re-pastes it) and
+/// on the system clipboard. Best effort: a missing clipboard tool is not an error.
+fn copy_sent_text(text: &str) {
+    // -w also forwards the buffer to the outer terminal's clipboard via OSC 52 (tmux 3.2+)
+    if !pipe_to_command("tmux", &["load-buffer", "-w", "-"], text) {
+        pipe_to_command("tmux", &["load-buffer", "-"], text);
+    }
+    for (program, args) in CLIPBOARD_COMMANDS {
+        if pipe_to_command(program, args, text) {
+            break;
+        }
+    }
+}
+
+/// Prefix of the tmux buffers `paste_text_to_pane` loads, followed by `{pid}-{n}`
+const SEND_BUFFER_PREFIX: &str = "mai-send-";
+
+/// Numbers the paste buffers of this process so no two sends share one
+static SEND_BUFFER_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// `mai-send-*` buffers left behind by mai processes that are no longer running,
+/// e.g. one killed between loading and pasting a buffer. `buffer_names` is the
+/// output of `tmux list-buffers -F '#{buffer_name}'`.
+fn stale_send_buffers(buffer_names: &str, is_running: impl Fn(u32) -> bool) -> Vec<String> {
+    buffer_names
+        .lines()
+        .filter(|name| {
+            let Some(rest) = name.strip_prefix(SEND_BUFFER_PREFIX) else {
+                return false;
+            };
+            // Older versions named the buffer `mai-send-{pid}` without a counter
+            let pid = rest.split('-').next().and_then(|pid| pid.parse().ok());
+            pid.is_none_or(|pid| !is_running(pid))
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// `kill -0` fails with EPERM for another user's process, so ask `ps` instead.
+/// If `ps` can't run, the process counts as running and its buffers are kept.
+fn process_running(pid: u32) -> bool {
+    Command::new("ps")
+        .args(["-p", &pid.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_or(true, |status| status.success())
+}
+
+/// Delete paste buffers leaked by earlier `mai send` runs. Best effort.
+fn prune_stale_send_buffers() {
+    let Ok(output) = Command::new("tmux")
+        .args(["list-buffers", "-F", "#{buffer_name}"])
+        .output()
+    else {
+        return;
+    };
+    if !output.status.success() {
+        return;
+    }
+    for name in stale_send_buffers(&String::from_utf8_lossy(&output.stdout), process_running) {
+        delete_buffer(&name);
+    }
+}
+
+fn delete_buffer(name: &str) {
+    let _ = Command::new("tmux")
+        .args(["delete-buffer", "-b", name])
+        .stderr(Stdio::null())
+        .status();
+}
+
+/// Paste text into a pane via a tmux buffer, then press Enter.
+/// Pasting (with bracketed paste) keeps multi-line text together instead of
+/// submitting each line separately like `send-keys` would.
+pub(crate) fn paste_text_to_pane(target: &str, text: &str) -> Result<()> {
+    let buffer_name = format!(
+        "{}{}-{}",
+        SEND_BUFFER_PREFIX,
+        std::process::id(),
+        SEND_BUFFER_COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+
+    let mut child = Command::new("tmux")
+        .args(["load-buffer", "-b", &buffer_name, "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to load tmux buffer: {}", e)))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to load tmux buffer: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MultiAiError::Tmux(format!(
+            "Failed to load tmux buffer: {}",
+            stderr.trim()
+        )));
+    }
+
+    // -d deletes the buffer after pasting, -p uses bracketed paste when the app supports it
+    let output = Command::new("tmux")
+        .args(["paste-buffer", "-d", "-p", "-b", &buffer_name, "-t", target])
+        .output()
+        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to paste buffer: {}", e)))?;
+    if !output.status.success() {
+        // -d only deletes the buffer when the paste went through
+        delete_buffer(&buffer_name);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MultiAiError::Tmux(format!(
+            "Failed to paste into {}: {}",
+            target,
+            stderr.trim()
+        )));
+    }
+
+    // Give the app a moment to process the paste before submitting
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    let output = Command::new("tmux")
+        .args(["send-keys", "-t", target, "Enter"])
+        .output()
+        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to send keys: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MultiAiError::Tmux(format!(
+            "Failed to send Enter to {}: {}",
+            target,
+            stderr.trim()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Type `text` into a pane with `send-keys`, then press Enter
+fn send_keys_to_pane(target: &str, text: &str) -> Result<()> {
+    let output = Command::new("tmux")
+        .args(["send-keys", "-t", target, text, "Enter"])
+        .output()
+        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to send keys: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MultiAiError::Tmux(format!(
+            "Failed to send keys to {}: {}",
+            target,
+            stderr.trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Index of the pane to send to within a column of `column_len` panes (top to bottom).
+/// Prompts go to the AI pane on top; commands go to `command_pane` if set, else the
+/// bottom pane. None when the column has no such pane (e.g. a single-pane column).
+fn target_pane_index(
+    target_type: TargetType,
+    column_len: usize,
+    command_pane: Option<usize>,
+) -> Option<usize> {
+    let index = match target_type {
+        TargetType::Prompt => 0,
+        TargetType::Command => command_pane.unwrap_or(column_len.checked_sub(1)?),
+    };
+    (index < column_len && !(target_type == TargetType::Command && index == 0)).then_some(index)
+}
+
+/// The pane to send to within a column (sorted top to bottom). Columns whose panes
+/// were tagged with roles at creation are matched by role, so a rearranged layout
+/// still hits the AI pane; untagged columns (e.g. older sessions) go by position.
+pub(crate) fn find_target_pane(
+    target_type: TargetType,
+    column: &[TmuxPane],
+    command_pane: Option<usize>,
+) -> Option<&TmuxPane> {
+    if column.iter().all(|pane| pane.role.is_none()) {
+        return target_pane_index(target_type, column.len(), command_pane)
+            .and_then(|index| column.get(index));
+    }
+    match target_type {
+        TargetType::Prompt => column.iter().find(|pane| pane.role == Some(PaneRole::Ai)),
+        TargetType::Command => match command_pane {
+            Some(index) => column.get(index).filter(|pane| pane.role == Some(PaneRole::Shell)),
+            None => column.iter().rev().find(|pane| pane.role == Some(PaneRole::Shell)),
+        },
+    }
+}
+
+/// Group panes into columns (left to right), each column sorted top to bottom.
+/// Panes whose left edges are within a few cells of each other share a column.
+/// The extra columns of a wide app (all `extra` panes) join the column to their left,
+/// so each group holds one app.
+pub(crate) fn group_panes_into_columns(panes: &[TmuxPane]) -> Vec<Vec<TmuxPane>> {
     // Re-sort purely by x first to identify columns.
-    let mut x_sorted = panes.clone();
+    let mut x_sorted = panes.to_vec();
     x_sorted.sort_by_key(|p| p.x);
     
     // Determine column starts
@@ -661,82 +2137,413 @@ fn execute_send_action(action: SendAction) -> Result<()> {
             .cloned()
             .collect();
         col_panes.sort_by_key(|p| p.y);
-        column_panes_map.push(col_panes);
+        let extra = col_panes.iter().all(|p| p.role == Some(PaneRole::Extra));
+        match column_panes_map.last_mut() {
+            Some(app_column) if extra => app_column.extend(col_panes),
+            _ => column_panes_map.push(col_panes),
+        }
     }
-    
-    // Determine which columns to target
-    let target_indices: Vec<usize> = match action.app_index {
-        Some(idx) => vec![idx],
-        None => (0..column_panes_map.len()).collect(),
+
+    column_panes_map
+}
+
+/// Print the raw pane list of `session` and the column mapping `mai send`
+/// derives from it, without entering the TUI.
+pub fn list_panes(project_config: &ProjectConfig, session: &str) -> Result<()> {
+    let window = "apps";
+    let panes = get_panes(session, window)?;
+
+    println!("Session: {} (window: {})", session, window);
+    println!("\nPanes (id left top role app):");
+    for pane in &panes {
+        let role = pane.role.map(PaneRole::as_str).unwrap_or("-");
+        println!("  {} {} {} {} {}", pane.id, pane.x, pane.y, role, pane.app.as_deref().unwrap_or("-"));
+    }
+
+    println!("\nColumns:");
+    let columns = group_panes_into_columns(&panes);
+    let names = column_app_names(&columns, &project_config.ai_apps);
+    for (i, (column, name)) in columns.iter().zip(&names).enumerate() {
+        let app_name = name.as_deref().unwrap_or("(no app)");
+        let prompt = find_target_pane(TargetType::Prompt, column, project_config.command_pane)
+            .map(|p| p.id.as_str());
+        let command = find_target_pane(TargetType::Command, column, project_config.command_pane)
+            .map(|p| p.id.as_str());
+        let extra: Vec<&str> = column
+            .iter()
+            .map(|p| p.id.as_str())
+            .filter(|&id| Some(id) != prompt && Some(id) != command)
+            .collect();
+        let (prompt, command) = (prompt.unwrap_or("-"), command.unwrap_or("-"));
+        print!("  [{}] {}: prompt={} command={}", i, app_name, prompt, command);
+        if !extra.is_empty() {
+            print!(" other={}", extra.join(","));
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Match `text` against `pattern`, where `*` matches any run of characters.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No '*' in the pattern: exact match
+        return rest.is_empty();
     };
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// The app each column of a session runs, read from the panes' `@mai_app` tags.
+/// Sessions created before the tags existed fall back to config order.
+fn column_app_names(columns: &[Vec<TmuxPane>], apps: &[AiApp]) -> Vec<Option<String>> {
+    let tagged = columns.iter().flatten().any(|p| p.app.is_some());
+    columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| match tagged {
+            true => column.iter().find_map(|p| p.app.clone()),
+            false => apps.get(i).map(|a| a.name.clone()),
+        })
+        .collect()
+}
 
-    for &app_idx in &target_indices {
-        if app_idx >= column_panes_map.len() {
-            continue; // Should we warn?
+/// Column running `app`, given the app names of a layout's columns (see `column_app_names`)
+fn app_column(names: &[Option<String>], app: &AiApp) -> Option<usize> {
+    names.iter().position(|name| name.as_deref() == Some(app.name.as_str()))
+}
+
+/// Indexes of columns whose app name, or the slug of the configured app of that
+/// name, matches any of `patterns`, in layout order.
+fn matching_columns(names: &[Option<String>], apps: &[AiApp], patterns: &[String]) -> Vec<usize> {
+    names
+        .iter()
+        .enumerate()
+        .filter_map(|(i, name)| Some((i, name.as_deref()?)))
+        .filter(|(_, name)| {
+            let slug = apps.iter().find(|a| a.name == *name).map(AiApp::slug);
+            patterns.iter().any(|p| {
+                glob_match(p, name) || slug.as_deref().is_some_and(|slug| glob_match(p, slug))
+            })
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// The session `mai send --app`/`--list-panes` acts on: the one named by
+/// `selector` (a branch prefix or full session name), else the project's only
+/// running session. Refuses to guess when several are running.
+pub fn pick_session(project_name: &str, selector: Option<&str>) -> Result<String> {
+    let sessions = find_project_sessions(project_name)?;
+    if let Some(selector) = selector {
+        let prefixed = format!("{}-{}", project_name, selector);
+        return sessions
+            .into_iter()
+            .find(|s| *s == prefixed || s == selector)
+            .ok_or_else(|| MultiAiError::Tmux(format!("No running session matches '{}'", selector)));
+    }
+    match sessions.as_slice() {
+        [] => Err(MultiAiError::Tmux("No active sessions found for this project".to_string())),
+        [session] => Ok(session.clone()),
+        _ => Err(MultiAiError::Tmux(format!(
+            "{} sessions are running ({}); pick one with --session <branch-prefix>",
+            sessions.len(),
+            sessions.join(", ")
+        ))),
+    }
+}
+
+/// How long `--wait-for` waits when no `--timeout` is given
+pub const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// `--wait-for`: after sending, block until `sentinel` appears in every pane
+/// that received the text, or fail once `timeout` has passed.
+pub struct WaitFor<'a> {
+    pub sentinel: &'a str,
+    pub timeout: Duration,
+}
+
+/// Per-run options for `mai send` that come from the command line
+#[derive(Default)]
+pub struct SendOptions<'a> {
+    /// `--list-panes`: print the session's panes instead of sending
+    pub list_panes: bool,
+    /// `--session`: prefix of the session to use when several are running
+    pub session: Option<String>,
+    /// `--app`: patterns naming the apps to send to
+    pub apps: Vec<String>,
+    /// `--command`: send to the command pane instead of the AI pane
+    pub to_command_pane: bool,
+    /// Text to send without opening the TUI
+    pub text: Option<String>,
+    pub wait_for: Option<WaitFor<'a>>,
+    /// `--dry-run`: show the targets and ask before sending
+    pub dry_run: bool,
+}
+
+/// A pane `--wait-for` is watching, with the number of lines before its last
+/// non-empty line when the text was sent (that line may get the echo appended).
+struct WaitingPane {
+    app_name: String,
+    id: String,
+    skip_lines: usize,
+}
+
+/// Send `text` to every app column matching `options.apps` in `session`,
+/// without entering the TUI. Prints which panes were hit, then waits for the
+/// `options.wait_for` sentinel if one is given.
+pub fn send_to_matching_apps(
+    project_config: &ProjectConfig,
+    session: &str,
+    text: &str,
+    options: &SendOptions,
+) -> Result<()> {
+    let SendOptions {
+        apps: patterns,
+        to_command_pane,
+        wait_for,
+        dry_run,
+        ..
+    } = options;
+    prune_stale_send_buffers();
+
+    let columns = group_panes_into_columns(&get_panes(session, "apps")?);
+    let names = column_app_names(&columns, &project_config.ai_apps);
+    let indexes = matching_columns(&names, &project_config.ai_apps, patterns);
+    if indexes.is_empty() {
+        let available: Vec<&str> = names.iter().flatten().map(String::as_str).collect();
+        return Err(MultiAiError::Config(format!(
+            "No apps in {} match {} (available: {})",
+            session,
+            patterns.join(", "),
+            available.join(", ")
+        )));
+    }
+
+    let target_type = if *to_command_pane {
+        TargetType::Command
+    } else {
+        TargetType::Prompt
+    };
+
+    let mut targets = Vec::new();
+    for i in indexes {
+        let app_name = names[i].as_ref().expect("matched columns have an app");
+        match find_target_pane(target_type, &columns[i], project_config.command_pane) {
+            Some(pane) => targets.push((app_name, pane)),
+            None => eprintln!("  {}: no matching pane in {}, skipped", app_name, session),
         }
-        
-        let target_column = &column_panes_map[app_idx];
-        
-        let target_pane_index = match action.target_type {
-            TargetType::Prompt => 0,
-            TargetType::Command => 1,
-        };
-        
-        if target_pane_index >= target_column.len() {
-            continue; // Warn?
+    }
+    if targets.is_empty() {
+        return Err(MultiAiError::Tmux(format!("Nothing was sent to {}", session)));
+    }
+
+    if *dry_run {
+        println!("Would send to {} pane(s):", targets.len());
+        for (app_name, pane) in &targets {
+            println!("  {}  {} -> {}", session, app_name, pane.id);
         }
-        
-        let target_pane = &target_column[target_pane_index];
-        
-        let mut final_text = action.text.clone();
-        
-        // Apply ultrathink if needed
-        if action.ultrathink && action.target_type == TargetType::Prompt {
-             // We need to get the app corresponding to this column.
-             // Assuming apps order matches column order.
-             if app_idx < action.apps.len() {
-                 if let Some(ultra) = action.apps[app_idx].ultrathink() {
-                     final_text.push_str("\n\n");
-                     final_text.push_str(ultra);
-                 }
-             }
+        println!("Text ({} line(s)):", text.lines().count());
+        for line in text.lines() {
+            println!("  | {}", line);
         }
-        
-        // Send Keys
-        let output = Command::new("tmux")
-            .args([
-                "send-keys",
-                "-t",
-                &target_pane.id,
-                &final_text,
-                "Enter",
-            ])
-            .output()
-            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to send keys: {}", e)))?;
-            
-        if !output.status.success() {
-             eprintln!("Failed to send keys to pane {}", target_pane.id);
+        print!("Send it? [y/N]: ");
+        io::stdout().flush()?;
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+            println!("Nothing sent.");
+            return Ok(());
         }
     }
 
-    Ok(())
+    let mut sent = 0;
+    let mut waiting = Vec::new();
+    for (app_name, pane) in targets {
+        let skip_lines = match wait_for {
+            Some(_) => last_non_empty_line(&tail::capture_pane(&pane.id)?),
+            None => 0,
+        };
+        match paste_text_to_pane(&pane.id, text) {
+            Ok(()) => {
+                println!("  {} -> {}", app_name, pane.id);
+                sent += 1;
+                waiting.push(WaitingPane {
+                    app_name: app_name.clone(),
+                    id: pane.id.clone(),
+                    skip_lines,
+                });
+            }
+            Err(e) => eprintln!("  {}: failed to send to {}: {}", app_name, pane.id, e),
+        }
+    }
+
+    if sent == 0 {
+        return Err(MultiAiError::Tmux(format!("Nothing was sent to {}", session)));
+    }
+    if project_config.copy_sent_text {
+        copy_sent_text(text);
+    }
+    println!("Sent to {} pane(s) in {}", sent, session);
+
+    match wait_for {
+        Some(wait) => wait_for_sentinel(waiting, text, wait),
+        None => Ok(()),
+    }
+}
+
+/// Input prompts of the supported agents and shells, as they appear at the start
+/// of a line the user typed
+const INPUT_MARKERS: &[&str] = &[">", "❯", "›", "$", "%"];
+
+/// Endings of shell prompts with a prefix, like `user@host:~/repo$`
+const SHELL_PROMPT_ENDINGS: &[char] = &['$', '#', '%'];
+
+/// The last input typed into a pane, found from the last line starting with an
+/// input marker (`> `, `❯ `, `$ `, `user@host:~$ ` and the like) that has text
+/// after it, plus the indented lines that continue it. Box borders around input fields are ignored.
+/// Falls back to the last non-empty line when no marker is found.
+fn last_user_input(content: &str) -> Option<String> {
+    let strip_border = |line: &str| {
+        line.trim_start_matches(['│', '┃', '|'])
+            .trim_end()
+            .trim_end_matches(['│', '┃', '|'])
+            .trim_end()
+            .to_string()
+    };
+    let lines: Vec<String> = content.lines().map(strip_border).collect();
+    let typed = |line: &str| {
+        let (head, rest) = line.trim_start().split_once(' ')?;
+        let is_prompt = INPUT_MARKERS.contains(&head)
+            || (head.len() > 1 && head.ends_with(SHELL_PROMPT_ENDINGS));
+        let rest = rest.trim();
+        (is_prompt && !rest.is_empty()).then(|| rest.to_string())
+    };
+
+    let Some((start, first)) = lines
+        .iter()
+        .enumerate()
+        .rev()
+        .find_map(|(i, line)| typed(line).map(|text| (i, text)))
+    else {
+        return content
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string);
+    };
+
+    let mut input = vec![first];
+    for line in &lines[start + 1..] {
+        if !line.starts_with("  ") || line.trim().is_empty() || typed(line).is_some() {
+            break;
+        }
+        input.push(line.trim().to_string());
+    }
+    Some(input.join("\n"))
+}
+
+fn last_non_empty_line(content: &str) -> usize {
+    let lines: Vec<&str> = content.lines().collect();
+    lines.iter().rposition(|line| !line.trim().is_empty()).unwrap_or(0)
+}
+
+/// Whether the sentinel shows up in a pane as a reply to `text`. Only lines the
+/// pane gained since sending are searched, starting after the last echo of the
+/// text, so the sentinel quoted in the prompt ("reply DONE when finished") does
+/// not count. If the echo can't be found, e.g. because the app rewrapped it, the
+/// sentinel must appear more often than it does in the text.
+fn sentinel_answered(content: &str, skip_lines: usize, text: &str, sentinel: &str) -> bool {
+    let lines: Vec<&str> = content.lines().collect();
+    let new_output = lines[skip_lines.min(lines.len())..].join("\n");
+    let echo_end = text
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .and_then(|last| new_output.rfind(last).map(|i| i + last.len()));
+    match echo_end {
+        Some(end) => new_output[end..].contains(sentinel),
+        None => new_output.matches(sentinel).count() > text.matches(sentinel).count(),
+    }
+}
+
+/// Poll the panes until each has answered `text` with the sentinel.
+fn wait_for_sentinel(mut panes: Vec<WaitingPane>, text: &str, wait: &WaitFor) -> Result<()> {
+    println!(
+        "Waiting for '{}' in {} pane(s) (timeout {}s)...",
+        wait.sentinel,
+        panes.len(),
+        wait.timeout.as_secs()
+    );
+    let deadline = Instant::now() + wait.timeout;
+    loop {
+        let mut pending = Vec::new();
+        for pane in panes {
+            let content = tail::capture_pane(&pane.id).map_err(|_| {
+                MultiAiError::Tmux(format!(
+                    "Pane {} ({}) closed before '{}' appeared",
+                    pane.id, pane.app_name, wait.sentinel
+                ))
+            })?;
+            if sentinel_answered(&content, pane.skip_lines, text, wait.sentinel) {
+                println!("  {}: found '{}'", pane.app_name, wait.sentinel);
+            } else {
+                pending.push(pane);
+            }
+        }
+        if pending.is_empty() {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            let names: Vec<&str> = pending.iter().map(|p| p.app_name.as_str()).collect();
+            return Err(MultiAiError::Tmux(format!(
+                "Timed out after {}s waiting for '{}' in {}",
+                wait.timeout.as_secs(),
+                wait.sentinel,
+                names.join(", ")
+            )));
+        }
+        panes = pending;
+        std::thread::sleep(WAIT_POLL_INTERVAL);
+    }
 }
 
 #[derive(Clone, Debug)]
-struct TmuxPane {
-    id: String,
-    x: usize,
-    y: usize,
+pub(crate) struct TmuxPane {
+    pub(crate) id: String,
+    pub(crate) x: usize,
+    pub(crate) y: usize,
+    // Set when mai created the pane (see `PANE_ROLE_OPTION`)
+    pub(crate) role: Option<PaneRole>,
+    // Name of the app the pane belongs to, when mai tagged it (see `PANE_APP_OPTION`)
+    pub(crate) app: Option<String>,
 }
 
-fn get_panes(session: &str, window: &str) -> Result<Vec<TmuxPane>> {
+pub(crate) fn get_panes(session: &str, window: &str) -> Result<Vec<TmuxPane>> {
     let output = Command::new("tmux")
         .args([
             "list-panes",
             "-t",
             &format!("{}:{}", session, window),
             "-F",
-            "#{pane_id} #{pane_left} #{pane_top}",
+            &format!(
+                "#{{pane_id}}\t#{{pane_left}}\t#{{pane_top}}\t#{{{}}}\t#{{{}}}",
+                PANE_ROLE_OPTION, PANE_APP_OPTION
+            ),
         ])
         .output()
         .map_err(|e| MultiAiError::CommandFailed(format!("Failed to list panes: {}", e)))?;
@@ -749,15 +2556,550 @@ fn get_panes(session: &str, window: &str) -> Result<Vec<TmuxPane>> {
     let mut panes = Vec::new();
     
     for line in output_str.lines() {
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() == 3 {
+        // The role and app are empty for panes mai didn't tag; app names may hold spaces
+        let parts: Vec<&str> = line.split('\t').collect();
+        if parts.len() >= 3 {
             panes.push(TmuxPane {
                 id: parts[0].to_string(),
                 x: parts[1].parse().unwrap_or(0),
                 y: parts[2].parse().unwrap_or(0),
+                role: parts.get(3).and_then(|role| PaneRole::parse(role)),
+                app: parts.get(4).filter(|app| !app.is_empty()).map(|app| app.to_string()),
             });
         }
     }
     
     Ok(panes)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        as_shell_comment, file_reference, find_target_pane, glob_match, group_panes_into_columns,
+        iterm2_columns, iterm2_sessions,
+        column_app_names, last_non_empty_line, last_user_input, matching_columns, send_targets, stale_send_buffers, process_running, SendOutcome, primary_sessions, sentinel_answered, target_pane_index, utc_timestamp,
+        worktree_diff, InputHistory, LastSend, SessionHealth, TargetType, TmuxPane, TuiState, MAX_ERROR_LOG_ENTRIES,
+    };
+    use std::path::PathBuf;
+    use std::process::Command;
+    use std::time::{Duration, UNIX_EPOCH};
+    use crate::config::{AiApp, AppKind, FileReferenceStyle, UltrathinkSeparator};
+    use crate::iterm2::ITerm2Pane;
+    use crate::tmux::PaneRole;
+
+    fn app(name: &str) -> AiApp {
+        serde_json::from_value(serde_json::json!({"name": name, "command": name})).unwrap()
+    }
+
+    fn pane(id: &str, x: usize, y: usize) -> TmuxPane {
+        TmuxPane { id: id.to_string(), x, y, role: None, app: None }
+    }
+
+    #[test]
+    fn test_group_panes_into_columns() {
+        let panes = vec![
+            pane("%4", 81, 20),
+            pane("%1", 0, 0),
+            pane("%3", 81, 0),
+            pane("%2", 0, 20),
+        ];
+        let columns = group_panes_into_columns(&panes);
+        let ids: Vec<Vec<&str>> = columns
+            .iter()
+            .map(|c| c.iter().map(|p| p.id.as_str()).collect())
+            .collect();
+        assert_eq!(ids, vec![vec!["%1", "%2"], vec!["%3", "%4"]]);
+    }
+
+    #[test]
+    fn test_group_panes_join_extra_columns_to_their_app() {
+        // A wide app in two columns, then a one-column app
+        let extra = |id: &str, x: usize| TmuxPane { role: Some(PaneRole::Extra), ..pane(id, x, 0) };
+        let panes = vec![
+            TmuxPane { role: Some(PaneRole::Ai), ..pane("%1", 0, 0) },
+            TmuxPane { role: Some(PaneRole::Shell), ..pane("%2", 0, 20) },
+            extra("%3", 54),
+            TmuxPane { role: Some(PaneRole::Ai), ..pane("%4", 108, 0) },
+            TmuxPane { role: Some(PaneRole::Shell), ..pane("%5", 108, 20) },
+        ];
+        let columns = group_panes_into_columns(&panes);
+        let ids: Vec<Vec<&str>> = columns
+            .iter()
+            .map(|c| c.iter().map(|p| p.id.as_str()).collect())
+            .collect();
+        assert_eq!(ids, vec![vec!["%1", "%2", "%3"], vec!["%4", "%5"]]);
+        // Commands still go to the shell below the AI pane, not the extra column
+        assert_eq!(find_target_pane(TargetType::Command, &columns[0], None).unwrap().id, "%2");
+    }
+
+    #[test]
+    fn test_group_panes_into_columns_empty() {
+        assert!(group_panes_into_columns(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_input_history_recall() {
+        let mut history = InputHistory::default();
+        history.push("first");
+        history.push("second");
+        history.push("second");
+        history.push("   ");
+        assert_eq!(history.entries, vec!["first", "second"]);
+
+        assert_eq!(history.older("draft"), Some("second"));
+        assert_eq!(history.older("second"), Some("first"));
+        assert_eq!(history.older("first"), Some("first"));
+        assert_eq!(history.newer(), Some("second"));
+        assert_eq!(history.newer(), Some("draft"));
+        assert_eq!(history.newer(), None);
+    }
+
+    #[test]
+    fn test_input_history_empty() {
+        let mut history = InputHistory::default();
+        assert_eq!(history.older("draft"), None);
+        assert_eq!(history.newer(), None);
+    }
+
+    #[test]
+    fn test_ultrathink_unavailable_reason() {
+        let mut state = TuiState::new(
+            vec!["s".to_string()],
+            vec![
+                AiApp { ultrathink: Some("ultrathink".to_string()), ..app("claude") },
+                app("codex"),
+            ],
+            UltrathinkSeparator::default(),
+        );
+
+        // "All Tools" is available when any app has a hint
+        assert_eq!(state.ultrathink_unavailable_reason(), None);
+
+        state.app_list_state.select(Some(2));
+        assert_eq!(state.ultrathink_unavailable_reason(), Some("no hint configured"));
+
+        state.toggle_target_type();
+        assert_eq!(state.target_type, TargetType::Command);
+        assert_eq!(state.ultrathink_unavailable_reason(), Some("command mode"));
+
+        state.toggle_target_type();
+        assert_eq!(state.target_type, TargetType::Prompt);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("claude-*", "claude-frontend"));
+        assert!(glob_match("*-backend", "claude-backend"));
+        assert!(glob_match("c*e-*d", "claude-backend"));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("codex", "codex"));
+        assert!(!glob_match("codex", "codex-mini"));
+        assert!(!glob_match("claude-*", "gemini"));
+        assert!(!glob_match("a*a", "a"));
+    }
+
+    #[test]
+    fn test_matching_columns_follow_app_tags() {
+        let apps = vec![
+            app("claude-frontend"),
+            app("codex"),
+            app("claude-backend"),
+        ];
+
+        let tagged = |id: &str, x: usize, app: &str| TmuxPane {
+            app: Some(app.to_string()),
+            ..pane(id, x, 0)
+        };
+        let patterns = |p: &[&str]| p.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+
+        // The session's layout no longer follows config order
+        let columns = vec![
+            vec![tagged("%1", 0, "codex")],
+            vec![tagged("%2", 81, "claude-backend")],
+            vec![tagged("%3", 162, "claude-frontend")],
+        ];
+        let names = column_app_names(&columns, &apps);
+        assert_eq!(names[0].as_deref(), Some("codex"));
+        assert_eq!(matching_columns(&names, &apps, &patterns(&["claude-*"])), vec![1, 2]);
+        assert_eq!(matching_columns(&names, &apps, &patterns(&["codex", "*-backend"])), vec![0, 1]);
+        assert!(matching_columns(&names, &apps, &patterns(&["gemini"])).is_empty());
+
+        // Sessions from before the tags map columns by config order
+        let untagged = vec![vec![pane("%1", 0, 0)], vec![pane("%2", 81, 0)]];
+        let names = column_app_names(&untagged, &apps);
+        assert_eq!(matching_columns(&names, &apps, &patterns(&["claude-*"])), vec![0]);
+    }
+
+    #[test]
+    fn test_send_targets_follow_app_tags() {
+        let apps = vec![
+            AiApp { ultrathink: Some("ultrathink".to_string()), ..app("claude") },
+            AiApp { kind: AppKind::Editor, ..app("vim") },
+            app("codex"),
+        ];
+        let column = |app: &str, x: usize| {
+            vec![TmuxPane { app: Some(app.to_string()), ..pane(&format!("%{}", x), x, 0) }]
+        };
+        // Created with `--app-order codex,claude,vim`
+        let columns = vec![column("codex", 0), column("claude", 81), column("vim", 162)];
+
+        let claude = send_targets(Some(0), TargetType::Prompt, &apps, &columns);
+        assert_eq!(claude[0].column, Some(1));
+        assert_eq!(claude[0].app.and_then(|app| app.ultrathink()), Some("ultrathink"));
+
+        // A prompt for all tools skips the editor column, wherever it sits
+        let all: Vec<(String, Option<usize>)> = send_targets(None, TargetType::Prompt, &apps, &columns)
+            .into_iter()
+            .map(|target| (target.label, target.column))
+            .collect();
+        assert_eq!(all, vec![("codex".to_string(), Some(0)), ("claude".to_string(), Some(1))]);
+        assert_eq!(send_targets(None, TargetType::Command, &apps, &columns).len(), 3);
+    }
+
+    #[test]
+    fn test_send_targets_in_a_partial_session() {
+        let apps = vec![app("claude"), app("codex"), app("gemini")];
+        let column = |app: &str, x: usize| {
+            vec![TmuxPane { app: Some(app.to_string()), ..pane(&format!("%{}", x), x, 0) }]
+        };
+        // `continue --select` reopened claude and gemini only
+        let columns = vec![column("claude", 0), column("gemini", 81)];
+
+        assert_eq!(send_targets(Some(2), TargetType::Prompt, &apps, &columns)[0].column, Some(1));
+        let codex = send_targets(Some(1), TargetType::Prompt, &apps, &columns);
+        assert_eq!((codex[0].label.as_str(), codex[0].column), ("codex", None));
+    }
+
+    #[test]
+    fn test_send_targets_skip_apps_left_out_by_keep_going() {
+        let apps = vec![
+            app("claude"),
+            app("codex"),
+            AiApp { ultrathink: Some("think harder".to_string()), ..app("gemini") },
+        ];
+        let column = |app: &str, x: usize| {
+            vec![TmuxPane { app: Some(app.to_string()), ..pane(&format!("%{}", x), x, 0) }]
+        };
+        // codex's worktree failed, so `add --keep-going` laid out the other two
+        let columns = vec![column("claude", 0), column("gemini", 81)];
+
+        let all = send_targets(None, TargetType::Prompt, &apps, &columns);
+        let hints: Vec<(&str, Option<&str>)> = all
+            .iter()
+            .map(|target| (target.label.as_str(), target.app.and_then(|app| app.ultrathink())))
+            .collect();
+        assert_eq!(hints, vec![("claude", None), ("gemini", Some("think harder"))]);
+    }
+
+    #[test]
+    fn test_session_health_from_column_count() {
+        assert_eq!(SessionHealth::from_column_count(3, 3), SessionHealth::Healthy);
+        assert_eq!(SessionHealth::from_column_count(4, 3), SessionHealth::Healthy);
+        assert_eq!(SessionHealth::from_column_count(1, 3), SessionHealth::MissingColumns(2));
+    }
+
+    #[test]
+    fn test_move_selected_app_keeps_column_mapping() {
+        let mut state = TuiState::new(
+            vec!["s".to_string()],
+            vec![app("claude"), app("codex"), app("gemini")],
+            UltrathinkSeparator::default(),
+        );
+
+        // Move gemini (row 3) to the top of the apps
+        state.app_list_state.select(Some(3));
+        state.move_selected_app(true);
+        state.move_selected_app(true);
+        state.move_selected_app(true); // already first; "All Tools" stays pinned
+        assert_eq!(state.app_view_order, vec![2, 0, 1]);
+        assert_eq!(state.app_list_state.selected(), Some(1));
+
+        // The selected row still targets gemini's column
+        state.input = "hi".to_string();
+        assert_eq!(state.create_send_action().unwrap().app_index, Some(2));
+
+        state.move_selected_app(false);
+        assert_eq!(state.app_view_order, vec![0, 2, 1]);
+        assert_eq!(state.app_list_state.selected(), Some(2));
+    }
+
+    #[test]
+    fn test_target_pane_index_with_three_pane_columns() {
+        // Two columns of three panes each (AI pane on top, two shells below)
+        let panes = vec![
+            pane("%1", 0, 0),
+            pane("%2", 0, 14),
+            pane("%3", 0, 28),
+            pane("%4", 81, 0),
+            pane("%5", 81, 14),
+            pane("%6", 81, 28),
+        ];
+        let columns = group_panes_into_columns(&panes);
+        assert_eq!(columns.len(), 2);
+        let column = &columns[1];
+        let id = |target_type, command_pane| {
+            target_pane_index(target_type, column.len(), command_pane)
+                .map(|index| column[index].id.as_str())
+        };
+
+        assert_eq!(id(TargetType::Prompt, None), Some("%4"));
+        assert_eq!(id(TargetType::Command, None), Some("%6"));
+        assert_eq!(id(TargetType::Command, Some(1)), Some("%5"));
+        assert_eq!(id(TargetType::Command, Some(3)), None);
+    }
+
+    #[test]
+    fn test_find_target_pane_prefers_roles_over_position() {
+        // The AI pane was swapped below the shell, so position alone would misfire
+        let column = vec![
+            TmuxPane { role: Some(PaneRole::Shell), ..pane("%2", 0, 0) },
+            TmuxPane { role: Some(PaneRole::Ai), ..pane("%1", 0, 20) },
+        ];
+        let id = |target_type, command_pane| {
+            find_target_pane(target_type, &column, command_pane).map(|p| p.id.as_str())
+        };
+        assert_eq!(id(TargetType::Prompt, None), Some("%1"));
+        assert_eq!(id(TargetType::Command, None), Some("%2"));
+        // A configured command pane must still be a shell
+        assert_eq!(id(TargetType::Command, Some(1)), None);
+
+        // Untagged columns fall back to position
+        let column = vec![pane("%1", 0, 0), pane("%2", 0, 20)];
+        assert_eq!(
+            find_target_pane(TargetType::Prompt, &column, None).map(|p| p.id.as_str()),
+            Some("%1")
+        );
+    }
+
+    #[test]
+    fn test_target_pane_index_single_pane_column_has_no_command_pane() {
+        assert_eq!(target_pane_index(TargetType::Prompt, 1, None), Some(0));
+        assert_eq!(target_pane_index(TargetType::Command, 1, None), None);
+        assert_eq!(target_pane_index(TargetType::Command, 0, None), None);
+        assert_eq!(target_pane_index(TargetType::Command, 2, None), Some(1));
+    }
+
+    #[test]
+    fn test_as_shell_comment() {
+        assert_eq!(as_shell_comment("fix the bug"), "# fix the bug");
+        assert_eq!(as_shell_comment("one\n\ntwo"), "# one\n#\n# two");
+    }
+
+    #[test]
+    fn test_file_reference_styles() {
+        let dir = std::env::temp_dir().join(format!("mai-file-ref-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("notes.md");
+        std::fs::write(&file, "first\nsecond\n").unwrap();
+        let path = file.to_string_lossy().to_string();
+
+        assert_eq!(
+            file_reference(&path, FileReferenceStyle::AtPath),
+            Ok(format!("@{}", path))
+        );
+        assert_eq!(
+            file_reference(&path, FileReferenceStyle::Inline),
+            Ok(format!("```{}\nfirst\nsecond\n```", path))
+        );
+        assert!(file_reference(&dir.to_string_lossy(), FileReferenceStyle::Inline).is_err());
+        assert!(file_reference(&format!("{}/missing.md", dir.display()), FileReferenceStyle::AtPath).is_err());
+        assert!(file_reference("  ", FileReferenceStyle::AtPath).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_worktree_diff_for_selected_app() {
+        let mut state = TuiState::new(
+            vec!["proj-feature".to_string()],
+            vec![app("claude"), app("codex")],
+            UltrathinkSeparator::default(),
+        );
+        state.project_name = "proj".to_string();
+        state.worktrees_path = PathBuf::from("/wt");
+
+        // "All Tools" has no single worktree
+        assert!(state.selected_worktree().is_err());
+        state.app_list_state.select(Some(2));
+        assert_eq!(state.selected_worktree(), Ok(PathBuf::from("/wt/feature-codex")));
+
+        let dir = std::env::temp_dir().join(format!("mai-worktree-diff-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git").args(args).current_dir(&dir).output().unwrap().status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        git(&["init", "-q"]);
+        std::fs::write(dir.join("a.txt"), "one\n").unwrap();
+        git(&["add", "a.txt"]);
+        git(&["-c", "user.name=t", "-c", "user.email=t@t", "commit", "-q", "-m", "a"]);
+        assert!(worktree_diff(&dir).is_err());
+
+        std::fs::write(dir.join("a.txt"), "two\n").unwrap();
+        let diff = worktree_diff(&dir).unwrap();
+        assert!(diff.starts_with("```diff\n"));
+        assert!(diff.contains("-one\n+two"));
+        assert!(diff.ends_with("\n```"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_repeat_to_next_app_wraps_past_all_tools() {
+        let mut state = TuiState::new(
+            vec!["s".to_string()],
+            vec![app("claude"), app("codex")],
+            UltrathinkSeparator::default(),
+        );
+        assert!(state.repeat_to_next_app().is_none());
+
+        state.last_send = Some(LastSend {
+            text: "fix it".to_string(),
+            target_type: TargetType::Prompt,
+            ultrathink: true,
+        });
+        state.app_list_state.select(Some(1));
+        state.input = "something else".to_string();
+
+        let action = state.repeat_to_next_app().unwrap();
+        assert_eq!(action.app_index, Some(1));
+        assert_eq!(action.text, "fix it");
+        assert!(action.ultrathink);
+        assert_eq!(state.app_list_state.selected(), Some(2));
+
+        // Past the last app it wraps to the first, not to "All Tools"
+        assert_eq!(state.repeat_to_next_app().unwrap().app_index, Some(0));
+        assert_eq!(state.app_list_state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_broadcast_reports_pane_count() {
+        let mut state = TuiState::new(vec![], vec![], UltrathinkSeparator::default());
+        state.record_send(SendOutcome {
+            targets: 3,
+            sent: 2,
+            failures: vec!["gemini: no prompt pane to send to".to_string()],
+        });
+        assert_eq!(state.notice.as_deref(), Some("Sent to 2 of 3 panes"));
+        assert_eq!(state.last_error.as_deref(), Some("gemini: no prompt pane to send to"));
+
+        // A single target needs no count
+        state.notice = None;
+        state.record_send(SendOutcome { targets: 1, sent: 1, failures: vec![] });
+        assert_eq!(state.notice, None);
+        assert_eq!(state.last_error, None);
+    }
+
+    #[test]
+    fn test_send_failures_keep_history() {
+        assert_eq!(utc_timestamp(UNIX_EPOCH + Duration::from_secs(1_760_000_000)), "2025-10-09 08:53:20Z");
+        assert_eq!(utc_timestamp(UNIX_EPOCH + Duration::from_secs(951_782_400)), "2000-02-29 00:00:00Z");
+
+        let mut state = TuiState::new(vec![], vec![], UltrathinkSeparator::default());
+        state.record_send_failures(vec!["pane %1 gone".to_string(), "pane %2 gone".to_string()]);
+        assert_eq!(state.last_error.as_deref(), Some("pane %1 gone (and 1 more)"));
+
+        // A clean send clears the status line but not the log
+        state.record_send_failures(vec![]);
+        assert_eq!(state.last_error, None);
+        assert_eq!(state.error_log.entries.len(), 2);
+
+        for i in 0..MAX_ERROR_LOG_ENTRIES {
+            state.record_send_failures(vec![format!("failure {}", i)]);
+        }
+        assert_eq!(state.error_log.entries.len(), MAX_ERROR_LOG_ENTRIES);
+        assert_eq!(state.error_log.entries[0].1, "failure 0");
+    }
+
+    #[test]
+    fn test_last_user_input() {
+        let claude = "> refactor the parser\n  and keep the tests\n\n⏺ Done.\n\n╭────────╮\n│ >      │\n╰────────╯\n  ? for shortcuts\n";
+        assert_eq!(
+            last_user_input(claude).as_deref(),
+            Some("refactor the parser\nand keep the tests")
+        );
+
+        let shell = "$ cargo test\ntest result: ok\n$ \n";
+        assert_eq!(last_user_input(shell).as_deref(), Some("cargo test"));
+        let shell = "me@host:~/repo$ git status\nnothing to commit\nme@host:~/repo$ \n";
+        assert_eq!(last_user_input(shell).as_deref(), Some("git status"));
+        // A markdown heading in agent output is not a root prompt
+        assert_eq!(last_user_input("> fix it\n# Summary\n").as_deref(), Some("fix it"));
+
+        // No prompt marker: the last non-empty line
+        assert_eq!(last_user_input("hello\nworld\n\n").as_deref(), Some("world"));
+        assert_eq!(last_user_input("\n\n"), None);
+    }
+
+    #[test]
+    fn test_stale_send_buffers() {
+        let names = "mai-send-100-0\nmai-send-200-3\nmai-send-100\nbuffer0\nmai-send-x\n";
+        let running = |pid| pid == 200;
+        assert_eq!(
+            stale_send_buffers(names, running),
+            vec!["mai-send-100-0", "mai-send-100", "mai-send-x"]
+        );
+
+        // pid 1 belongs to root, where `kill -0` from another user gets EPERM
+        assert!(process_running(std::process::id()));
+        assert!(process_running(1));
+    }
+
+    #[test]
+    fn test_sentinel_answered() {
+        let text = "Fix the tests.\nReply DONE when finished.\n";
+        let before = "$ claude\n> \n\n\n";
+        let skip = last_non_empty_line(before);
+
+        // The prompt is echoed twice (typeahead, then the app's own redraw)
+        let echoed = "$ claude\n> Fix the tests.\nReply DONE when finished.\n\nFix the tests.\nReply DONE when finished.\nWorking...\n";
+        assert!(!sentinel_answered(echoed, skip, text, "DONE"));
+        let answered = format!("{}All tests pass. DONE\n", echoed);
+        assert!(sentinel_answered(&answered, skip, text, "DONE"));
+
+        // Output from before the send doesn't count
+        let old = "DONE\n$ claude\n> \n";
+        let old_skip = last_non_empty_line(old);
+        assert!(!sentinel_answered(&format!("{}Working...\n", old), old_skip, text, "DONE"));
+
+        // Rewrapped echo: fall back to counting copies
+        let rewrapped = "> Fix the tests. Reply DONE\n  when finished.\n";
+        assert!(!sentinel_answered(rewrapped, 0, text, "DONE"));
+        assert!(sentinel_answered(&format!("{}DONE\n", rewrapped), 0, text, "DONE"));
+    }
+
+    #[test]
+    fn test_primary_sessions_skips_grouped_views() {
+        let output = "proj-feat\tproj-feat\nproj-feat-view\tproj-feat\nproj-fix\t\nother\n";
+        assert_eq!(primary_sessions(output), ["proj-feat", "proj-fix", "other"]);
+    }
+
+    #[test]
+    fn test_iterm2_columns_keep_gaps_for_closed_columns() {
+        let pane = |id: &str, session: &str, column: usize, row: usize, role: PaneRole| ITerm2Pane {
+            id: id.to_string(),
+            session: session.to_string(),
+            column,
+            row,
+            role: Some(role),
+        };
+        // Column 1 of proj-feat was closed by hand; its shell pane order is scrambled
+        let panes = vec![
+            pane("c", "proj-feat", 2, 1, PaneRole::Shell),
+            pane("a", "proj-feat", 0, 0, PaneRole::Ai),
+            pane("b", "proj-feat", 2, 0, PaneRole::Ai),
+            pane("x", "other-main", 0, 0, PaneRole::Ai),
+        ];
+        assert_eq!(iterm2_sessions(&panes, "proj"), ["proj-feat"]);
+        assert_eq!(iterm2_sessions(&panes, "missing"), ["other-main", "proj-feat"]);
+
+        let columns = iterm2_columns(panes, "proj-feat");
+        let ids: Vec<Vec<&str>> = columns
+            .iter()
+            .map(|column| column.iter().map(|pane| pane.id.as_str()).collect())
+            .collect();
+        assert_eq!(ids, vec![vec!["a"], vec![], vec!["b", "c"]]);
+        assert!(find_target_pane(TargetType::Prompt, &columns[1], None).is_none());
+        assert_eq!(find_target_pane(TargetType::Command, &columns[2], None).unwrap().id, "c");
+    }
+}
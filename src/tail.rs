@@ -0,0 +1,231 @@
+use crate::error::{MultiAiError, Result};
+use crate::send::{group_panes_into_columns, TmuxPane};
+use crate::util;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Lines of existing output shown per pane when tailing starts
+const INITIAL_LINES: usize = 10;
+
+/// An AI pane being followed, with how many of its lines were already printed.
+struct TailedPane {
+    label: String,
+    id: String,
+    seen: usize,
+}
+
+/// Stream new output from every AI pane of `{project}-{prefix}` until Ctrl+C.
+pub fn run_tail(project_name: &str, branch_prefix: &str) -> Result<()> {
+    let session = format!("{}-{}", project_name, branch_prefix);
+    let mut panes = find_ai_panes(&session, branch_prefix)?;
+    if panes.is_empty() {
+        return Err(MultiAiError::Tmux(format!(
+            "No panes found in session '{}'",
+            session
+        )));
+    }
+
+    // Ctrl+C stops tailing rather than exiting
+    let _interrupts = util::catch_interrupts(None);
+
+    let width = panes.iter().map(|p| p.label.len()).max().unwrap_or(0);
+    println!(
+        "Tailing {} pane(s) in '{}' (Ctrl+C to stop)...",
+        panes.len(),
+        session
+    );
+
+    let mut first_poll = true;
+    while !util::interrupted() {
+        for pane in &mut panes {
+            let Ok(content) = capture_pane(&pane.id) else {
+                continue;
+            };
+            let lines: Vec<&str> = content.lines().collect();
+            if first_poll {
+                pane.seen = stable_line_count(&lines).saturating_sub(INITIAL_LINES);
+            }
+            let (new_lines, seen) = new_stable_lines(&lines, pane.seen);
+            for line in new_lines {
+                println!("[{:<width$}] {}", pane.label, line, width = width);
+            }
+            pane.seen = seen;
+        }
+        first_poll = false;
+
+        if !session_exists(&session) {
+            println!("Session '{}' ended.", session);
+            break;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    Ok(())
+}
+
+/// Number of lines that are done being written: trailing blank lines are ignored
+/// and the last non-empty line is held back since it may still be in progress.
+fn stable_line_count(lines: &[&str]) -> usize {
+    let len = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |i| i + 1);
+    len.saturating_sub(1)
+}
+
+/// Lines past `seen` that are stable, plus the new seen count. If the pane was
+/// cleared (fewer lines than seen), resync without reprinting what is left.
+fn new_stable_lines<'a>(lines: &[&'a str], seen: usize) -> (Vec<&'a str>, usize) {
+    let stable = stable_line_count(lines);
+    (lines[seen.min(stable)..stable].to_vec(), stable)
+}
+
+pub(crate) fn capture_pane(pane_id: &str) -> Result<String> {
+    capture(pane_id, &["-J", "-S", "-"])
+}
+
+/// Just the visible screen of a pane, row by row, without its scrollback
+pub(crate) fn capture_visible_pane(pane_id: &str) -> Result<String> {
+    capture(pane_id, &[])
+}
+
+fn capture(pane_id: &str, options: &[&str]) -> Result<String> {
+    let output = Command::new("tmux")
+        .args(["capture-pane", "-p"])
+        .args(options)
+        .args(["-t", pane_id])
+        .output()
+        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to capture pane: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(MultiAiError::Tmux(format!(
+            "Failed to capture pane {}",
+            pane_id
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn session_exists(session: &str) -> bool {
+    Command::new("tmux")
+        .args(["has-session", "-t", session])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Find the AI pane of each app: the top pane of every column in the `apps`
+/// window (single-window layout), or the left pane of each other window
+/// (multi-window layout). Labels come from the worktree directory slug.
+fn find_ai_panes(session: &str, branch_prefix: &str) -> Result<Vec<TailedPane>> {
+    let output = Command::new("tmux")
+        .args([
+            "list-panes",
+            "-s",
+            "-t",
+            session,
+            "-F",
+            "#{window_index}\t#{window_name}\t#{pane_id}\t#{pane_left}\t#{pane_top}\t#{pane_current_path}",
+        ])
+        .output()
+        .map_err(|e| MultiAiError::CommandFailed(format!("Failed to list panes: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(MultiAiError::Tmux(format!(
+            "Session '{}' not found. Start it with 'mai add {}' first.",
+            session, branch_prefix
+        )));
+    }
+
+    // window index -> (window name, panes with their current path)
+    let mut windows: BTreeMap<usize, (String, Vec<(TmuxPane, String)>)> = BTreeMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let parts: Vec<&str> = line.split('\t').collect();
+        if parts.len() != 6 {
+            continue;
+        }
+        let pane = TmuxPane {
+            id: parts[2].to_string(),
+            x: parts[3].parse().unwrap_or(0),
+            y: parts[4].parse().unwrap_or(0),
+            role: None,
+            app: None,
+        };
+        windows
+            .entry(parts[0].parse().unwrap_or(0))
+            .or_insert_with(|| (parts[1].to_string(), Vec::new()))
+            .1
+            .push((pane, parts[5].to_string()));
+    }
+
+    let label_for = |id: &str, panes: &[(TmuxPane, String)], fallback: &str| {
+        panes
+            .iter()
+            .find(|(p, _)| p.id == id)
+            .and_then(|(_, path)| Path::new(path).file_name())
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(&format!("{}-", branch_prefix)))
+            .map(str::to_string)
+            .unwrap_or_else(|| fallback.to_string())
+    };
+
+    let mut tailed = Vec::new();
+    for (window_name, panes) in windows.values() {
+        let tmux_panes: Vec<TmuxPane> = panes.iter().map(|(p, _)| p.clone()).collect();
+        if window_name == "apps" {
+            for (i, column) in group_panes_into_columns(&tmux_panes).iter().enumerate() {
+                if let Some(top) = column.first() {
+                    tailed.push(TailedPane {
+                        label: label_for(&top.id, panes, &format!("col{}", i + 1)),
+                        id: top.id.clone(),
+                        seen: 0,
+                    });
+                }
+            }
+        } else if let Some(first) = tmux_panes.iter().min_by_key(|p| (p.x, p.y)) {
+            tailed.push(TailedPane {
+                label: label_for(&first.id, panes, window_name),
+                id: first.id.clone(),
+                seen: 0,
+            });
+        }
+    }
+
+    Ok(tailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{new_stable_lines, stable_line_count};
+
+    #[test]
+    fn test_stable_line_count_holds_back_last_line() {
+        assert_eq!(stable_line_count(&["a", "b", "typing", "", ""]), 2);
+        assert_eq!(stable_line_count(&["only"]), 0);
+        assert_eq!(stable_line_count(&[]), 0);
+    }
+
+    #[test]
+    fn test_new_stable_lines() {
+        let lines = ["one", "two", "three", "partial"];
+        let (new, seen) = new_stable_lines(&lines, 1);
+        assert_eq!(new, vec!["two", "three"]);
+        assert_eq!(seen, 3);
+
+        let (new, seen) = new_stable_lines(&lines, 3);
+        assert!(new.is_empty());
+        assert_eq!(seen, 3);
+    }
+
+    #[test]
+    fn test_new_stable_lines_after_clear() {
+        let (new, seen) = new_stable_lines(&["fresh", "prompt"], 40);
+        assert!(new.is_empty());
+        assert_eq!(seen, 1);
+    }
+}
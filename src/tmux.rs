@@ -1,26 +1,143 @@
-use crate::config::{AiApp, TmuxLayout};
+use crate::config::{shell_quote, AiApp, AppKind, Mode, TmuxLayout};
 use crate::error::{MultiAiError, Result};
+use crate::send::{get_panes, group_panes_into_columns, TmuxPane};
+use std::path::PathBuf;
 use std::process::Command;
 use std::thread;
 use std::time::Duration;
 
+/// How many times `attach_session` checks for the session before giving up
+const ATTACH_ATTEMPTS: u32 = 3;
+const ATTACH_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// tmux user option naming what each pane mai creates is for. `mai send` reads it to
+/// find targets by role rather than by position.
+pub const PANE_ROLE_OPTION: &str = "@mai_role";
+
+/// tmux user option naming the app a pane belongs to. `mai send --app` reads it to map
+/// columns to apps, since `--app-order`, `--select` and wide apps change the layout.
+pub const PANE_APP_OPTION: &str = "@mai_app";
+
+/// Appended to the session name for the grouped session `add --group` creates
+const GROUPED_SESSION_SUFFIX: &str = "-view";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneRole {
+    /// Runs the AI app
+    Ai,
+    /// Runs an editor app (`kind: "editor"`)
+    Editor,
+    /// Plain shell in the worktree
+    Shell,
+    /// Shell in one of the extra columns of an app with `columns` above 1; never a
+    /// send target
+    Extra,
+}
+
+impl PaneRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PaneRole::Ai => "ai",
+            PaneRole::Editor => "editor",
+            PaneRole::Shell => "shell",
+            PaneRole::Extra => "extra",
+        }
+    }
+
+    /// Role of the top pane of `app`'s column
+    pub fn for_app(app: &AiApp) -> Self {
+        match app.kind {
+            AppKind::Ai => PaneRole::Ai,
+            AppKind::Editor => PaneRole::Editor,
+            AppKind::Shell => PaneRole::Shell,
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "ai" => Some(PaneRole::Ai),
+            "editor" => Some(PaneRole::Editor),
+            "shell" => Some(PaneRole::Shell),
+            "extra" => Some(PaneRole::Extra),
+            _ => None,
+        }
+    }
+}
+
 pub struct TmuxManager {
     session_name: String,
+    branch_prefix: String,
+    log_dir: Option<PathBuf>,
+    label: Option<String>,
+    /// Size of each shell pane as a percentage of its app's space
+    shell_pane_percent: u8,
+    /// How long a new pane's shell gets to start before the app command is typed
+    pane_init_delay: Duration,
+    /// Name or slug of the app whose window or column is selected once the session exists
+    focus_app: Option<String>,
 }
 
 impl TmuxManager {
     pub fn new(project_name: &str, branch_prefix: &str) -> Self {
         let session_name = format!("{}-{}", project_name, branch_prefix);
-        Self { session_name }
+        Self {
+            session_name,
+            branch_prefix: branch_prefix.to_string(),
+            log_dir: None,
+            label: None,
+            shell_pane_percent: 50,
+            pane_init_delay: Duration::from_millis(500),
+            focus_app: None,
+        }
+    }
+
+    pub fn session_name(&self) -> &str {
+        &self.session_name
+    }
+
+    /// Tee each AI pane's output to `<log_dir>/<branch-prefix>-<app>.log` (`/` in the
+    /// prefix becomes `-`)
+    pub fn with_log_dir(mut self, log_dir: Option<&str>) -> Self {
+        self.log_dir = log_dir.map(|dir| PathBuf::from(shellexpand::tilde(dir).as_ref()));
+        self
+    }
+
+    /// Show `label` as the terminal title while attached, instead of the session name
+    pub fn with_label(mut self, label: Option<&str>) -> Self {
+        self.label = label.map(str::to_string);
+        self
+    }
+
+    /// Give each AI pane `ratio` percent of its app's space and the shell pane the rest
+    /// (`pane_split_ratio`; an even split when `None`)
+    pub fn with_pane_split_ratio(mut self, ratio: Option<u8>) -> Self {
+        self.shell_pane_percent = 100 - ratio.unwrap_or(50);
+        self
+    }
+
+    /// Wait `delay` for each new pane's shell before typing the app command
+    /// (`pane_init_delay_ms`)
+    pub fn with_pane_init_delay(mut self, delay: Duration) -> Self {
+        self.pane_init_delay = delay;
+        self
+    }
+
+    /// Open the session on the app named (or slugged) `app` instead of the first one
+    /// (`focus_app`); ignored when no app matches
+    pub fn with_focus_app(mut self, app: Option<&str>) -> Self {
+        self.focus_app = app.map(str::to_string);
+        self
     }
 
+    /// Create the session and launch each app. Returns the AI pane ids in app order,
+    /// leaving out editor and shell apps.
     pub fn create_session(
-        &self,
+        &mut self,
         _ai_apps: &[AiApp],
         worktree_paths: &[(AiApp, String)],
         layout: TmuxLayout,
-    ) -> Result<()> {
-        if !self.is_tmux_installed() {
+    ) -> Result<Vec<String>> {
+        if !Self::is_tmux_installed() {
             return Err(MultiAiError::Tmux("tmux is not installed".to_string()));
         }
 
@@ -37,24 +154,51 @@ impl TmuxManager {
             ));
         }
 
-        match layout {
+        if let Some(dir) = &self.log_dir
+            && let Err(e) = std::fs::create_dir_all(dir)
+        {
+            eprintln!(
+                "  ⚠ Could not create log_dir {}: {} (pane logging disabled)",
+                dir.display(),
+                e
+            );
+            self.log_dir = None;
+        }
+
+        let focus = focus_index(worktree_paths, self.focus_app.as_deref());
+        let top_panes = match layout {
             TmuxLayout::MultiWindow => {
                 let first = &worktree_paths[0];
-                self.create_initial_window(&first.0, &first.1)?;
+                let mut ai_panes = vec![self.create_initial_window(&first.0, &first.1)?];
 
                 for (ai_app, worktree_path) in worktree_paths.iter().skip(1) {
-                    self.add_window(ai_app, worktree_path)?;
+                    ai_panes.push(self.add_window(ai_app, worktree_path)?);
                 }
 
-                self.select_window_by_name(&worktree_paths[0].0)?;
+                self.select_window_by_name(&worktree_paths[focus].0)?;
+                ai_panes
             }
             TmuxLayout::SingleWindow => {
-                self.create_single_window(worktree_paths)?;
+                let ai_panes = self.create_single_window(worktree_paths)?;
                 self.select_window("apps")?;
+                // Columns follow app order, so the focused app's top pane is at `focus`
+                self.select_pane(&ai_panes[focus])?;
+                ai_panes
             }
+        };
+
+        if let Some(label) = &self.label
+            && let Err(e) = self.set_title(label)
+        {
+            eprintln!("  ⚠ Could not set session title: {}", e);
         }
 
-        Ok(())
+        Ok(top_panes
+            .into_iter()
+            .zip(worktree_paths)
+            .filter(|(_, (app, _))| app.is_ai())
+            .map(|(pane, _)| pane)
+            .collect())
     }
 
     fn select_window(&self, window: &str) -> Result<()> {
@@ -78,7 +222,7 @@ impl TmuxManager {
         Ok(())
     }
 
-    fn create_initial_window(&self, ai_app: &AiApp, worktree_path: &str) -> Result<()> {
+    fn create_initial_window(&self, ai_app: &AiApp, worktree_path: &str) -> Result<String> {
         let output = Command::new("tmux")
             .args([
                 "new-session",
@@ -103,12 +247,10 @@ impl TmuxManager {
             )));
         }
 
-        self.split_window_for_ai(ai_app, worktree_path)?;
-
-        Ok(())
+        self.split_window_for_ai(ai_app, worktree_path)
     }
 
-    fn add_window(&self, ai_app: &AiApp, worktree_path: &str) -> Result<()> {
+    fn add_window(&self, ai_app: &AiApp, worktree_path: &str) -> Result<String> {
         let output = Command::new("tmux")
             .args([
                 "new-window",
@@ -130,12 +272,11 @@ impl TmuxManager {
             )));
         }
 
-        self.split_window_for_ai(ai_app, worktree_path)?;
-
-        Ok(())
+        self.split_window_for_ai(ai_app, worktree_path)
     }
 
-    fn split_window_for_ai(&self, ai_app: &AiApp, worktree_path: &str) -> Result<()> {
+    /// Returns the id of the pane running the app
+    fn split_window_for_ai(&self, ai_app: &AiApp, worktree_path: &str) -> Result<String> {
         // Capture the current (left) pane id before split so we can target it robustly
         let left_pane_id = self.current_pane_id(ai_app)?;
 
@@ -149,7 +290,10 @@ impl TmuxManager {
                 "-c",
                 worktree_path,
                 "-p",
-                "50",
+                &self.shell_pane_percent.to_string(),
+                "-P",
+                "-F",
+                "#{pane_id}",
             ])
             .output()
             .map_err(|e| MultiAiError::CommandFailed(format!("Failed to split window: {}", e)))?;
@@ -162,11 +306,17 @@ impl TmuxManager {
             )));
         }
 
+        let shell_pane_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        self.tag_pane(&left_pane_id, PaneRole::for_app(ai_app), Some(&ai_app.name));
+        self.tag_pane(&shell_pane_id, PaneRole::Shell, Some(&ai_app.name));
+
         // Wait for shell to initialize
-        thread::sleep(Duration::from_millis(500));
+        thread::sleep(self.pane_init_delay);
 
-        // Launch the AI app in the left/original pane by id
-        let launch_command = format!("cd {} && {}", worktree_path, ai_app.command());
+        self.start_pane_log(&left_pane_id, ai_app);
+
+        // Launch the app in the left/original pane by id
+        let launch_command = ai_app.launch_line(worktree_path, &Mode::TmuxMultiWindow);
         let output = Command::new("tmux")
             .args(["send-keys", "-t", &left_pane_id, &launch_command, "Enter"])
             .output()
@@ -180,6 +330,23 @@ impl TmuxManager {
             )));
         }
 
+        Ok(left_pane_id)
+    }
+
+    fn select_pane(&self, pane_id: &str) -> Result<()> {
+        let output = Command::new("tmux")
+            .args(["select-pane", "-t", pane_id])
+            .output()
+            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to select pane: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(MultiAiError::Tmux(format!(
+                "Failed to select pane: {}",
+                stderr
+            )));
+        }
+
         Ok(())
     }
 
@@ -204,7 +371,62 @@ impl TmuxManager {
         Ok(())
     }
 
+    /// Create `<session>-view`, grouped with this session: it shares the windows but
+    /// keeps its own current window, so another terminal can follow a different app.
+    /// Returns the new session's name.
+    pub fn create_grouped_session(&self) -> Result<String> {
+        let view = format!("{}{}", self.session_name, GROUPED_SESSION_SUFFIX);
+        let output = Command::new("tmux")
+            .args(["new-session", "-d", "-t", &self.session_name, "-s", &view])
+            .output()
+            .map_err(|e| {
+                MultiAiError::CommandFailed(format!("Failed to create grouped session: {}", e))
+            })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(MultiAiError::Tmux(format!(
+                "Failed to create grouped session '{}': {}",
+                view,
+                stderr.trim()
+            )));
+        }
+
+        Ok(view)
+    }
+
+    /// Other sessions in this session's group (e.g. from `add --group`)
+    fn grouped_sessions(&self) -> Vec<String> {
+        let Ok(output) = Command::new("tmux")
+            .args(["list-sessions", "-F", "#{session_name}\t#{session_group}"])
+            .output()
+        else {
+            return Vec::new();
+        };
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .filter(|(name, group)| *group == self.session_name && *name != self.session_name)
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+
     pub fn attach_session(&self) -> Result<()> {
+        // A just-created session can take a moment to register with the server;
+        // wait for it rather than failing with "can't find session"
+        let mut attempts = 0;
+        while !self.session_exists()? {
+            attempts += 1;
+            if attempts >= ATTACH_ATTEMPTS {
+                return Err(MultiAiError::Tmux(format!(
+                    "Session '{}' not found",
+                    self.session_name
+                )));
+            }
+            thread::sleep(ATTACH_RETRY_DELAY);
+        }
+
         let output = Command::new("tmux")
             .args(["attach-session", "-t", &self.session_name])
             .spawn()
@@ -225,7 +447,7 @@ impl TmuxManager {
         Ok(())
     }
 
-    fn session_exists(&self) -> Result<bool> {
+    pub fn session_exists(&self) -> Result<bool> {
         let output = Command::new("tmux")
             .args(["has-session", "-t", &self.session_name])
             .output()
@@ -234,8 +456,26 @@ impl TmuxManager {
         Ok(output.status.success())
     }
 
+    /// TTYs of the clients currently attached to this session (empty if it isn't running)
+    pub fn attached_clients(&self) -> Result<Vec<String>> {
+        let output = Command::new("tmux")
+            .args(["list-clients", "-t", &self.session_name, "-F", "#{client_tty}"])
+            .output()
+            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to list clients: {}", e)))?;
+
+        if !output.status.success() {
+            return Ok(Vec::new());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
     pub fn kill_session(&self) -> Result<()> {
-        if !self.is_tmux_installed() {
+        if !Self::is_tmux_installed() {
             return Err(MultiAiError::Tmux("tmux is not installed".to_string()));
         }
 
@@ -244,6 +484,13 @@ impl TmuxManager {
             return Ok(());
         }
 
+        // Grouped sessions would otherwise keep the windows and their apps running
+        for grouped in self.grouped_sessions() {
+            let _ = Command::new("tmux")
+                .args(["kill-session", "-t", &grouped])
+                .output();
+        }
+
         let output = Command::new("tmux")
             .args(["kill-session", "-t", &self.session_name])
             .output()
@@ -262,7 +509,7 @@ impl TmuxManager {
         Ok(())
     }
 
-    fn is_tmux_installed(&self) -> bool {
+    pub fn is_tmux_installed() -> bool {
         Command::new("tmux")
             .arg("-V")
             .output()
@@ -318,9 +565,14 @@ impl TmuxManager {
         Ok(id)
     }
 
-    fn create_single_window(&self, worktree_paths: &[(AiApp, String)]) -> Result<()> {
+    /// Returns the top (AI) pane id of each app's column, left to right
+    fn create_single_window(&self, worktree_paths: &[(AiApp, String)]) -> Result<Vec<String>> {
+        let Some(first) = worktree_paths.first() else {
+            return Err(MultiAiError::Tmux(
+                "No worktrees to create session for".to_string(),
+            ));
+        };
         // Create a detached session with a single window named 'apps'
-        let first = &worktree_paths[0];
         let window_name = "apps";
         let output = Command::new("tmux")
             .args([
@@ -345,18 +597,31 @@ impl TmuxManager {
             )));
         }
 
+        // One entry per column: the app's index, its worktree and whether the column is
+        // one of a wide app's extra shell columns
+        let columns: Vec<(usize, &str, bool)> = worktree_paths
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (app, path))| {
+                (0..app.columns()).map(move |column| (i, path.as_str(), column > 0))
+            })
+            .collect();
+
         // Capture the initial pane id (leftmost/first column)
-        let mut column_panes: Vec<String> = Vec::with_capacity(worktree_paths.len());
+        let mut column_panes: Vec<String> = Vec::with_capacity(columns.len());
         let leftmost_pane = self.current_pane_id_in_window(window_name)?;
         column_panes.push(leftmost_pane.clone());
 
         // Create additional columns by repeatedly splitting the LEFTMOST pane.
         // Using percentages based on the remaining column count yields equal-width columns.
         // We insert each newly created pane just to the right of the leftmost entry so that
-        // column_panes remains in left-to-right order matching worktree_paths.
-        for (idx, (_app, path)) in worktree_paths.iter().enumerate().skip(1) {
-            let total = worktree_paths.len();
+        // column_panes remains in left-to-right order matching `columns`, which puts the
+        // pane of the idx-th split at position total - idx.
+        // A single app keeps the whole window as its column and only gets the AI/shell split.
+        let total = columns.len();
+        for idx in 1..total {
             let percentage = self.calculate_split_percentage(idx, total);
+            let path = columns[total - idx].1;
 
             let output = Command::new("tmux")
                 .args([
@@ -387,11 +652,19 @@ impl TmuxManager {
             column_panes.insert(1, new_pane.clone());
         }
 
-        // For each column, split vertically to create shell pane and launch AI in the top pane
-        for (i, (ai_app, path)) in worktree_paths.iter().enumerate() {
-            let top_pane = &column_panes[i];
+        // For each app's column, split vertically to create shell pane and launch AI in the
+        // top pane; extra columns stay a single shell
+        for (&(app_index, path, extra), top_pane) in columns.iter().zip(&column_panes) {
+            if extra {
+                self.tag_pane(top_pane, PaneRole::Extra, Some(&worktree_paths[app_index].0.name));
+                continue;
+            }
+            let ai_app = &worktree_paths[app_index].0;
             let output = Command::new("tmux")
-                .args(["split-window", "-v", "-t", top_pane, "-c", path, "-p", "50"])
+                .args([
+                    "split-window", "-v", "-t", top_pane, "-c", path, "-p",
+                    &self.shell_pane_percent.to_string(), "-P", "-F", "#{pane_id}",
+                ])
                 .output()
                 .map_err(|e| MultiAiError::CommandFailed(format!("Failed to split row: {}", e)))?;
             if !output.status.success() {
@@ -402,11 +675,17 @@ impl TmuxManager {
                 )));
             }
 
+            let shell_pane = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            self.tag_pane(top_pane, PaneRole::for_app(ai_app), Some(&ai_app.name));
+            self.tag_pane(&shell_pane, PaneRole::Shell, Some(&ai_app.name));
+
             // Allow shell to initialize
-            thread::sleep(Duration::from_millis(500));
+            thread::sleep(self.pane_init_delay);
+
+            self.start_pane_log(top_pane, ai_app);
 
-            // Launch AI command in the top pane
-            let launch_command = format!("cd {} && {}", path, ai_app.command());
+            // Launch the app in the top pane
+            let launch_command = ai_app.launch_line(path, &Mode::TmuxSingleWindow);
             let output = Command::new("tmux")
                 .args(["send-keys", "-t", top_pane, &launch_command, "Enter"])
                 .output()
@@ -422,6 +701,274 @@ impl TmuxManager {
             }
         }
 
+        Ok(columns
+            .iter()
+            .zip(column_panes)
+            .filter(|((_, _, extra), _)| !extra)
+            .map(|(_, pane)| pane)
+            .collect())
+    }
+
+    /// Names of this session's windows, in index order
+    pub fn window_names(&self) -> Result<Vec<String>> {
+        let output = Command::new("tmux")
+            .args(["list-windows", "-t", &self.session_name, "-F", "#{window_name}"])
+            .output()
+            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to list windows: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(MultiAiError::Tmux(format!(
+                "Failed to list windows of {}: {}",
+                self.session_name,
+                stderr.trim()
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Panes of each app, its AI pane first and then its shells: the columns of `apps`
+    /// in a single-window session, or one window per app in a multi-window one. The
+    /// extra columns of wide apps are left out.
+    pub fn app_panes(&self) -> Result<Vec<Vec<TmuxPane>>> {
+        let windows = self.window_names()?;
+        if windows.iter().any(|w| w == "apps") {
+            return Ok(single_window_app_panes(&get_panes(&self.session_name, "apps")?));
+        }
+        windows
+            .iter()
+            .map(|window| {
+                // The AI pane is on the left with the shells to its right
+                let mut panes = get_panes(&self.session_name, window)?;
+                panes.sort_by_key(|pane| (pane.role == Some(PaneRole::Shell), pane.x, pane.y));
+                Ok(panes)
+            })
+            .collect()
+    }
+
+    /// Split off or kill shell panes until every app in `apps` (from `app_panes`) has
+    /// `panes_per_app` panes. AI panes are never touched, so the agents keep running.
+    /// Returns how many shell panes were added and removed.
+    pub fn relayout(&self, apps: &[Vec<TmuxPane>], panes_per_app: usize) -> Result<(usize, usize)> {
+        let single_window = self.window_names()?.iter().any(|w| w == "apps");
+        let shells_wanted = panes_per_app.saturating_sub(1);
+        let (mut added, mut removed) = (0, 0);
+
+        for panes in apps {
+            let Some((ai_pane, shells)) = panes.split_first() else {
+                continue;
+            };
+            for extra in shells.iter().skip(shells_wanted) {
+                self.kill_pane(&extra.id)?;
+                removed += 1;
+            }
+
+            // Each split divides the last pane so it and the panes still to come end up
+            // the same size. The first shell of an app gets the usual shell share, below
+            // the AI pane in a column or beside it in the app's own window.
+            let missing = shells_wanted.saturating_sub(shells.len());
+            let mut last = shells.last().unwrap_or(ai_pane).id.clone();
+            for i in 0..missing {
+                let from_ai = shells.is_empty() && i == 0;
+                let direction = if from_ai && !single_window { "-h" } else { "-v" };
+                let remaining = missing - i;
+                let percent = if from_ai {
+                    self.shell_pane_percent as usize
+                } else {
+                    100 * remaining / (remaining + 1)
+                };
+                last = self.split_shell(&last, direction, percent, ai_pane.app.as_deref())?;
+                added += 1;
+            }
+        }
+        Ok((added, removed))
+    }
+
+    /// Split a shell pane of `app` off `pane_id` in the same directory and return its id
+    fn split_shell(
+        &self,
+        pane_id: &str,
+        direction: &str,
+        percent: usize,
+        app: Option<&str>,
+    ) -> Result<String> {
+        let output = Command::new("tmux")
+            .args([
+                "split-window", direction, "-t", pane_id, "-c", "#{pane_current_path}", "-p",
+                &percent.to_string(), "-P", "-F", "#{pane_id}",
+            ])
+            .output()
+            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to split pane: {}", e)))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(MultiAiError::Tmux(format!(
+                "Failed to split {}: {}",
+                pane_id,
+                stderr.trim()
+            )));
+        }
+
+        let shell_pane = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        self.tag_pane(&shell_pane, PaneRole::Shell, app);
+        Ok(shell_pane)
+    }
+
+    fn kill_pane(&self, pane_id: &str) -> Result<()> {
+        let output = Command::new("tmux")
+            .args(["kill-pane", "-t", pane_id])
+            .output()
+            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to kill pane: {}", e)))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(MultiAiError::Tmux(format!(
+                "Failed to kill {}: {}",
+                pane_id,
+                stderr.trim()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether `synchronize-panes` is on for `window`
+    pub fn synchronize(&self, window: &str) -> Result<bool> {
+        let target = format!("{}:{}", self.session_name, window);
+        let output = Command::new("tmux")
+            .args(["show-window-options", "-v", "-t", &target, "synchronize-panes"])
+            .output()
+            .map_err(|e| {
+                MultiAiError::CommandFailed(format!("Failed to read synchronize-panes: {}", e))
+            })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(MultiAiError::Tmux(format!(
+                "Failed to read synchronize-panes on {}: {}",
+                target,
+                stderr.trim()
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim() == "on")
+    }
+
+    /// Turn `synchronize-panes` on or off for `window`, so keys typed in one pane go
+    /// to every pane of it
+    pub fn set_synchronize(&self, window: &str, on: bool) -> Result<()> {
+        let target = format!("{}:{}", self.session_name, window);
+        let value = if on { "on" } else { "off" };
+        let output = Command::new("tmux")
+            .args(["set-window-option", "-t", &target, "synchronize-panes", value])
+            .output()
+            .map_err(|e| {
+                MultiAiError::CommandFailed(format!("Failed to set synchronize-panes: {}", e))
+            })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(MultiAiError::Tmux(format!(
+                "Failed to set synchronize-panes on {}: {}",
+                target,
+                stderr.trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Set a pane-level tmux option (`set-option -p`, tmux 3.0+)
+    pub fn set_pane_option(&self, pane_id: &str, option: &str, value: &str) -> Result<()> {
+        let output = Command::new("tmux")
+            .args(["set-option", "-p", "-t", pane_id, option, value])
+            .output()
+            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to set pane option: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(MultiAiError::Tmux(format!(
+                "Failed to set {} on {}: {}",
+                option,
+                pane_id,
+                stderr.trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Set the outer terminal title for this session only. Window names stay as they
+    /// are, since `mai send` and `mai tail` find panes through them.
+    fn set_title(&self, label: &str) -> Result<()> {
+        // The title string is a tmux format, so a literal '#' has to be doubled
+        let title = label.replace('#', "##");
+        for (option, value) in [("set-titles", "on"), ("set-titles-string", title.as_str())] {
+            let output = Command::new("tmux")
+                .args(["set-option", "-t", &self.session_name, option, value])
+                .output()
+                .map_err(|e| {
+                    MultiAiError::CommandFailed(format!("Failed to set session option: {}", e))
+                })?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(MultiAiError::Tmux(format!(
+                    "Failed to set {}: {}",
+                    option,
+                    stderr.trim()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record the pane's role and app for `mai send`. Best effort: without pane options
+    /// (tmux before 3.0) send falls back to finding panes by position.
+    fn tag_pane(&self, pane_id: &str, role: PaneRole, app: Option<&str>) {
+        let _ = self.set_pane_option(pane_id, PANE_ROLE_OPTION, role.as_str());
+        if let Some(app) = app {
+            let _ = self.set_pane_option(pane_id, PANE_APP_OPTION, app);
+        }
+    }
+
+    /// Start logging the AI pane when `log_dir` is set. Failures only warn, since the
+    /// session itself is still usable.
+    fn start_pane_log(&self, pane_id: &str, ai_app: &AiApp) {
+        let Some(dir) = &self.log_dir else {
+            return;
+        };
+        // Only agents are logged; an editor's screen redraws make a useless log
+        if !ai_app.is_ai() {
+            return;
+        }
+        // A slashed prefix (feature/login) would point into a missing subdirectory
+        let log_name = format!("{}-{}.log", self.branch_prefix.replace('/', "-"), ai_app.slug());
+        let log_path = dir.join(log_name);
+        let command = format!("cat >> {}", shell_quote(&log_path.to_string_lossy()));
+        if let Err(e) = self.pipe_pane(pane_id, &command) {
+            eprintln!("  ⚠ Could not log {}: {}", ai_app.as_str(), e);
+        }
+    }
+
+    /// Pipe a pane's output to a shell command (`tmux pipe-pane -o`)
+    fn pipe_pane(&self, pane_id: &str, command: &str) -> Result<()> {
+        let output = Command::new("tmux")
+            .args(["pipe-pane", "-o", "-t", pane_id, command])
+            .output()
+            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to pipe pane: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(MultiAiError::Tmux(format!(
+                "Failed to pipe pane: {}",
+                stderr
+            )));
+        }
+
         Ok(())
     }
 
@@ -434,3 +981,212 @@ impl TmuxManager {
         100 / remaining
     }
 }
+
+/// Position of the app matching `focus_app` by name or slug, or 0 (the first app) when
+/// it's unset or not among `worktree_paths`
+fn focus_index(worktree_paths: &[(AiApp, String)], focus_app: Option<&str>) -> usize {
+    focus_app
+        .and_then(|focus| {
+            worktree_paths
+                .iter()
+                .position(|(app, _)| app.name == focus || app.slug() == focus)
+        })
+        .unwrap_or(0)
+}
+
+/// `app_panes` of a single-window session: its columns, without the extra columns
+/// of wide apps
+fn single_window_app_panes(panes: &[TmuxPane]) -> Vec<Vec<TmuxPane>> {
+    group_panes_into_columns(panes)
+        .into_iter()
+        .map(|column| column.into_iter().filter(|pane| pane.role != Some(PaneRole::Extra)).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{focus_index, single_window_app_panes, PaneRole, TmuxManager};
+    use crate::config::{AiApp, TmuxLayout};
+    use crate::send::{find_target_pane, get_panes, group_panes_into_columns, TargetType, TmuxPane};
+
+    fn pane(id: &str, x: usize, y: usize, role: PaneRole, app: &str) -> TmuxPane {
+        TmuxPane { id: id.to_string(), x, y, role: Some(role), app: Some(app.to_string()) }
+    }
+
+    /// Widths of the columns produced by splitting the leftmost pane of a `width`-cell
+    /// window `total - 1` times, rounding each new pane down like tmux does
+    fn simulate_columns(width: usize, total: usize) -> Vec<usize> {
+        let manager = TmuxManager::new("project", "prefix");
+        let mut leftmost = width;
+        let mut columns = Vec::new();
+        for idx in 1..total {
+            let new_pane = leftmost * manager.calculate_split_percentage(idx, total) / 100;
+            leftmost -= new_pane;
+            columns.push(new_pane);
+        }
+        columns.push(leftmost);
+        columns
+    }
+
+    #[test]
+    fn test_focus_index() {
+        let apps: Vec<AiApp> = serde_json::from_str(
+            r#"[{"name": "claude", "command": "claude"}, {"name": "Codex CLI", "command": "codex", "slug": "codex"}]"#,
+        )
+        .unwrap();
+        let paths: Vec<(AiApp, String)> = apps.into_iter().map(|app| (app, String::new())).collect();
+        assert_eq!(focus_index(&paths, None), 0);
+        assert_eq!(focus_index(&paths, Some("Codex CLI")), 1);
+        assert_eq!(focus_index(&paths, Some("codex")), 1);
+        assert_eq!(focus_index(&paths, Some("gemini")), 0);
+    }
+
+    #[test]
+    fn test_split_percentages_for_four_columns() {
+        let manager = TmuxManager::new("project", "prefix");
+        let percentages: Vec<usize> = (1..4)
+            .map(|idx| manager.calculate_split_percentage(idx, 4))
+            .collect();
+        assert_eq!(percentages, vec![25, 33, 50]);
+    }
+
+    #[test]
+    fn test_split_percentages_give_near_equal_columns() {
+        for width in [80, 120, 200, 317] {
+            for total in 2..=8 {
+                let columns = simulate_columns(width, total);
+                assert_eq!(columns.iter().sum::<usize>(), width);
+                let ideal = width / total;
+                // Integer percentages lose at most a couple of cells per split; no
+                // column may end up starved
+                for &column in &columns {
+                    assert!(
+                        column + 3 >= ideal && column <= ideal + 3,
+                        "width {} with {} columns gave {:?}",
+                        width,
+                        total,
+                        columns
+                    );
+                }
+            }
+        }
+    }
+
+    /// Kills the test session even when an assertion fails
+    struct SessionGuard(TmuxManager);
+
+    impl Drop for SessionGuard {
+        fn drop(&mut self) {
+            let _ = self.0.kill_session();
+        }
+    }
+
+    #[test]
+    fn test_single_app_panes_form_one_column() {
+        // What `create_single_window` builds for one app: its AI pane over a shell
+        let panes = vec![pane("%1", 0, 20, PaneRole::Shell, "solo"), pane("%0", 0, 0, PaneRole::Ai, "solo")];
+        let columns = group_panes_into_columns(&panes);
+        assert_eq!(columns.len(), 1);
+        assert_eq!(find_target_pane(TargetType::Prompt, &columns[0], None).unwrap().id, "%0");
+        assert_eq!(find_target_pane(TargetType::Command, &columns[0], None).unwrap().id, "%1");
+    }
+
+    #[test]
+    #[ignore = "creates a real tmux session; run with `cargo test -- --ignored`"]
+    fn test_single_app_single_window_layout() {
+        let apps: Vec<AiApp> = serde_json::from_str(r#"[{"name": "solo", "command": "true"}]"#).unwrap();
+        let dir = std::env::temp_dir().to_string_lossy().into_owned();
+        let project = format!("mai-test-{}", std::process::id());
+        let mut manager = TmuxManager::new(&project, "single");
+        let ai_panes = manager
+            .create_session(&apps, &[(apps[0].clone(), dir)], TmuxLayout::SingleWindow)
+            .unwrap();
+        let session = SessionGuard(manager);
+
+        // One column: the AI pane on top and the shell below it
+        let panes = get_panes(&session.0.session_name, "apps").unwrap();
+        assert_eq!(panes.len(), 2);
+        let columns = group_panes_into_columns(&panes);
+        assert_eq!(columns.len(), 1);
+        assert_eq!(ai_panes.len(), 1);
+
+        let prompt = find_target_pane(TargetType::Prompt, &columns[0], None).unwrap();
+        let command = find_target_pane(TargetType::Command, &columns[0], None).unwrap();
+        assert_eq!(prompt.id, ai_panes[0]);
+        assert_ne!(command.id, prompt.id);
+        assert!(command.y > prompt.y);
+
+        // Relayout adds and removes shells below the AI pane, which stays put
+        let apps = session.0.app_panes().unwrap();
+        assert_eq!(session.0.relayout(&apps, 4).unwrap(), (2, 0));
+        let apps = session.0.app_panes().unwrap();
+        assert_eq!(apps[0].len(), 4);
+        assert_eq!(apps[0][0].id, ai_panes[0]);
+        assert_eq!(session.0.relayout(&apps, 1).unwrap(), (0, 3));
+        let panes = get_panes(&session.0.session_name, "apps").unwrap();
+        assert_eq!(panes.len(), 1);
+        assert_eq!(panes[0].id, ai_panes[0]);
+    }
+
+    #[test]
+    fn test_wide_app_panes_group_per_app() {
+        // `wide` has `columns: 2`: its AI pane over a shell, then a full-height extra shell
+        let panes = vec![
+            pane("%0", 0, 0, PaneRole::Ai, "wide"),
+            pane("%1", 0, 20, PaneRole::Shell, "wide"),
+            pane("%2", 54, 0, PaneRole::Extra, "wide"),
+            pane("%3", 108, 0, PaneRole::Ai, "narrow"),
+            pane("%4", 108, 20, PaneRole::Shell, "narrow"),
+        ];
+        let columns = group_panes_into_columns(&panes);
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0].len(), 3);
+        let prompts: Vec<&str> = columns
+            .iter()
+            .map(|column| find_target_pane(TargetType::Prompt, column, None).unwrap().id.as_str())
+            .collect();
+        assert_eq!(prompts, vec!["%0", "%3"]);
+
+        // Relayout never sees the extra column
+        let apps = single_window_app_panes(&panes);
+        let ids: Vec<Vec<&str>> = apps
+            .iter()
+            .map(|column| column.iter().map(|pane| pane.id.as_str()).collect())
+            .collect();
+        assert_eq!(ids, vec![vec!["%0", "%1"], vec!["%3", "%4"]]);
+    }
+
+    #[test]
+    #[ignore = "creates a real tmux session; run with `cargo test -- --ignored`"]
+    fn test_wide_app_single_window_layout() {
+        let apps: Vec<AiApp> = serde_json::from_str(
+            r#"[{"name": "wide", "command": "true", "columns": 2}, {"name": "narrow", "command": "true"}]"#,
+        )
+        .unwrap();
+        let dir = std::env::temp_dir().to_string_lossy().into_owned();
+        let project = format!("mai-test-{}", std::process::id());
+        let mut manager = TmuxManager::new(&project, "wide");
+        let worktree_paths: Vec<(AiApp, String)> =
+            apps.iter().map(|app| (app.clone(), dir.clone())).collect();
+        let ai_panes = manager
+            .create_session(&apps, &worktree_paths, TmuxLayout::SingleWindow)
+            .unwrap();
+        let session = SessionGuard(manager);
+
+        // Three columns on screen, read back as one group per app
+        let panes = get_panes(&session.0.session_name, "apps").unwrap();
+        assert_eq!(panes.len(), 5);
+        let columns = group_panes_into_columns(&panes);
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0].len(), 3);
+        assert_eq!(ai_panes.len(), 2);
+        for (column, ai_pane) in columns.iter().zip(&ai_panes) {
+            assert_eq!(&find_target_pane(TargetType::Prompt, column, None).unwrap().id, ai_pane);
+        }
+
+        // Relayout leaves the extra column alone
+        let apps = session.0.app_panes().unwrap();
+        assert_eq!(apps[0].len(), 2);
+        assert_eq!(session.0.relayout(&apps, 2).unwrap(), (0, 0));
+    }
+}
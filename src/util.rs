@@ -0,0 +1,214 @@
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::ListItem;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+static COLOR_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Set by the Ctrl+C handler while an `InterruptGuard` is alive
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+/// Whether a command handles Ctrl+C itself, and the notice printed when it comes
+static CATCHING: Mutex<Option<Option<&'static str>>> = Mutex::new(None);
+
+/// Install the process's only Ctrl+C handler (`ctrlc` allows one). Ctrl+C exits with
+/// status 130, unless an `InterruptGuard` is alive: then the first one only sets
+/// `interrupted()` so the command can stop cleanly, and a second one still exits.
+pub fn install_interrupt_handler() -> Result<(), ctrlc::Error> {
+    ctrlc::set_handler(|| {
+        let catching = *CATCHING.lock().unwrap_or_else(|e| e.into_inner());
+        match catching {
+            Some(notice) if !INTERRUPTED.swap(true, Ordering::SeqCst) => {
+                if let Some(notice) = notice {
+                    eprintln!("{}", notice);
+                }
+            }
+            _ => std::process::exit(130),
+        }
+    })
+}
+
+/// Handles Ctrl+C for the current command until dropped; see `install_interrupt_handler`
+pub struct InterruptGuard(());
+
+impl Drop for InterruptGuard {
+    fn drop(&mut self) {
+        *CATCHING.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// Catch Ctrl+C instead of exiting, printing `notice` when it comes. Clears any
+/// earlier interrupt, so each guard starts fresh.
+pub fn catch_interrupts(notice: Option<&'static str>) -> InterruptGuard {
+    INTERRUPTED.store(false, Ordering::SeqCst);
+    *CATCHING.lock().unwrap_or_else(|e| e.into_inner()) = Some(notice);
+    InterruptGuard(())
+}
+
+/// Whether Ctrl+C was pressed since the current `catch_interrupts`
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Turn off colors and emoji for the rest of the run (`--no-color` or `NO_COLOR`).
+pub fn disable_color() {
+    COLOR_DISABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn color_enabled() -> bool {
+    !COLOR_DISABLED.load(Ordering::Relaxed)
+}
+
+/// Whether the `NO_COLOR` convention (https://no-color.org) asks for plain output:
+/// the variable is set to a non-empty value.
+pub fn no_color_env() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// `Style::default().fg(color)`, or a plain style when color is disabled.
+pub fn fg(color: Color) -> Style {
+    if color_enabled() {
+        Style::default().fg(color)
+    } else {
+        Style::default()
+    }
+}
+
+/// `Style::default().bg(color)`, or reversed video when color is disabled so the
+/// highlighted row stays visible.
+pub fn bg(color: Color) -> Style {
+    if color_enabled() {
+        Style::default().bg(color)
+    } else {
+        Style::default().add_modifier(Modifier::REVERSED)
+    }
+}
+
+/// Style of the selected row in lists: black on gray, or reversed video.
+pub fn selected() -> Style {
+    bg(Color::Gray).patch(fg(Color::Black))
+}
+
+/// A status symbol for stdout messages such as `"✓ "`, or `""` when color is disabled.
+pub fn icon(symbol: &'static str) -> &'static str {
+    if color_enabled() { symbol } else { "" }
+}
+
+/// Where a shell would find `program`: the path itself when it contains a `/`, else
+/// the first match in a `PATH` directory
+pub fn find_program(program: &str) -> Option<PathBuf> {
+    if program.contains('/') {
+        let path = PathBuf::from(program);
+        return path.is_file().then_some(path);
+    }
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths)
+        .map(|dir| dir.join(program))
+        .find(|path| path.is_file())
+}
+
+/// `text` cut to at most `width` characters, ending in `…` when anything was cut
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    if width > 0 {
+        cut.push('…');
+    }
+    cut
+}
+
+/// `prefix` then `text`, continued on lines indented to the prefix's width whenever
+/// the text doesn't fit in `width` characters
+pub fn wrap_after(prefix: &str, text: &str, width: usize) -> Vec<String> {
+    let indent = prefix.chars().count();
+    let room = width.saturating_sub(indent).max(1);
+    let chars: Vec<char> = text.chars().collect();
+    if chars.is_empty() {
+        return vec![prefix.to_string()];
+    }
+    chars
+        .chunks(room)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let lead = if i == 0 { prefix.to_string() } else { " ".repeat(indent) };
+            format!("{}{}", lead, chunk.iter().collect::<String>())
+        })
+        .collect()
+}
+
+/// A row of a multi-select list: `label` after a checkbox, cut to the `width` of the
+/// list's inner area, or wrapped in full when `expanded` (the focused row)
+pub fn checkbox_item(checked: bool, label: &str, width: u16, expanded: bool) -> ListItem<'static> {
+    let prefix = if checked { " [x] " } else { " [ ] " };
+    let width = width as usize;
+    if expanded {
+        ListItem::new(wrap_after(prefix, label, width).into_iter().map(Line::from).collect::<Vec<_>>())
+    } else {
+        let room = width.saturating_sub(prefix.chars().count());
+        ListItem::new(format!("{}{}", prefix, truncate(label, room)))
+    }
+}
+
+/// Parse a duration like `30m`, `2h`, `7d` or `1w` (also `s` for seconds), as
+/// accepted by `--since`-style flags. Returns a message for clap on invalid input.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let invalid = || {
+        format!(
+            "invalid duration '{}': expected a whole number and a unit, e.g. 30m, 2h, 7d or 1w",
+            value
+        )
+    };
+    let split = value.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount.parse().map_err(|_| invalid())?;
+    let unit_secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    amount
+        .checked_mul(unit_secs)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("duration '{}' is too large", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_duration, truncate, wrap_after};
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30m"), Ok(Duration::from_secs(30 * 60)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::from_secs(2 * 3600)));
+        assert_eq!(parse_duration(" 7d "), Ok(Duration::from_secs(7 * 86400)));
+        assert_eq!(parse_duration("1w"), Ok(Duration::from_secs(604800)));
+        assert_eq!(parse_duration("0m"), Ok(Duration::ZERO));
+
+        for invalid in ["", "30", "m", "1.5h", "-2h", "2 h", "2H", "3y", "2h30m"] {
+            assert!(parse_duration(invalid).is_err(), "{:?} should be rejected", invalid);
+        }
+        assert!(parse_duration("99999999999999999w").is_err());
+    }
+
+    #[test]
+    fn test_truncate_and_wrap_labels() {
+        assert_eq!(truncate("claude --model opus", 30), "claude --model opus");
+        assert_eq!(truncate("claude --model opus", 10), "claude --…");
+        assert_eq!(truncate("claude", 0), "");
+
+        assert_eq!(wrap_after(" [x] ", "claude", 20), vec![" [x] claude"]);
+        assert_eq!(
+            wrap_after(" [x] ", "claude --model opus", 12),
+            vec![" [x] claude ", "     --model", "      opus"]
+        );
+    }
+}
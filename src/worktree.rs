@@ -2,6 +2,12 @@ use crate::error::{MultiAiError, Result};
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a gwt command may run before it's treated as stuck, e.g. on a prompt
+const GWT_TIMEOUT: Duration = Duration::from_secs(300);
+const GWT_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 pub struct WorktreeManager {
     project_path: PathBuf,
@@ -60,37 +66,34 @@ impl WorktreeManager {
 
         for entry in std::fs::read_dir(&gwt_projects_dir).ok()?.flatten() {
             let path = entry.path();
-            if path.extension().map(|e| e == "jsonc").unwrap_or(false) {
-                if let Ok(content) = std::fs::read_to_string(&path) {
-                    if let Ok(Some(serde_json::Value::Object(map))) =
-                        jsonc_parser::parse_to_serde_value(&content, &Default::default())
-                    {
-                        // Check if this config matches our project path
-                        let matches = if let Some(serde_json::Value::String(proj_path)) =
-                            map.get("projectPath")
-                        {
-                            let config_proj_path = PathBuf::from(proj_path);
-                            if let Some(ref proj_canonical) = project_path_canonical {
-                                if let Ok(config_canonical) = config_proj_path.canonicalize() {
-                                    proj_canonical == &config_canonical
-                                } else {
-                                    project_path == &config_proj_path
-                                }
-                            } else {
-                                project_path == &config_proj_path
-                            }
+            if path.extension().map(|e| e == "jsonc").unwrap_or(false)
+                && let Ok(content) = std::fs::read_to_string(&path)
+                && let Ok(Some(serde_json::Value::Object(map))) =
+                    jsonc_parser::parse_to_serde_value(&content, &Default::default())
+            {
+                // Check if this config matches our project path
+                let matches = if let Some(serde_json::Value::String(proj_path)) =
+                    map.get("projectPath")
+                {
+                    let config_proj_path = PathBuf::from(proj_path);
+                    if let Some(ref proj_canonical) = project_path_canonical {
+                        if let Ok(config_canonical) = config_proj_path.canonicalize() {
+                            proj_canonical == &config_canonical
                         } else {
-                            false
-                        };
-
-                        if matches {
-                            if let Some(serde_json::Value::String(wt_path)) =
-                                map.get("worktreesPath")
-                            {
-                                return Some(PathBuf::from(wt_path));
-                            }
+                            project_path == config_proj_path
                         }
+                    } else {
+                        project_path == config_proj_path
                     }
+                } else {
+                    false
+                };
+
+                if matches
+                    && let Some(serde_json::Value::String(wt_path)) =
+                        map.get("worktreesPath")
+                {
+                    return Some(PathBuf::from(wt_path));
                 }
             }
         }
@@ -104,10 +107,10 @@ impl WorktreeManager {
         }
         let content = std::fs::read_to_string(config_path).ok()?;
         let parsed = jsonc_parser::parse_to_serde_value(&content, &Default::default()).ok()??;
-        if let serde_json::Value::Object(map) = parsed {
-            if let Some(serde_json::Value::String(wt_path)) = map.get("worktreesPath") {
-                return Some(PathBuf::from(wt_path));
-            }
+        if let serde_json::Value::Object(map) = parsed
+            && let Some(serde_json::Value::String(wt_path)) = map.get("worktreesPath")
+        {
+            return Some(PathBuf::from(wt_path));
         }
         None
     }
@@ -121,61 +124,28 @@ impl WorktreeManager {
     }
 
     pub fn add_worktree(&self, branch_name: &str) -> Result<PathBuf> {
+        self.add_worktree_impl(branch_name, true)
+    }
+
+    pub fn add_worktree_quiet(&self, branch_name: &str) -> Result<PathBuf> {
+        self.add_worktree_impl(branch_name, false)
+    }
+
+    fn add_worktree_impl(&self, branch_name: &str, verbose: bool) -> Result<PathBuf> {
         let worktree_path = self.worktrees_path.join(branch_name);
 
-        if !self.has_gwt_cli() {
+        if !Self::has_gwt_cli() {
             return Err(MultiAiError::Worktree(
                 "gwt CLI is not installed or not in PATH".to_string(),
             ));
         }
 
-        let mut child = Command::new("gwt")
-            .arg("add")
-            .arg(branch_name)
-            .current_dir(&self.project_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to execute gwt: {}", e)))?;
-
-        // Stream stdout
-        if let Some(stdout) = child.stdout.take() {
-            let reader = BufReader::new(stdout);
-            for line in reader.lines().map_while(|r| r.ok()) {
-                println!("    {}", line);
-            }
-        }
-
-        // Wait for the process to complete and check status
-        let status = child
-            .wait()
-            .map_err(|e| MultiAiError::CommandFailed(format!("Failed to wait for gwt: {}", e)))?;
-
-        if !status.success() {
-            // Capture any stderr output
-            let mut stderr_msg = String::new();
-            if let Some(stderr) = child.stderr.take() {
-                let reader = BufReader::new(stderr);
-                for line in reader.lines().map_while(|r| r.ok()) {
-                    stderr_msg.push_str(&line);
-                    stderr_msg.push('\n');
-                }
-            }
-
-            return Err(MultiAiError::Worktree(format!(
-                "Failed to create worktree: {}",
-                if stderr_msg.is_empty() {
-                    "Unknown error"
-                } else {
-                    &stderr_msg
-                }
-            )));
-        }
+        self.run_gwt(&["add", branch_name], verbose, "create")?;
 
         Ok(worktree_path)
     }
 
-    pub fn has_gwt_cli(&self) -> bool {
+    pub fn has_gwt_cli() -> bool {
         Command::new("gwt")
             .arg("--version")
             .output()
@@ -192,52 +162,83 @@ impl WorktreeManager {
     }
 
     fn remove_worktree_impl(&self, branch_name: &str, verbose: bool) -> Result<()> {
-        if !self.has_gwt_cli() {
+        if !Self::has_gwt_cli() {
             return Err(MultiAiError::Worktree(
                 "gwt CLI is not installed or not in PATH".to_string(),
             ));
         }
 
+        self.run_gwt(&["remove", branch_name, "--force"], verbose, "remove")
+    }
+
+    /// Runs `gwt` in the project, streaming its stdout when `verbose`. stdin is
+    /// closed, so a gwt prompt reads EOF and fails instead of waiting for input that
+    /// never comes; a run that is still going after `GWT_TIMEOUT` is killed.
+    fn run_gwt(&self, args: &[&str], verbose: bool, action: &str) -> Result<()> {
         let mut child = Command::new("gwt")
-            .arg("remove")
-            .arg(branch_name)
-            .arg("--force")
+            .args(args)
             .current_dir(&self.project_path)
+            .stdin(Stdio::null())
             .stdout(if verbose { Stdio::piped() } else { Stdio::null() })
             .stderr(Stdio::piped())
             .spawn()
             .map_err(|e| {
-                MultiAiError::CommandFailed(format!("Failed to execute gwt remove: {}", e))
+                MultiAiError::CommandFailed(format!("Failed to execute gwt {}: {}", args[0], e))
             })?;
 
-        // Stream stdout only in verbose mode
-        if verbose {
-            if let Some(stdout) = child.stdout.take() {
-                let reader = BufReader::new(stdout);
-                for line in reader.lines().map_while(|r| r.ok()) {
+        // Read both pipes on their own threads so the wait below can time out
+        let stdout = child.stdout.take().map(|stdout| {
+            thread::spawn(move || {
+                for line in BufReader::new(stdout).lines().map_while(|r| r.ok()) {
                     println!("    {}", line);
                 }
-            }
-        }
-
-        // Wait for the process to complete and check status
-        let status = child.wait().map_err(|e| {
-            MultiAiError::CommandFailed(format!("Failed to wait for gwt remove: {}", e))
-        })?;
-
-        if !status.success() {
-            // Capture any stderr output
-            let mut stderr_msg = String::new();
-            if let Some(stderr) = child.stderr.take() {
-                let reader = BufReader::new(stderr);
-                for line in reader.lines().map_while(|r| r.ok()) {
+            })
+        });
+        let stderr = child.stderr.take().map(|stderr| {
+            thread::spawn(move || {
+                let mut stderr_msg = String::new();
+                for line in BufReader::new(stderr).lines().map_while(|r| r.ok()) {
                     stderr_msg.push_str(&line);
                     stderr_msg.push('\n');
                 }
+                stderr_msg
+            })
+        });
+
+        let deadline = Instant::now() + GWT_TIMEOUT;
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if Instant::now() >= deadline => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(MultiAiError::Worktree(format!(
+                        "gwt {} did not finish within {}s and was stopped; it may be waiting for input. Run `gwt {}` in {} to see what it needs.",
+                        args[0],
+                        GWT_TIMEOUT.as_secs(),
+                        args.join(" "),
+                        self.project_path.display()
+                    )));
+                }
+                Ok(None) => thread::sleep(GWT_POLL_INTERVAL),
+                Err(e) => {
+                    return Err(MultiAiError::CommandFailed(format!(
+                        "Failed to wait for gwt {}: {}",
+                        args[0], e
+                    )));
+                }
             }
+        };
+
+        if let Some(handle) = stdout {
+            let _ = handle.join();
+        }
+        let stderr_msg = stderr.and_then(|h| h.join().ok()).unwrap_or_default();
 
+        if !status.success() {
             return Err(MultiAiError::Worktree(format!(
-                "Failed to remove worktree: {}",
+                "Failed to {} worktree: {}",
+                action,
                 if stderr_msg.is_empty() {
                     "Unknown error"
                 } else {
@@ -249,6 +250,49 @@ impl WorktreeManager {
         Ok(())
     }
 
+    /// Moves a worktree with `git worktree move`, which also updates git's
+    /// metadata for it. The destination must not exist yet.
+    pub fn move_worktree(&self, from: &Path, to: &Path) -> Result<()> {
+        let output = Command::new("git")
+            .args(["worktree", "move"])
+            .arg(from)
+            .arg(to)
+            .current_dir(&self.project_path)
+            .output()
+            .map_err(|e| {
+                MultiAiError::CommandFailed(format!("Failed to execute git worktree move: {}", e))
+            })?;
+
+        if !output.status.success() {
+            return Err(MultiAiError::Worktree(format!(
+                "Failed to move worktree: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Clears stale worktree metadata (e.g. left by an interrupted `gwt add`).
+    pub fn prune_worktrees(&self) -> Result<()> {
+        let output = Command::new("git")
+            .args(["worktree", "prune"])
+            .current_dir(&self.project_path)
+            .output()
+            .map_err(|e| {
+                MultiAiError::CommandFailed(format!("Failed to execute git worktree prune: {}", e))
+            })?;
+
+        if !output.status.success() {
+            return Err(MultiAiError::Worktree(format!(
+                "Failed to prune worktrees: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(())
+    }
+
     pub fn is_gwt_project(&self) -> bool {
         // Check if git-worktree-config.jsonc exists in current directory
         let gwt_config_jsonc = self.project_path.join("git-worktree-config.jsonc");